@@ -0,0 +1,64 @@
+import gobley.gradle.GobleyHost
+import gobley.gradle.cargo.dsl.jvm
+import gobley.gradle.uniffi.tasks.InstallUniffiBindgenTask
+import org.gradle.accessors.dm.*
+
+// Runs the tests of the fixture of the same name in `tests/uniffi` against the `jvm-ffm` bindings
+// configured in the `uniffi.toml` of this project.
+plugins {
+    kotlin("multiplatform")
+    kotlin("plugin.atomicfu")
+    id("dev.gobley.cargo")
+    id("dev.gobley.uniffi")
+}
+
+// https://github.com/gradle/gradle/issues/15383
+apply<VersionCatalogPlugin>()
+val libs = extensions.getByName("libs") as LibrariesForLibs
+
+val fixtureDirectory: Directory = rootProject.layout.projectDirectory.dir("tests/uniffi/$name")
+
+cargo {
+    packageDirectory.set(fixtureDirectory)
+    builds.jvm {
+        embedRustLibrary.set(rustTarget == GobleyHost.current.rustTarget)
+    }
+}
+
+uniffi {
+    bindgenFromPath(rootProject.layout.projectDirectory.dir("crates/gobley-uniffi-bindgen"))
+    generateFromLibrary {
+        namespace = name.replace('-', '_')
+        config.set(layout.projectDirectory.file("uniffi.toml"))
+    }
+}
+
+kotlin {
+    // Older than Java 22, so the toolchain the UniFFI plugin uses for `jvm-ffm` is tested as well.
+    jvmToolchain(17)
+    jvm()
+    sourceSets {
+        commonTest {
+            kotlin.srcDir(fixtureDirectory.dir("src/commonTest/kotlin"))
+            dependencies {
+                implementation(kotlin("test"))
+                implementation(libs.kotlinx.coroutines.test)
+                implementation(libs.kotest.assertions.core)
+            }
+        }
+        jvmTest {
+            kotlin.srcDir(fixtureDirectory.dir("src/jvmTest/kotlin"))
+        }
+    }
+}
+
+tasks.withType<Test> {
+    useJUnitPlatform()
+    reports {
+        junitXml.required.set(true)
+    }
+}
+
+tasks.withType<InstallUniffiBindgenTask> {
+    quiet = false
+}
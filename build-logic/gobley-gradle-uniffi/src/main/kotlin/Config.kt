@@ -10,6 +10,8 @@ import kotlinx.serialization.SerialName
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.decodeFromString
 import net.peanuuutz.tomlkt.Toml
+import net.peanuuutz.tomlkt.TomlElement
+import net.peanuuutz.tomlkt.TomlTable
 import java.io.File
 
 /**
@@ -38,6 +40,18 @@ internal data class Config(
     @SerialName("jvm_dynamic_library_dependencies") val jvmDynamicLibraryDependencies: List<String>? = null,
     @SerialName("android_dynamic_library_dependencies") val androidDynamicLibraryDependencies: List<String>? = null,
     @SerialName("dynamic_library_dependencies") val dynamicLibraryDependencies: List<String>? = null,
+    @SerialName("native_dynamic_library_dependencies") val nativeDynamicLibraryDependencies: List<String>? = null,
+    @SerialName("kotlin_source_sets") val kotlinSourceSets: Map<String, String>? = null,
+    @SerialName("timestamp_type") val timestampType: String? = null,
+    @SerialName("generate_unknown_enum_variants") val generateUnknownEnumVariants: TomlElement? = null,
+    @SerialName("errors") val errors: Map<String, Error>? = null,
+    @SerialName("cinterop") val cinterop: Cinterop? = null,
+    @SerialName("pool_rust_buffers") val poolRustBuffers: Boolean? = null,
+    @SerialName("track_object_leaks") val trackObjectLeaks: Boolean? = null,
+    @SerialName("instrument_calls") val instrumentCalls: Boolean? = null,
+    @SerialName("suspend_sync_calls") val suspendSyncCalls: List<String>? = null,
+    @SerialName("suspend_dispatcher") val suspendDispatcher: String? = null,
+    @SerialName("generate_completable_futures") val generateCompletableFutures: Boolean? = null,
 ) {
     @Serializable
     internal data class CustomType(
@@ -45,6 +59,27 @@ internal data class Config(
         @SerialName("type_name") val typeName: String? = null,
         @SerialName("lift") val lift: String? = null,
         @SerialName("lower") val lower: String? = null,
+        @SerialName("preset") val preset: String? = null,
+        @SerialName("jvm") val jvm: CustomType? = null,
+        @SerialName("android") val android: CustomType? = null,
+        @SerialName("native") val native: CustomType? = null,
+    )
+
+    @Serializable
+    internal data class Error(
+        @SerialName("superclass") val superclass: String? = null,
+        @SerialName("interfaces") val interfaces: List<String>? = null,
+        @SerialName("jvm") val jvm: Error? = null,
+        @SerialName("android") val android: Error? = null,
+        @SerialName("native") val native: Error? = null,
+    )
+
+    @Serializable
+    internal data class Cinterop(
+        @SerialName("static_libraries") val staticLibraries: List<String>? = null,
+        @SerialName("library_paths") val libraryPaths: List<String>? = null,
+        @SerialName("compiler_opts") val compilerOpts: List<String>? = null,
+        @SerialName("linker_opts") val linkerOpts: List<String>? = null,
     )
 
     companion object {
@@ -57,4 +92,64 @@ internal data class Config(
 
 internal fun Config(file: File): Config {
     return Config.toml.decodeFromString<Config>(file.readText(Charsets.UTF_8))
+}
+
+/**
+ * The name of the file containing the workspace-level defaults. Must be synced with
+ * `WORKSPACE_CONFIG_FILE_NAME` in the bindgen.
+ */
+internal const val WORKSPACE_CONFIG_FILE_NAME = "gobley.toml"
+
+/**
+ * Reads the configuration in [file] merged on top of the workspace-level defaults in
+ * [workspaceConfig], as the bindgen does. Must be synced with `WorkspaceConfig::merge` in the
+ * bindgen: tables are merged key by key, while other values including arrays are replaced.
+ */
+internal fun Config(file: File?, workspaceConfig: File?): Config {
+    val table = readWorkspaceConfigTable(workspaceConfig).toMutableMap()
+    if (file != null) {
+        mergeTables(table, Config.toml.parseToTomlTable(file.readText(Charsets.UTF_8)))
+    }
+    return Config.toml.decodeFromTomlElement(Config.serializer(), TomlTable(table))
+}
+
+/**
+ * Finds the workspace-level defaults like `WorkspaceConfig::find` in the bindgen: the nearest
+ * `gobley.toml` from [packageRoot] up to [workspaceRoot], or the workspace `Cargo.toml` when its
+ * `[workspace.metadata.gobley]` table is not empty.
+ */
+internal fun findWorkspaceConfig(packageRoot: File, workspaceRoot: File): File? {
+    var directory: File? = packageRoot
+    while (directory != null) {
+        val configFile = directory.resolve(WORKSPACE_CONFIG_FILE_NAME)
+        if (configFile.isFile) return configFile
+        if (directory == workspaceRoot) break
+        directory = directory.parentFile
+    }
+    val manifestFile = workspaceRoot.resolve("Cargo.toml")
+    return manifestFile.takeIf { it.isFile && readWorkspaceConfigTable(it).isNotEmpty() }
+}
+
+/**
+ * Reads a `gobley.toml` file, or the `[workspace.metadata.gobley]` table when [file] is a
+ * `Cargo.toml`.
+ */
+private fun readWorkspaceConfigTable(file: File?): Map<String, TomlElement> {
+    if (file == null) return emptyMap()
+    val table = Config.toml.parseToTomlTable(file.readText(Charsets.UTF_8))
+    if (file.name != "Cargo.toml") return table
+    val workspace = table["workspace"] as? TomlTable
+    val metadata = workspace?.get("metadata") as? TomlTable
+    return metadata?.get("gobley") as? TomlTable ?: emptyMap()
+}
+
+private fun mergeTables(base: MutableMap<String, TomlElement>, overrides: Map<String, TomlElement>) {
+    for ((key, value) in overrides) {
+        val baseValue = base[key]
+        base[key] = if (baseValue is TomlTable && value is TomlTable) {
+            TomlTable(baseValue.toMutableMap().also { mergeTables(it, value) })
+        } else {
+            value
+        }
+    }
 }
\ No newline at end of file
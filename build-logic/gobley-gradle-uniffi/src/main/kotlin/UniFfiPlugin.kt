@@ -11,11 +11,13 @@ import gobley.gradle.InternalGobleyGradleApi
 import gobley.gradle.PluginIds
 import gobley.gradle.Variant
 import gobley.gradle.android.GobleyAndroidExtensionDelegate
+import gobley.gradle.cargo.dsl.CargoAndroidBuild
 import gobley.gradle.cargo.dsl.CargoExtension
 import gobley.gradle.cargo.dsl.CargoJvmBuild
 import gobley.gradle.cargo.dsl.CargoNativeBuild
 import gobley.gradle.kotlin.GobleyKotlinExtensionDelegate
 import gobley.gradle.rust.CrateType
+import gobley.gradle.rust.targets.RustAndroidTarget
 import gobley.gradle.rust.targets.RustTarget
 import gobley.gradle.rust.targets.RustWasmTarget
 import gobley.gradle.uniffi.dsl.BindingsGeneration
@@ -39,12 +41,16 @@ import org.gradle.api.file.RegularFile
 import org.gradle.api.provider.Provider
 import org.gradle.api.tasks.Delete
 import org.gradle.api.tasks.TaskProvider
+import org.gradle.api.tasks.testing.Test
 import org.gradle.jvm.tasks.Jar
+import org.gradle.jvm.toolchain.JavaLanguageVersion
+import org.gradle.jvm.toolchain.JavaToolchainService
 import org.gradle.kotlin.dsl.create
 import org.gradle.kotlin.dsl.getByType
 import org.gradle.kotlin.dsl.named
 import org.gradle.kotlin.dsl.register
 import org.gradle.kotlin.dsl.withType
+import org.jetbrains.kotlin.gradle.dsl.KotlinMultiplatformExtension
 import org.jetbrains.kotlin.gradle.plugin.KotlinPlatformType
 import org.jetbrains.kotlin.gradle.plugin.KotlinSourceSet
 import org.jetbrains.kotlin.gradle.plugin.KotlinTarget
@@ -55,13 +61,23 @@ import org.jetbrains.kotlin.gradle.plugin.mpp.KotlinWithJavaTarget
 import org.jetbrains.kotlin.gradle.targets.jvm.KotlinJvmTarget
 import org.jetbrains.kotlin.gradle.tasks.CInteropProcess
 import org.jetbrains.kotlin.gradle.tasks.KotlinCompilationTask
+import org.jetbrains.kotlin.gradle.tasks.UsesKotlinJavaToolchain
+import java.io.File
 
 private const val TASK_GROUP = "uniffi"
 
+// The first Java version where the Foreign Function & Memory API is not a preview feature.
+private const val JVM_FFM_JAVA_VERSION = 22
+
+// Must be synced with `JNI_SHIM_DIR_ENV` in the bindgen.
+private const val JNI_SHIM_DIRECTORY_ENVIRONMENT_VARIABLE = "GOBLEY_UNIFFI_JNI_SHIM_DIR"
+
 class UniFfiPlugin : Plugin<Project> {
     private lateinit var uniFfiExtension: UniFfiExtension
     private lateinit var bindingsGeneration: BindingsGeneration
     private lateinit var cargoExtension: CargoExtension
+    private lateinit var originalConfig: Config
+    private var workspaceConfig: File? = null
 
     @OptIn(InternalGobleyGradleApi::class)
     private lateinit var kotlinExtensionDelegate: GobleyKotlinExtensionDelegate
@@ -127,6 +143,20 @@ class UniFfiPlugin : Plugin<Project> {
             androidDelegate = delegate
         }
 
+        // The Kotlin source sets and the Cargo builds are configured before the bindgen runs, so
+        // the options affecting them are read from the original configuration file, merged on top
+        // of the workspace-level defaults like the bindgen does.
+        workspaceConfig = cargoExtension.cargoPackage.get().let {
+            findWorkspaceConfig(it.root.asFile, it.workspaceRoot.asFile)
+        }
+        originalConfig = Config(
+            bindingsGeneration.config
+                .orElse(cargoExtension.packageDirectory.file("uniffi.toml"))
+                .get().asFile
+                .takeIf { it.exists() },
+            workspaceConfig,
+        )
+
         bindingsGeneration.namespace.convention(cargoExtension.cargoPackage.map { it.libraryCrateName })
         (bindingsGeneration as? BindingsGenerationFromUdl)?.udlFile?.convention(
             cargoExtension.cargoPackage.map {
@@ -150,12 +180,32 @@ class UniFfiPlugin : Plugin<Project> {
         }
     }
 
+    private val kotlinSourceSets: Map<String, String>
+        get() = originalConfig.kotlinSourceSets.orEmpty()
+
+    /**
+     * Whether the Android bindings use the JNI shim, which is generated with the bindings and
+     * compiled into the Android libraries.
+     */
+    private val usesAndroidJni: Boolean
+        get() = originalConfig.kotlinTargets.orEmpty().contains("android-jni")
+
+    /**
+     * Whether the JVM bindings use the Foreign Function & Memory API instead of JNA.
+     */
+    private val usesJvmFfm: Boolean
+        get() = originalConfig.kotlinTargets.orEmpty().contains("jvm-ffm")
+
     private fun Project.configureBindingTasks() {
         val bindingsGeneration = bindingsGeneration
 
         val buildRustTarget = bindingsGeneration.build.orNull ?: run {
+            // The Android libraries can't be built before the JNI shim is generated.
             @OptIn(InternalGobleyGradleApi::class)
-            val androidTargetsToBuild = cargoExtension.androidTargetsToBuild.get().toList()
+            val androidTargetsToBuild = when {
+                usesAndroidJni -> emptyList()
+                else -> cargoExtension.androidTargetsToBuild.get().toList()
+            }
 
             @OptIn(InternalGobleyGradleApi::class)
             val hasJvmTarget = kotlinExtensionDelegate.targets.any {
@@ -180,7 +230,13 @@ class UniFfiPlugin : Plugin<Project> {
                 RustTarget(nativeTarget.konanTarget)
             }
 
-            (androidTargetsToBuild + jvmTargetsToBuild + nativeTargetsToBuild).first()
+            (androidTargetsToBuild + jvmTargetsToBuild + nativeTargetsToBuild).firstOrNull()
+                ?: throw GradleException(
+                    when {
+                        usesAndroidJni -> "The android-jni target needs a JVM or Kotlin/Native build to generate the bindings from"
+                        else -> "No Cargo build available for UniFFI"
+                    }
+                )
         }
 
         if (buildRustTarget is RustWasmTarget) {
@@ -190,6 +246,10 @@ class UniFfiPlugin : Plugin<Project> {
         val build = cargoExtension.builds.findByRustTarget(buildRustTarget)
             ?: throw GradleException("Cargo build for $buildRustTarget not available")
 
+        if (usesAndroidJni && buildRustTarget is RustAndroidTarget) {
+            throw GradleException("$buildRustTarget can't be used to generate the bindings of the android-jni target, since the JNI shim is compiled into the Android libraries. Try building with other targets.")
+        }
+
         val availableVariants = build.kotlinTargets.flatMap {
             when (it) {
                 is KotlinJvmTarget, is KotlinWithJavaTarget<*, *> -> listOf((build as CargoJvmBuild<*>).jvmVariant.get())
@@ -234,6 +294,7 @@ class UniFfiPlugin : Plugin<Project> {
                     regularFile.takeIf { it.asFile.exists() }
                 }
             )
+            workspaceConfig.set(this@UniFfiPlugin.workspaceConfig)
 
             crateName.set(cargoExtension.cargoPackage.map { it.libraryCrateName })
             packageRoot.set(cargoExtension.cargoPackage.map { it.root.asFile.path })
@@ -301,6 +362,7 @@ class UniFfiPlugin : Plugin<Project> {
                 formatCode.set(uniFfiExtension.formatCode.get())
 
             config.set(mergeUniffiConfig.flatMap { it.outputConfig })
+            workspaceConfig.set(this@UniFfiPlugin.workspaceConfig)
 
             if (externalPackageUniFfiConfigurations != null) {
                 externalPackageConfigs.addAll(externalPackageUniFfiConfigurations)
@@ -320,6 +382,24 @@ class UniFfiPlugin : Plugin<Project> {
             dependsOn(cargoBuildTaskForBindings, installBindgen, mergeUniffiConfig)
         }
 
+        if (usesAndroidJni) {
+            // Passes the generated shims to `gobley_uniffi_bindgen::include_jni_shim` in the build
+            // script of the crate.
+            val jniShimDirectory = bindingsDirectory.map { it.dir("jni") }
+            cargoExtension.builds.withType<CargoAndroidBuild>().configureEach {
+                variants {
+                    buildTaskProvider.configure {
+                        additionalEnvironment.put(
+                            JNI_SHIM_DIRECTORY_ENVIRONMENT_VARIABLE,
+                            jniShimDirectory.map { it.asFile.path },
+                        )
+                        inputs.dir(jniShimDirectory)
+                        dependsOn(buildBindings)
+                    }
+                }
+            }
+        }
+
         if (uniFfiExtension.generateDuringSync.get()) {
             @OptIn(InternalGobleyGradleApi::class)
             GradleUtils.runTaskDuringSync(this, buildBindings)
@@ -373,16 +453,31 @@ class UniFfiPlugin : Plugin<Project> {
         }
 
         val dummyDefFile = nativeBindingsCInteropDef("dummy")
+        val generatedDefFile = bindingsGeneration.namespace.flatMap {
+            nativeBindingsCInteropGeneratedDef(it)
+        }
         val generateDummyDefFileTask = tasks.register("generateDummyDefFile") {
+            // The generated .def file is written by the bindgen, so it must run first.
+            dependsOn(tasks.named("buildUniffiBindings"))
+            inputs.file(generatedDefFile).optional()
+            outputs.file(dummyDefFile)
             doLast {
+                // Linking the Rust library is handled by CargoPlugin, so only the additional linker
+                // options are taken from the .def file generated by the bindgen.
+                val linkerOpts = generatedDefFile.get().asFile
+                    .takeIf { it.exists() }
+                    ?.readLines(Charsets.UTF_8)
+                    ?.filter { it.startsWith("linkerOpts") }
+                    .orEmpty()
                 dummyDefFile.get().asFile.run {
                     parentFile.mkdirs()
-                    writeBytes(byteArrayOf())
+                    writeText(linkerOpts.joinToString("") { "$it\n" }, Charsets.UTF_8)
                 }
             }
-            mustRunAfter(tasks.named("buildUniffiBindings"))
         }
 
+        val jvmCommonSourceSet = configureKotlinJvmCommonSourceSet()
+
         @OptIn(InternalGobleyGradleApi::class)
         kotlinExtensionDelegate.targets.configureEach {
             when (this) {
@@ -391,14 +486,14 @@ class UniFfiPlugin : Plugin<Project> {
                     if (kotlinExtensionDelegate.pluginId == PluginIds.KOTLIN_JVM) {
                         configureKotlinCommonTarget()
                     }
-                    configureKotlinJvmTarget()
+                    configureKotlinJvmTarget(this, jvmCommonSourceSet)
                 }
 
                 is KotlinAndroidTarget -> {
                     if (kotlinExtensionDelegate.pluginId == PluginIds.KOTLIN_ANDROID) {
                         configureKotlinCommonTarget()
                     }
-                    configureKotlinAndroidTarget()
+                    configureKotlinAndroidTarget(jvmCommonSourceSet)
                 }
 
                 is KotlinNativeTarget -> configureKotlinNativeTarget(
@@ -412,13 +507,61 @@ class UniFfiPlugin : Plugin<Project> {
         }
     }
 
+    /**
+     * The name of the source set the bindgen writes the bindings of [target] to, as a key of
+     * `kotlin_source_sets`. Must be synced with `Config::source_set_name` in the bindgen.
+     */
+    private fun bindingsSourceSetName(target: String): String {
+        return kotlinSourceSets[target] ?: "${target}Main"
+    }
+
+    /**
+     * The Kotlin source set the bindings of [target] are compiled in. When `kotlin_source_sets`
+     * names a source set declared in the project, e.g. `appleMain`, the bindings are added there
+     * instead of [default].
+     */
+    @OptIn(InternalGobleyGradleApi::class)
+    private fun bindingsSourceSet(target: String, default: KotlinSourceSet): KotlinSourceSet {
+        if (kotlinExtensionDelegate.pluginId != PluginIds.KOTLIN_MULTIPLATFORM) return default
+        val sourceSetName = kotlinSourceSets[target] ?: return default
+        return kotlinExtensionDelegate.sourceSets.findByName(sourceSetName) ?: default
+    }
+
+    /**
+     * Creates the intermediate source set shared by the JVM and Android source sets when
+     * `kotlin_source_sets.jvm_common` is set.
+     */
+    @OptIn(InternalGobleyGradleApi::class)
+    private fun Project.configureKotlinJvmCommonSourceSet(): KotlinSourceSet? {
+        if (kotlinExtensionDelegate.pluginId != PluginIds.KOTLIN_MULTIPLATFORM) return null
+        val sourceSetName = kotlinSourceSets["jvm_common"] ?: return null
+        val hasJvmOrAndroidTarget = kotlinExtensionDelegate.targets.any {
+            it is KotlinJvmTarget || it is KotlinAndroidTarget
+        }
+        if (!hasJvmOrAndroidTarget) return null
+
+        val kotlinMultiplatformExtension = extensions.getByType<KotlinMultiplatformExtension>()
+        return kotlinMultiplatformExtension.sourceSets.maybeCreate(sourceSetName).apply {
+            dependsOn(kotlinExtensionDelegate.sourceSets.commonMain)
+            kotlin.srcDir(sourceSetBindingsDirectory(sourceSetName))
+            if (uniFfiExtension.addDependencies.get()) {
+                dependencies {
+                    // The JVM and Android source sets add JNA in the format of each platform.
+                    compileOnly("net.java.dev.jna:jna") {
+                        version { prefer(DependencyVersions.JNA) }
+                    }
+                }
+            }
+        }
+    }
+
     @OptIn(InternalGobleyGradleApi::class)
     private fun Project.configureKotlinCommonTarget() {
-        with(kotlinExtensionDelegate.sourceSets.commonMain) {
+        with(bindingsSourceSet("common", kotlinExtensionDelegate.sourceSets.commonMain)) {
             kotlin.srcDir(
                 when (kotlinExtensionDelegate.pluginId) {
                     PluginIds.KOTLIN_ANDROID, PluginIds.KOTLIN_JVM -> mainBindingsDirectory
-                    else -> commonBindingsDirectory
+                    else -> sourceSetBindingsDirectory(bindingsSourceSetName("common"))
                 }
             )
             // Android Studio doesn't recognize directories added using the above method. See #79.
@@ -442,12 +585,18 @@ class UniFfiPlugin : Plugin<Project> {
     }
 
     @OptIn(InternalGobleyGradleApi::class)
-    private fun Project.configureKotlinJvmTarget() {
-        with(kotlinExtensionDelegate.sourceSets.jvmMain) {
+    private fun Project.configureKotlinJvmTarget(
+        kotlinTarget: KotlinTarget,
+        jvmCommonSourceSet: KotlinSourceSet?,
+    ) {
+        with(bindingsSourceSet("jvm", kotlinExtensionDelegate.sourceSets.jvmMain)) {
             if (kotlinExtensionDelegate.pluginId == PluginIds.KOTLIN_MULTIPLATFORM) {
-                kotlin.srcDir(jvmBindingsDirectory)
+                kotlin.srcDir(sourceSetBindingsDirectory(bindingsSourceSetName("jvm")))
             }
-            if (uniFfiExtension.addDependencies.get()) {
+            if (jvmCommonSourceSet != null) {
+                dependsOn(jvmCommonSourceSet)
+            }
+            if (uniFfiExtension.addDependencies.get() && !usesJvmFfm) {
                 dependencies {
                     implementation("net.java.dev.jna:jna") {
                         version { prefer(DependencyVersions.JNA) }
@@ -455,18 +604,62 @@ class UniFfiPlugin : Plugin<Project> {
                 }
             }
         }
+        if (usesJvmFfm) {
+            configureJvmFfm(kotlinTarget)
+        }
+    }
+
+    /**
+     * The `jvm-ffm` bindings use the Foreign Function & Memory API, which is final since Java 22.
+     * Compiles and tests the JVM target with a Java 22 toolchain when the one of the project is
+     * older, and allows the tests to call native code.
+     */
+    private fun Project.configureJvmFfm(kotlinTarget: KotlinTarget) {
+        val javaToolchains = extensions.getByType<JavaToolchainService>()
+        val ffmJavaLauncher = javaToolchains.launcherFor {
+            languageVersion.set(JavaLanguageVersion.of(JVM_FFM_JAVA_VERSION))
+        }
+        kotlinTarget.compilations.configureEach {
+            compileTaskProvider.configure {
+                if (this is UsesKotlinJavaToolchain) {
+                    val javaVersion = kotlinJavaToolchain.javaVersion.get()
+                    if (javaVersion.majorVersion.toInt() < JVM_FFM_JAVA_VERSION) {
+                        kotlinJavaToolchain.toolchain.use(ffmJavaLauncher)
+                    }
+                }
+            }
+        }
+
+        @OptIn(InternalGobleyGradleApi::class)
+        val testTaskName = when (kotlinExtensionDelegate.pluginId) {
+            PluginIds.KOTLIN_MULTIPLATFORM -> "${kotlinTarget.name}Test"
+            else -> "test"
+        }
+        tasks.withType<Test>().configureEach {
+            if (name != testTaskName) return@configureEach
+            val javaVersion = javaLauncher.orNull?.metadata?.languageVersion?.asInt()
+            if (javaVersion == null || javaVersion < JVM_FFM_JAVA_VERSION) {
+                javaLauncher.set(ffmJavaLauncher)
+            }
+            jvmArgs("--enable-native-access=ALL-UNNAMED")
+        }
     }
 
     @OptIn(InternalGobleyGradleApi::class)
-    private fun Project.configureKotlinAndroidTarget() {
-        with(kotlinExtensionDelegate.sourceSets.androidMain) {
+    private fun Project.configureKotlinAndroidTarget(jvmCommonSourceSet: KotlinSourceSet?) {
+        with(bindingsSourceSet("android", kotlinExtensionDelegate.sourceSets.androidMain)) {
             if (kotlinExtensionDelegate.pluginId == PluginIds.KOTLIN_MULTIPLATFORM) {
-                kotlin.srcDir(androidBindingsDirectory)
+                kotlin.srcDir(sourceSetBindingsDirectory(bindingsSourceSetName("android")))
+            }
+            if (jvmCommonSourceSet != null) {
+                dependsOn(jvmCommonSourceSet)
             }
             if (uniFfiExtension.addDependencies.get()) {
                 dependencies {
-                    implementation("net.java.dev.jna:jna@aar") {
-                        version { prefer(DependencyVersions.JNA) }
+                    if (!usesAndroidJni) {
+                        implementation("net.java.dev.jna:jna@aar") {
+                            version { prefer(DependencyVersions.JNA) }
+                        }
                     }
                     implementation("androidx.annotation:annotation") {
                         version { prefer(DependencyVersions.KOTLINX_COROUTINES) }
@@ -518,9 +711,9 @@ class UniFfiPlugin : Plugin<Project> {
                     dependsOn(generateDummyDefFileTask)
                 }
             }
-            defaultSourceSet {
-                kotlin.srcDir(nativeBindingsDirectory)
-            }
+            bindingsSourceSet("native", defaultSourceSet).kotlin.srcDir(
+                sourceSetBindingsDirectory(bindingsSourceSetName("native"))
+            )
             compileTaskProvider.configure {
                 compilerOptions.optIn.add("kotlinx.cinterop.ExperimentalForeignApi")
             }
@@ -528,9 +721,10 @@ class UniFfiPlugin : Plugin<Project> {
     }
 
     private fun Project.configureUnsupportedTarget(kotlinTarget: KotlinTarget) {
-        kotlinTarget.compilations.getByName("main").defaultSourceSet {
-            kotlin.srcDir(stubBindingsDirectory)
-        }
+        val defaultSourceSet = kotlinTarget.compilations.getByName("main").defaultSourceSet
+        bindingsSourceSet("stub", defaultSourceSet).kotlin.srcDir(
+            sourceSetBindingsDirectory(bindingsSourceSetName("stub"))
+        )
     }
 }
 
@@ -540,20 +734,8 @@ private val Project.bindingsDirectory: Provider<Directory>
 private val Project.mainBindingsDirectory: Provider<Directory>
     get() = bindingsDirectory.map { it.dir("main/kotlin") }
 
-private val Project.commonBindingsDirectory: Provider<Directory>
-    get() = bindingsDirectory.map { it.dir("commonMain/kotlin") }
-
-private val Project.jvmBindingsDirectory: Provider<Directory>
-    get() = bindingsDirectory.map { it.dir("jvmMain/kotlin") }
-
-private val Project.androidBindingsDirectory: Provider<Directory>
-    get() = bindingsDirectory.map { it.dir("androidMain/kotlin") }
-
-private val Project.nativeBindingsDirectory: Provider<Directory>
-    get() = bindingsDirectory.map { it.dir("nativeMain/kotlin") }
-
-private val Project.stubBindingsDirectory: Provider<Directory>
-    get() = bindingsDirectory.map { it.dir("stubMain/kotlin") }
+private fun Project.sourceSetBindingsDirectory(sourceSetName: String): Provider<Directory> =
+    bindingsDirectory.map { it.dir("$sourceSetName/kotlin") }
 
 private val Project.androidGeneratedProguardFile: Provider<RegularFile>
     get() = bindingsDirectory.map { it.file("androidMain/generated-proguard-rules.txt") }
@@ -570,6 +752,9 @@ private fun Project.nativeBindingsCInteropDef(libraryCrateName: String): Provide
 private fun Project.nativeBindingsCInteropHeader(namespace: String): Provider<RegularFile> =
     nativeBindingsCInteropDirectory.map { it.file("headers/$namespace/$namespace.h") }
 
+private fun Project.nativeBindingsCInteropGeneratedDef(namespace: String): Provider<RegularFile> =
+    nativeBindingsCInteropDirectory.map { it.file("headers/$namespace/$namespace.def") }
+
 private fun KotlinSourceSet.getConflictingDependency(
     dependencyNotation: String,
 ): ExternalModuleDependency? {
@@ -47,6 +47,14 @@ abstract class BuildUniffiBindingsTask : CargoPackageTask() {
     @get:PathSensitive(PathSensitivity.RELATIVE)
     abstract val config: RegularFileProperty
 
+    /**
+     * Path to the optional workspace-level configuration file.
+     */
+    @get:InputFile
+    @get:Optional
+    @get:PathSensitive(PathSensitivity.RELATIVE)
+    abstract val workspaceConfig: RegularFileProperty
+
     /**
      * Paths to the optional uniffi config files.
      */
@@ -122,6 +130,9 @@ abstract class BuildUniffiBindingsTask : CargoPackageTask() {
                     }
                 }
             }
+            if (workspaceConfig.isPresent) {
+                arguments("--workspace-config", workspaceConfig.get())
+            }
             if (externalPackageConfigs.isPresent) {
                 for (packageConfigFile in externalPackageConfigs.get()) {
                     val config = Config(packageConfigFile)
@@ -33,6 +33,14 @@ abstract class MergeUniffiConfigTask : DefaultTask() {
     @get:PathSensitive(PathSensitivity.RELATIVE)
     abstract val originalConfig: RegularFileProperty
 
+    /**
+     * The workspace-level defaults the original configuration is merged on top of.
+     */
+    @get:InputFile
+    @get:Optional
+    @get:PathSensitive(PathSensitivity.RELATIVE)
+    abstract val workspaceConfig: RegularFileProperty
+
     @get:Input
     @get:Optional
     abstract val crateName: Property<String>
@@ -107,7 +115,7 @@ abstract class MergeUniffiConfigTask : DefaultTask() {
 
     @TaskAction
     fun mergeConfig() {
-        val originalConfig = originalConfig.orNull?.asFile?.let(::Config) ?: Config()
+        val originalConfig = Config(originalConfig.orNull?.asFile, workspaceConfig.orNull?.asFile)
         val result = originalConfig.copy(
             // Properties read by the Gradle plugins
             crateName = crateName.orNull,
@@ -0,0 +1,204 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+//! Code snippets of the `jvm-ffm` target, which passes FFI values to method handles of the Java
+//! Foreign Function & Memory API.
+//!
+//! Method handles only take primitives and `MemorySegment`s, the carriers of FFI values. The
+//! Kotlin types of FFI values are the same as with JNA, so that the shared templates work
+//! unchanged, and are converted to and from their carriers around every call.
+
+use uniffi_bindgen::interface::{ComponentInterface, FfiType};
+
+use super::KotlinCodeOracle;
+
+const MEMORY_SEGMENT: &str = "java.lang.foreign.MemorySegment";
+const NULL_SEGMENT: &str = "java.lang.foreign.MemorySegment.NULL";
+const ADDRESS_LAYOUT: &str = "java.lang.foreign.ValueLayout.ADDRESS";
+
+/// The name of the external `RustBuffer` of `ffi_type`, if it is a `RustBuffer` of another crate.
+fn external_rust_buffer_name<'a>(
+    ffi_type: &'a FfiType,
+    ci: &ComponentInterface,
+) -> Option<&'a str> {
+    match ffi_type {
+        FfiType::RustBuffer(Some(metadata)) if metadata.module_path != ci.crate_name() => {
+            Some(&metadata.name)
+        }
+        _ => None,
+    }
+}
+
+/// The `MemoryLayout` of `ffi_type` in function descriptors and FFI structs.
+pub(super) fn layout(ffi_type: &FfiType) -> String {
+    let layout = match ffi_type {
+        FfiType::Int8 | FfiType::UInt8 => "java.lang.foreign.ValueLayout.JAVA_BYTE",
+        FfiType::Int16 | FfiType::UInt16 => "java.lang.foreign.ValueLayout.JAVA_SHORT",
+        FfiType::Int32 | FfiType::UInt32 => "java.lang.foreign.ValueLayout.JAVA_INT",
+        FfiType::Int64 | FfiType::UInt64 | FfiType::Handle => {
+            "java.lang.foreign.ValueLayout.JAVA_LONG"
+        }
+        FfiType::Float32 => "java.lang.foreign.ValueLayout.JAVA_FLOAT",
+        FfiType::Float64 => "java.lang.foreign.ValueLayout.JAVA_DOUBLE",
+        FfiType::RustArcPtr(_)
+        | FfiType::VoidPointer
+        | FfiType::Callback(_)
+        | FfiType::Reference(_)
+        | FfiType::MutReference(_) => ADDRESS_LAYOUT,
+        FfiType::RustBuffer(_) => "UniffiRustBufferLayout",
+        FfiType::ForeignBytes => "UniffiForeignBytesLayout",
+        FfiType::RustCallStatus => "UniffiRustCallStatusLayout",
+        FfiType::Struct(name) => {
+            return format!("{}.LAYOUT", KotlinCodeOracle.ffi_struct_name(name))
+        }
+    };
+    layout.to_owned()
+}
+
+/// The Kotlin type passing `ffi_type` to and from method handles.
+pub(super) fn carrier(ffi_type: &FfiType) -> &'static str {
+    match ffi_type {
+        FfiType::Int8 | FfiType::UInt8 => "Byte",
+        FfiType::Int16 | FfiType::UInt16 => "Short",
+        FfiType::Int32 | FfiType::UInt32 => "Int",
+        FfiType::Int64 | FfiType::UInt64 | FfiType::Handle => "Long",
+        FfiType::Float32 => "Float",
+        FfiType::Float64 => "Double",
+        _ => MEMORY_SEGMENT,
+    }
+}
+
+/// Whether `ffi_type` is a struct passed by value, which is copied to memory allocated by the
+/// caller.
+pub(super) fn is_by_value_struct(ffi_type: &FfiType) -> bool {
+    matches!(
+        ffi_type,
+        FfiType::RustBuffer(_)
+            | FfiType::ForeignBytes
+            | FfiType::RustCallStatus
+            | FfiType::Struct(_)
+    )
+}
+
+/// The carrier of `value`, whose Kotlin type is the one of `ffi_type`. Structs passed by value are
+/// allocated with `uniffiArena`.
+pub(super) fn lower(ffi_type: &FfiType, value: &str, ci: &ComponentInterface) -> String {
+    match ffi_type {
+        FfiType::RustArcPtr(_) => format!("({value} ?: {NULL_SEGMENT})"),
+        FfiType::RustBuffer(_) => match external_rust_buffer_name(ffi_type, ci) {
+            Some(name) => format!("{value}.from{name}ToLocal().uniffiToSegment(uniffiArena)"),
+            None => format!("{value}.uniffiToSegment(uniffiArena)"),
+        },
+        FfiType::ForeignBytes | FfiType::RustCallStatus | FfiType::Struct(_) => {
+            format!("{value}.uniffiToSegment(uniffiArena)")
+        }
+        FfiType::Callback(_) => format!("{value}.uniffiUpcallStub()"),
+        FfiType::Reference(_) | FfiType::MutReference(_) => format!("{value}.uniffiSegment"),
+        _ => value.to_owned(),
+    }
+}
+
+/// The Kotlin value of the carrier `value` of `ffi_type`.
+pub(super) fn lift(ffi_type: &FfiType, value: &str, ci: &ComponentInterface) -> String {
+    match ffi_type {
+        FfiType::RustArcPtr(_) => format!("uniffiNullable({value})"),
+        FfiType::RustBuffer(_) => match external_rust_buffer_name(ffi_type, ci) {
+            Some(name) => format!("uniffiReadRustBufferByValue({value}).as{name}()"),
+            None => format!("uniffiReadRustBufferByValue({value})"),
+        },
+        FfiType::ForeignBytes => format!("uniffiReadForeignBytesByValue({value})"),
+        FfiType::RustCallStatus => format!("uniffiReadUniffiRustCallStatusByValue({value})"),
+        FfiType::Struct(name) => format!(
+            "uniffiRead{}UniffiByValue({value})",
+            KotlinCodeOracle.ffi_struct_name(name)
+        ),
+        FfiType::Callback(name) => {
+            format!(
+                "{}Native({value})",
+                KotlinCodeOracle.ffi_callback_name(name)
+            )
+        }
+        FfiType::Reference(inner) | FfiType::MutReference(inner) => format!(
+            "{}({value}.reinterpret({}.byteSize()))",
+            KotlinCodeOracle.ffi_type_label(ffi_type, ci),
+            layout(inner)
+        ),
+        _ => value.to_owned(),
+    }
+}
+
+/// Reads the field of type `ffi_type` at `offset` in `segment`, as a Kotlin value.
+pub(super) fn read(
+    ffi_type: &FfiType,
+    segment: &str,
+    offset: &str,
+    ci: &ComponentInterface,
+) -> String {
+    match ffi_type {
+        FfiType::RustArcPtr(_) => {
+            format!("uniffiNullable({segment}.get({ADDRESS_LAYOUT}, {offset}))")
+        }
+        FfiType::Callback(name) => format!(
+            "uniffiNullable({segment}.get({ADDRESS_LAYOUT}, {offset}))?.let(::{}Native)",
+            KotlinCodeOracle.ffi_callback_name(name)
+        ),
+        FfiType::RustBuffer(_) => match external_rust_buffer_name(ffi_type, ci) {
+            Some(name) => format!("uniffiReadRustBufferByValue({segment}, {offset}).as{name}()"),
+            None => format!("uniffiReadRustBufferByValue({segment}, {offset})"),
+        },
+        FfiType::ForeignBytes => format!("uniffiReadForeignBytesByValue({segment}, {offset})"),
+        FfiType::RustCallStatus => {
+            format!("uniffiReadUniffiRustCallStatusByValue({segment}, {offset})")
+        }
+        FfiType::Struct(name) => format!(
+            "uniffiRead{}UniffiByValue({segment}, {offset})",
+            KotlinCodeOracle.ffi_struct_name(name)
+        ),
+        FfiType::Reference(_) | FfiType::MutReference(_) => lift(
+            ffi_type,
+            &format!("{segment}.get({ADDRESS_LAYOUT}, {offset})"),
+            ci,
+        ),
+        _ => format!("{segment}.get({}, {offset})", layout(ffi_type)),
+    }
+}
+
+/// Writes the Kotlin value `value` of `ffi_type` to the field at `offset` in `segment`.
+pub(super) fn write(
+    ffi_type: &FfiType,
+    segment: &str,
+    offset: &str,
+    value: &str,
+    ci: &ComponentInterface,
+) -> String {
+    match ffi_type {
+        FfiType::RustArcPtr(_) => {
+            format!("{segment}.set({ADDRESS_LAYOUT}, {offset}, {value} ?: {NULL_SEGMENT})")
+        }
+        FfiType::Callback(_) => format!(
+            "{segment}.set({ADDRESS_LAYOUT}, {offset}, {value}?.uniffiUpcallStub() ?: {NULL_SEGMENT})"
+        ),
+        FfiType::RustBuffer(_) => match external_rust_buffer_name(ffi_type, ci) {
+            Some(name) => format!("{value}.from{name}ToLocal().uniffiWriteTo({segment}, {offset})"),
+            None => format!("{value}.uniffiWriteTo({segment}, {offset})"),
+        },
+        FfiType::ForeignBytes | FfiType::RustCallStatus | FfiType::Struct(_) => {
+            format!("{value}.uniffiWriteTo({segment}, {offset})")
+        }
+        FfiType::Reference(_) | FfiType::MutReference(_) => {
+            format!("{segment}.set({ADDRESS_LAYOUT}, {offset}, {value}.uniffiSegment)")
+        }
+        _ => format!("{segment}.set({}, {offset}, {value})", layout(ffi_type)),
+    }
+}
+
+/// The carrier returned by an upcall of `ffi_type` when the Kotlin callback throws.
+pub(super) fn default_carrier_value(ffi_type: &FfiType) -> String {
+    match carrier(ffi_type) {
+        MEMORY_SEGMENT => NULL_SEGMENT.to_owned(),
+        _ => KotlinCodeOracle.ffi_default_value(ffi_type),
+    }
+}
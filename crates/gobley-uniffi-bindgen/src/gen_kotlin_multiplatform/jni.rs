@@ -0,0 +1,533 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+//! Code snippets of the `android-jni` target, which calls Rust through `external` functions
+//! implemented by a generated Rust source file instead of JNA.
+//!
+//! The JNI functions take primitives and objects, the carriers of FFI values. Pointers are passed
+//! as `Long`s, and structs as Kotlin objects whose fields are read and written by the Rust side.
+//! The Kotlin types of FFI values are the same as with JNA, so that the shared templates work
+//! unchanged, and are converted to and from their carriers around every call.
+
+use std::fmt::Write;
+
+use uniffi_bindgen::interface::{ComponentInterface, FfiCallbackFunction, FfiDefinition, FfiType};
+
+use super::{Config, KotlinCodeOracle};
+
+/// A Kotlin callback stored by Rust in an argument or a field of `FfiType::Callback`.
+///
+/// Function pointers can't carry the Kotlin object they call, so each place a callback is passed
+/// to gets a static holding the object and a function forwarding to it. Callbacks are singletons,
+/// so the object of a place never changes in practice.
+pub struct CallbackSlot {
+    /// The name of the static and the prefix of the function, unique within the bindings.
+    pub name: String,
+    pub callback: FfiCallbackFunction,
+}
+
+/// The name of the external `RustBuffer` of `ffi_type`, if it is a `RustBuffer` of another crate.
+fn external_rust_buffer_name<'a>(
+    ffi_type: &'a FfiType,
+    ci: &ComponentInterface,
+) -> Option<&'a str> {
+    match ffi_type {
+        FfiType::RustBuffer(Some(metadata)) if metadata.module_path != ci.crate_name() => {
+            Some(&metadata.name)
+        }
+        _ => None,
+    }
+}
+
+/// The Kotlin type passing `ffi_type` from Kotlin to Rust: the arguments of JNI functions and the
+/// return values of upcalls.
+pub(super) fn carrier(ffi_type: &FfiType, ci: &ComponentInterface) -> String {
+    match ffi_type {
+        FfiType::Handle | FfiType::RustArcPtr(_) | FfiType::VoidPointer => "Long".to_owned(),
+        FfiType::RustBuffer(_) => "RustBufferByValue".to_owned(),
+        FfiType::Callback(name) => format!("{}Upcall", KotlinCodeOracle.ffi_callback_name(name)),
+        _ => KotlinCodeOracle.ffi_type_label_by_value(ffi_type, ci),
+    }
+}
+
+/// The Kotlin type passing `ffi_type` from Rust to Kotlin: the return values of JNI functions and
+/// the arguments of upcalls.
+pub(super) fn carrier_from_rust(ffi_type: &FfiType, ci: &ComponentInterface) -> String {
+    match ffi_type {
+        FfiType::Callback(_) => "Long".to_owned(),
+        _ => carrier(ffi_type, ci),
+    }
+}
+
+/// The carrier of `value`, whose Kotlin type is the one of `ffi_type`.
+pub(super) fn lower(ffi_type: &FfiType, value: &str, ci: &ComponentInterface) -> String {
+    match ffi_type {
+        FfiType::RustArcPtr(_) => format!("({value}?.address ?: 0L)"),
+        FfiType::VoidPointer => format!("{value}.address"),
+        FfiType::RustBuffer(_) => match external_rust_buffer_name(ffi_type, ci) {
+            Some(name) => format!("{value}.from{name}ToLocal()"),
+            None => value.to_owned(),
+        },
+        FfiType::Callback(_) => format!("{value}.uniffiUpcall()"),
+        _ => value.to_owned(),
+    }
+}
+
+/// The Kotlin value of the carrier `value` of `ffi_type`, received from Rust.
+pub(super) fn lift(ffi_type: &FfiType, value: &str, ci: &ComponentInterface) -> String {
+    match ffi_type {
+        FfiType::RustArcPtr(_) => format!("uniffiNullablePointer({value})"),
+        FfiType::VoidPointer => format!("Pointer({value})"),
+        FfiType::RustBuffer(_) => match external_rust_buffer_name(ffi_type, ci) {
+            Some(name) => format!("{value}.as{name}()"),
+            None => value.to_owned(),
+        },
+        FfiType::Callback(name) => {
+            format!(
+                "{}Native({value})",
+                KotlinCodeOracle.ffi_callback_name(name)
+            )
+        }
+        _ => value.to_owned(),
+    }
+}
+
+/// The Kotlin type of the field storing an FFI struct field of `ffi_type`, if it differs from the
+/// type of the property. Pointers are stored as addresses and callbacks as their upcall adapters,
+/// which the Rust side can read without calling Kotlin.
+pub(super) fn field_carrier(ffi_type: &FfiType) -> Option<String> {
+    match ffi_type {
+        FfiType::RustArcPtr(_) | FfiType::VoidPointer => Some("Long".to_owned()),
+        FfiType::Callback(name) => Some(format!(
+            "{}Upcall?",
+            KotlinCodeOracle.ffi_callback_name(name)
+        )),
+        _ => None,
+    }
+}
+
+/// The value stored in the field of the struct field `value` of `ffi_type`.
+pub(super) fn field_lower(ffi_type: &FfiType, value: &str) -> String {
+    match ffi_type {
+        FfiType::RustArcPtr(_) => format!("({value}?.address ?: 0L)"),
+        FfiType::VoidPointer => format!("{value}.address"),
+        FfiType::Callback(_) => format!("{value}?.uniffiUpcall()"),
+        _ => value.to_owned(),
+    }
+}
+
+/// The struct field of `ffi_type` stored in the field `value`.
+pub(super) fn field_lift(ffi_type: &FfiType, value: &str) -> String {
+    match ffi_type {
+        FfiType::RustArcPtr(_) => format!("uniffiNullablePointer({value})"),
+        FfiType::VoidPointer => format!("Pointer({value})"),
+        FfiType::Callback(_) => format!("{value}?.uniffiTarget"),
+        _ => value.to_owned(),
+    }
+}
+
+/// The value of an FFI struct field of `ffi_type` in a struct created by the Rust side. Unlike
+/// `ffi_default_value`, never allocates a `RustBuffer`.
+pub(super) fn default_value(ffi_type: &FfiType, ci: &ComponentInterface) -> String {
+    match ffi_type {
+        FfiType::Handle => "0L".to_owned(),
+        FfiType::VoidPointer => "Pointer(0L)".to_owned(),
+        FfiType::RustBuffer(_)
+        | FfiType::ForeignBytes
+        | FfiType::RustCallStatus
+        | FfiType::Struct(_) => {
+            format!(
+                "{}()",
+                KotlinCodeOracle.ffi_type_label_by_value(ffi_type, ci)
+            )
+        }
+        _ => KotlinCodeOracle.ffi_default_value(ffi_type),
+    }
+}
+
+/// The prefix of the binary names of the classes in the package of the bindings, e.g.
+/// `uniffi/foo/`.
+fn class_prefix(config: &Config) -> String {
+    config
+        .package_name()
+        .split('.')
+        .fold(String::new(), |prefix, segment| prefix + segment + "/")
+}
+
+/// Escapes `name` for the symbol of a JNI function, as specified by the JNI.
+fn mangle(name: &str) -> String {
+    let mut mangled = String::new();
+    for c in name.chars() {
+        match c {
+            '.' | '/' => mangled.push('_'),
+            '_' => mangled.push_str("_1"),
+            ';' => mangled.push_str("_2"),
+            '[' => mangled.push_str("_3"),
+            c if c.is_ascii_alphanumeric() => mangled.push(c),
+            c => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    write!(mangled, "_0{unit:04x}").unwrap();
+                }
+            }
+        }
+    }
+    mangled
+}
+
+/// The symbol of the JNI function implementing `method` of `UniffiJni`.
+pub(super) fn native_name(method: &str, config: &Config) -> String {
+    format!(
+        "Java_{}_UniffiJni_{}",
+        mangle(&config.package_name()),
+        mangle(method)
+    )
+}
+
+/// The binary name of the class `class_name` in the package of the bindings, as passed to
+/// `FindClass`.
+pub(super) fn class_name(class_name: &str, config: &Config) -> String {
+    format!("{}{class_name}", class_prefix(config))
+}
+
+/// The Kotlin class of the objects passed for `FfiType::MutReference(inner)`, which Rust writes
+/// the value to, relative to the package of the bindings.
+pub(super) fn reference_class(inner: &FfiType) -> String {
+    match inner {
+        FfiType::RustArcPtr(_) => "PointerByReference".to_owned(),
+        FfiType::RustBuffer(_) => "RustBufferStruct".to_owned(),
+        FfiType::Struct(name) => format!("{}Struct", KotlinCodeOracle.ffi_struct_name(name)),
+        _ => format!("{}ByReference", primitive_name(inner)),
+    }
+}
+
+/// The Kotlin name of the primitive carrying `ffi_type`.
+fn primitive_name(ffi_type: &FfiType) -> &'static str {
+    match ffi_type {
+        FfiType::Int8 | FfiType::UInt8 => "Byte",
+        FfiType::Int16 | FfiType::UInt16 => "Short",
+        FfiType::Int32 | FfiType::UInt32 => "Int",
+        FfiType::Int64
+        | FfiType::UInt64
+        | FfiType::Handle
+        | FfiType::RustArcPtr(_)
+        | FfiType::VoidPointer => "Long",
+        FfiType::Float32 => "Float",
+        FfiType::Float64 => "Double",
+        _ => panic!("{ffi_type:?} is not a primitive"),
+    }
+}
+
+fn is_primitive(ffi_type: &FfiType) -> bool {
+    !matches!(
+        ffi_type,
+        FfiType::RustBuffer(_)
+            | FfiType::ForeignBytes
+            | FfiType::RustCallStatus
+            | FfiType::Struct(_)
+            | FfiType::Callback(_)
+            | FfiType::Reference(_)
+            | FfiType::MutReference(_)
+    )
+}
+
+/// The JVM type descriptor of the class `class_name` in the package of the bindings.
+fn descriptor_of_class(class_name: &str, config: &Config) -> String {
+    format!("L{};", self::class_name(class_name, config))
+}
+
+fn primitive_descriptor(ffi_type: &FfiType) -> &'static str {
+    match primitive_name(ffi_type) {
+        "Byte" => "B",
+        "Short" => "S",
+        "Int" => "I",
+        "Long" => "J",
+        "Float" => "F",
+        _ => "D",
+    }
+}
+
+/// The JVM type descriptor of the carrier of `ffi_type` passed from Kotlin to Rust, which is also
+/// the type of the fields of FFI structs other than pointers and callbacks.
+fn descriptor(ffi_type: &FfiType, config: &Config) -> String {
+    match ffi_type {
+        FfiType::RustBuffer(_) => descriptor_of_class("RustBufferStruct$ByValue", config),
+        FfiType::ForeignBytes => descriptor_of_class("ForeignBytesStruct$ByValue", config),
+        FfiType::RustCallStatus => {
+            descriptor_of_class("UniffiRustCallStatusStruct$ByValue", config)
+        }
+        FfiType::Struct(name) => descriptor_of_class(
+            &format!(
+                "{}Struct$UniffiByValue",
+                KotlinCodeOracle.ffi_struct_name(name)
+            ),
+            config,
+        ),
+        FfiType::Callback(name) => descriptor_of_class(
+            &format!("{}Upcall", KotlinCodeOracle.ffi_callback_name(name)),
+            config,
+        ),
+        FfiType::MutReference(inner) => descriptor_of_class(&reference_class(inner), config),
+        FfiType::Reference(_) => panic!("{ffi_type:?} is not implemented for JNI"),
+        _ => primitive_descriptor(ffi_type).to_owned(),
+    }
+}
+
+/// The JVM type descriptor of the field storing an FFI struct field of `ffi_type`.
+pub(super) fn field_descriptor(ffi_type: &FfiType, config: &Config) -> String {
+    match ffi_type {
+        FfiType::RustArcPtr(_) | FfiType::VoidPointer => "J".to_owned(),
+        _ => descriptor(ffi_type, config),
+    }
+}
+
+/// Whether Kotlin can call function pointers of `callback` received from Rust. Only the methods of
+/// the vtables of callback interfaces write to `FfiType::MutReference` arguments, and those are
+/// always implemented in Kotlin.
+pub(super) fn is_invokable(callback: &FfiCallbackFunction) -> bool {
+    !callback
+        .arguments()
+        .iter()
+        .any(|arg| matches!(arg.type_(), FfiType::MutReference(_)))
+}
+
+/// Whether Rust can call Kotlin implementations of `callback`. The callbacks only implemented in
+/// Rust, like the completion of foreign futures, take structs by value, which aren't converted to
+/// Kotlin objects.
+pub(super) fn is_upcallable(callback: &FfiCallbackFunction) -> bool {
+    !callback.arguments().iter().any(|arg| {
+        matches!(
+            arg.type_(),
+            FfiType::Struct(_)
+                | FfiType::ForeignBytes
+                | FfiType::RustCallStatus
+                | FfiType::Reference(_)
+        )
+    })
+}
+
+/// The JVM method descriptor of `callback` of the upcall adapter of `callback`.
+pub(super) fn upcall_descriptor(callback: &FfiCallbackFunction, config: &Config) -> String {
+    let mut descriptor = String::from("(");
+    for argument in callback.arguments() {
+        match argument.type_() {
+            FfiType::Callback(_) => descriptor.push('J'),
+            type_ => descriptor.push_str(&self::descriptor(&type_, config)),
+        }
+    }
+    if callback.has_rust_call_status_arg() {
+        descriptor.push_str(&descriptor_of_class(
+            "UniffiRustCallStatusStruct$ByReference",
+            config,
+        ));
+    }
+    descriptor.push(')');
+    match callback.return_type() {
+        Some(return_type) => descriptor.push_str(&self::descriptor(return_type, config)),
+        None => descriptor.push('V'),
+    }
+    descriptor
+}
+
+/// The Rust type of `ffi_type` in the scaffolding functions.
+pub(super) fn rust_type(ffi_type: &FfiType) -> String {
+    match ffi_type {
+        FfiType::Int8 => "i8".to_owned(),
+        FfiType::UInt8 => "u8".to_owned(),
+        FfiType::Int16 => "i16".to_owned(),
+        FfiType::UInt16 => "u16".to_owned(),
+        FfiType::Int32 => "i32".to_owned(),
+        FfiType::UInt32 => "u32".to_owned(),
+        FfiType::Int64 => "i64".to_owned(),
+        FfiType::UInt64 | FfiType::Handle => "u64".to_owned(),
+        FfiType::Float32 => "f32".to_owned(),
+        FfiType::Float64 => "f64".to_owned(),
+        FfiType::RustArcPtr(_) | FfiType::VoidPointer => "*const c_void".to_owned(),
+        FfiType::RustBuffer(_) => "RustBuffer".to_owned(),
+        FfiType::ForeignBytes => "ForeignBytes".to_owned(),
+        FfiType::RustCallStatus => "RustCallStatus".to_owned(),
+        FfiType::Callback(name) => KotlinCodeOracle.ffi_callback_name(name),
+        FfiType::Struct(name) => KotlinCodeOracle.ffi_struct_name(name),
+        FfiType::Reference(inner) => format!("*const {}", rust_type(inner)),
+        FfiType::MutReference(inner) => format!("*mut {}", rust_type(inner)),
+    }
+}
+
+/// The `jni_sys` type of the carrier of `ffi_type` passed from Kotlin to Rust.
+pub(super) fn sys_type(ffi_type: &FfiType) -> &'static str {
+    if !is_primitive(ffi_type) {
+        return "jobject";
+    }
+    match primitive_name(ffi_type) {
+        "Byte" => "jbyte",
+        "Short" => "jshort",
+        "Int" => "jint",
+        "Long" => "jlong",
+        "Float" => "jfloat",
+        _ => "jdouble",
+    }
+}
+
+/// The `jni_sys` type of the carrier of `ffi_type` passed from Rust to Kotlin.
+pub(super) fn sys_type_from_rust(ffi_type: &FfiType) -> &'static str {
+    match ffi_type {
+        FfiType::Callback(_) => "jlong",
+        _ => sys_type(ffi_type),
+    }
+}
+
+/// The field of `jvalue` holding the carrier of `ffi_type` passed from Rust to Kotlin.
+pub(super) fn jvalue_field(ffi_type: &FfiType) -> &'static str {
+    match sys_type_from_rust(ffi_type) {
+        "jbyte" => "b",
+        "jshort" => "s",
+        "jint" => "i",
+        "jlong" => "j",
+        "jfloat" => "f",
+        "jdouble" => "d",
+        _ => "l",
+    }
+}
+
+/// The `JNIEnv` function reading a field of an FFI struct of `ffi_type`.
+pub(super) fn field_getter(ffi_type: &FfiType) -> String {
+    if is_primitive(ffi_type) {
+        format!("Get{}Field", primitive_name(ffi_type))
+    } else {
+        "GetObjectField".to_owned()
+    }
+}
+
+/// The `JNIEnv` function calling a Kotlin method returning `return_type`.
+pub(super) fn call_method(return_type: Option<&FfiType>) -> String {
+    match return_type {
+        None => "CallVoidMethodA".to_owned(),
+        Some(return_type) if is_primitive(return_type) => {
+            format!("Call{}MethodA", primitive_name(return_type))
+        }
+        Some(_) => "CallObjectMethodA".to_owned(),
+    }
+}
+
+/// Converts the JNI value `value` of `ffi_type`, passed from Kotlin, to its Rust value. Callbacks
+/// are stored in the static of `slot`.
+pub(super) fn from_java(ffi_type: &FfiType, value: &str, slot: &str) -> String {
+    match ffi_type {
+        FfiType::RustArcPtr(_) | FfiType::VoidPointer => {
+            format!("{value} as usize as *const c_void")
+        }
+        FfiType::Float32 | FfiType::Float64 => value.to_owned(),
+        _ if is_primitive(ffi_type) => format!("{value} as {}", rust_type(ffi_type)),
+        FfiType::RustBuffer(_) => format!("read_rust_buffer(env, {value})"),
+        FfiType::ForeignBytes => format!("read_foreign_bytes(env, {value})"),
+        FfiType::RustCallStatus => format!("read_call_status(env, {value})"),
+        FfiType::Struct(name) => format!(
+            "read_{}(env, {value})",
+            KotlinCodeOracle.ffi_struct_name(name)
+        ),
+        FfiType::Callback(_) => format!(
+            "if store_callback(env, &{slot}, {value}) {{ Some({slot}_upcall) }} else {{ None }}"
+        ),
+        // Rust only reads the struct for the duration of the call, but the vtables of callback
+        // interfaces must outlive it, so they're leaked.
+        FfiType::Reference(inner) => format!(
+            "Box::into_raw(Box::new({})) as *const {}",
+            from_java_by_reference(inner, value),
+            rust_type(inner)
+        ),
+        FfiType::MutReference(_) => panic!("{ffi_type:?} is not implemented for JNI"),
+    }
+}
+
+/// Reads the Rust value of `inner` from the object `value` passed for
+/// `FfiType::MutReference(inner)`.
+pub(super) fn from_java_by_reference(inner: &FfiType, value: &str) -> String {
+    match inner {
+        FfiType::RustBuffer(_) | FfiType::Struct(_) => from_java(inner, value, ""),
+        _ => from_java(
+            inner,
+            &format!(
+                "jni!(env, {}, {value}, cache().{}_value)",
+                field_getter(inner),
+                reference_class(inner)
+            ),
+            "",
+        ),
+    }
+}
+
+/// Converts the Rust value `value` of `ffi_type` to its JNI value, passed to Kotlin.
+pub(super) fn to_java(ffi_type: &FfiType, value: &str) -> String {
+    match ffi_type {
+        FfiType::RustArcPtr(_) | FfiType::VoidPointer => format!("{value} as usize as jlong"),
+        FfiType::Float32 | FfiType::Float64 => value.to_owned(),
+        _ if is_primitive(ffi_type) => format!("{value} as {}", sys_type(ffi_type)),
+        FfiType::RustBuffer(_) => format!("new_rust_buffer(env, {value})"),
+        FfiType::Callback(_) => format!("{value}.map_or(0, |callback| callback as usize as jlong)"),
+        _ => panic!("passing {ffi_type:?} from Rust to Kotlin is not implemented for JNI"),
+    }
+}
+
+/// The slots of the callbacks passed to Rust, see [`CallbackSlot`].
+pub(super) fn callback_slots(ci: &ComponentInterface) -> Vec<CallbackSlot> {
+    let callback = |name: &str| {
+        ci.ffi_definitions()
+            .find_map(|definition| match definition {
+                FfiDefinition::CallbackFunction(callback) if callback.name() == name => {
+                    Some(callback)
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("callback {name} is not defined"))
+    };
+
+    let mut slots = Vec::new();
+    for definition in ci.ffi_definitions() {
+        match definition {
+            FfiDefinition::Function(function) => {
+                for argument in function.arguments() {
+                    if let FfiType::Callback(name) = argument.type_() {
+                        slots.push(CallbackSlot {
+                            name: format!("{}_{}", function.name(), argument.name()),
+                            callback: callback(&name),
+                        });
+                    }
+                }
+            }
+            // The callbacks passed to the others are only converted when Kotlin calls them.
+            FfiDefinition::CallbackFunction(function) if is_invokable(&function) => {
+                for argument in function.arguments() {
+                    if let FfiType::Callback(name) = argument.type_() {
+                        slots.push(CallbackSlot {
+                            name: format!(
+                                "{}_{}",
+                                KotlinCodeOracle.ffi_callback_name(function.name()),
+                                argument.name()
+                            ),
+                            callback: callback(&name),
+                        });
+                    }
+                }
+            }
+            FfiDefinition::Struct(ffi_struct) => {
+                for field in ffi_struct.fields() {
+                    if let FfiType::Callback(name) = field.type_() {
+                        slots.push(CallbackSlot {
+                            name: format!(
+                                "{}_{}",
+                                KotlinCodeOracle.ffi_struct_name(ffi_struct.name()),
+                                field.name()
+                            ),
+                            callback: callback(&name),
+                        });
+                    }
+                }
+            }
+            FfiDefinition::CallbackFunction(_) => {}
+        }
+    }
+    slots.retain(|slot| is_upcallable(&slot.callback));
+    slots
+}
@@ -25,6 +25,7 @@ macro_rules! impl_code_type_for_miscellany {
     };
 }
 
-impl_code_type_for_miscellany!(TimestampCodeType, "kotlinx.datetime.Instant", "Timestamp");
+// Imported with an alias, as the Kotlin type depends on `Config::timestamp_type_name()`.
+impl_code_type_for_miscellany!(TimestampCodeType, "UniffiInstant", "Timestamp");
 
 impl_code_type_for_miscellany!(DurationCodeType, "kotlin.time.Duration", "Duration");
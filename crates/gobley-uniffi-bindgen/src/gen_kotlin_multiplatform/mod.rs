@@ -21,10 +21,14 @@ mod callback_interface;
 mod compounds;
 mod custom;
 mod enum_;
+mod ffm;
+mod jni;
 mod miscellany;
 mod object;
+mod presets;
 mod primitives;
 mod record;
+pub(crate) mod validation;
 mod variant;
 
 #[rustfmt::skip]
@@ -85,6 +89,39 @@ trait CodeType: Debug {
     }
 }
 
+/// Enums selected by their names, or every enum with `true`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EnumSelection {
+    All(bool),
+    Only(Vec<String>),
+}
+
+impl Default for EnumSelection {
+    fn default() -> Self {
+        Self::All(false)
+    }
+}
+
+impl EnumSelection {
+    fn contains(&self, name: &str) -> bool {
+        match self {
+            Self::All(all) => *all,
+            Self::Only(names) => names.iter().any(|n| n == name),
+        }
+    }
+}
+
+/// The Kotlin type timestamps are mapped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimestampType {
+    /// Part of the standard library since Kotlin 2.1.20.
+    #[serde(rename = "kotlin.time.Instant")]
+    KotlinTime,
+    #[serde(rename = "kotlinx.datetime.Instant")]
+    KotlinxDatetime,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ConfigKotlinTarget {
     #[serde(rename = "jvm")]
@@ -95,6 +132,12 @@ pub enum ConfigKotlinTarget {
     Native,
     #[serde(rename = "stub")]
     Stub,
+    /// The JVM, calling Rust through the Foreign Function & Memory API of Java 22 instead of JNA.
+    #[serde(rename = "jvm-ffm")]
+    JvmFfm,
+    /// Android, calling Rust through generated JNI functions instead of JNA.
+    #[serde(rename = "android-jni")]
+    AndroidJni,
 }
 
 // config options to customize the generated Kotlin.
@@ -126,6 +169,39 @@ pub struct Config {
     android_dynamic_library_dependencies: Vec<String>,
     #[serde(default)]
     dynamic_library_dependencies: Vec<String>,
+    #[serde(default)]
+    native_dynamic_library_dependencies: Vec<String>,
+    #[serde(default)]
+    kotlin_source_sets: SourceSetConfig,
+    #[serde(default)]
+    timestamp_type: Option<TimestampType>,
+    #[serde(default)]
+    generate_unknown_enum_variants: EnumSelection,
+    #[serde(default)]
+    errors: HashMap<String, ErrorConfig>,
+    #[serde(default)]
+    cinterop: CinteropConfig,
+    #[serde(default)]
+    pool_rust_buffers: bool,
+    /// Records the live objects in `UniffiObjectTracker` and reports the ones freed by the cleaner.
+    #[serde(default)]
+    track_object_leaks: bool,
+    /// Reports every call across the FFI to `uniffiCallInterceptor`.
+    #[serde(default)]
+    instrument_calls: bool,
+    /// Sync functions and methods rendered as `suspend` functions, as `*`, `<function>`, `<object>`
+    /// or `<object>.<method>`.
+    #[serde(default)]
+    suspend_sync_calls: Vec<String>,
+    /// The `CoroutineDispatcher` the sync calls in `suspend_sync_calls` run on.
+    suspend_dispatcher: Option<String>,
+    /// Adds `CompletableFuture` companions to the async functions and methods on the JVM and
+    /// Android.
+    #[serde(default)]
+    generate_completable_futures: bool,
+    /// Keys in the configuration of the crate not used by the bindgen.
+    #[serde(skip)]
+    unknown_keys: Vec<String>,
 }
 
 // TODO: Make this public in 0.4.0
@@ -137,8 +213,24 @@ pub(crate) enum Visibility {
     Internal,
 }
 
+/// The names of the Kotlin source sets the bindings of each target are written to. When a name is
+/// not given, `<target>Main` is used.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(default)]
+pub struct SourceSetConfig {
+    common: Option<String>,
+    jvm: Option<String>,
+    android: Option<String>,
+    /// An intermediate source set shared by the JVM and Android targets, e.g. `jvmCommonMain`.
+    /// When set, the code common to both targets is generated only once in this source set,
+    /// and the JVM and Android source sets only contain the platform-specific code.
+    jvm_common: Option<String>,
+    native: Option<String>,
+    stub: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CustomTypeConfig {
     imports: Option<Vec<String>>,
     type_name: Option<String>,
@@ -146,6 +238,78 @@ pub struct CustomTypeConfig {
     lift: String,
     from_custom: String, // b/w compat alias for lower
     lower: String,
+    /// The name of a built-in preset filling in the values above, e.g. `kotlin.uuid.Uuid`.
+    preset: Option<String>,
+    /// The opt-in annotation required by the preset.
+    #[serde(skip)]
+    opt_in: Option<String>,
+    /// Overrides used by the bindings of each target, falling back to the values above.
+    jvm: Option<CustomTypeTargetConfig>,
+    android: Option<CustomTypeTargetConfig>,
+    native: Option<CustomTypeTargetConfig>,
+}
+
+/// Overrides of [`CustomTypeConfig`] for a single Kotlin target, e.g. `[custom_types.Url.jvm]`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomTypeTargetConfig {
+    imports: Option<Vec<String>>,
+    type_name: Option<String>,
+    lift: Option<String>,
+    lower: Option<String>,
+}
+
+/// Options of the cinterop definition file generated with the header for Kotlin/Native.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CinteropConfig {
+    /// The static libraries included in the cinterop library. Defaults to `lib<cdylib_name>.a`.
+    static_libraries: Option<Vec<String>>,
+    /// The directories the static libraries are searched in.
+    library_paths: Vec<String>,
+    /// Additional options passed to the C compiler when processing the header.
+    compiler_opts: Vec<String>,
+    /// Additional options passed to the linker, after the native dynamic library dependencies.
+    linker_opts: Vec<String>,
+}
+
+/// The supertypes of an error type, e.g. `[errors.NetworkError]`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ErrorConfig {
+    /// The fully qualified name of the superclass, `kotlin.Exception` by default.
+    superclass: Option<String>,
+    /// The fully qualified names of additional interfaces implemented by the error.
+    interfaces: Vec<String>,
+    /// Overrides used by the bindings of each target, for classes not available in common code.
+    jvm: Option<ErrorTargetConfig>,
+    android: Option<ErrorTargetConfig>,
+    native: Option<ErrorTargetConfig>,
+}
+
+/// Overrides of [`ErrorConfig`] for a single Kotlin target, e.g. `[errors.NetworkError.jvm]`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ErrorTargetConfig {
+    superclass: Option<String>,
+}
+
+impl ErrorConfig {
+    /// The superclass used by the bindings of `target`, one of `jvm`, `android`, `native` or
+    /// `common`.
+    fn superclass(&self, target: &str) -> &str {
+        let target_config = match target {
+            "jvm" => &self.jvm,
+            "android" => &self.android,
+            "native" => &self.native,
+            _ => &None,
+        };
+        target_config
+            .as_ref()
+            .and_then(|target_config| target_config.superclass.as_deref())
+            .or(self.superclass.as_deref())
+            .unwrap_or("kotlin.Exception")
+    }
 }
 
 // functions replace literal "{}" in strings with a specified value.
@@ -167,6 +331,37 @@ impl CustomTypeConfig {
         };
         converter.replace("{}", name)
     }
+
+    /// Applies the overrides of `target`, one of `jvm`, `android` or `native`.
+    fn for_target(&self, target: &str) -> CustomTypeConfig {
+        let target_config = match target {
+            "jvm" => &self.jvm,
+            "android" => &self.android,
+            "native" => &self.native,
+            _ => &None,
+        };
+        let mut config = CustomTypeConfig {
+            jvm: None,
+            android: None,
+            native: None,
+            ..self.clone()
+        };
+        if let Some(target_config) = target_config {
+            if target_config.imports.is_some() {
+                config.imports.clone_from(&target_config.imports);
+            }
+            if target_config.type_name.is_some() {
+                config.type_name.clone_from(&target_config.type_name);
+            }
+            if let Some(lift) = &target_config.lift {
+                config.lift.clone_from(lift);
+            }
+            if let Some(lower) = &target_config.lower {
+                config.lower.clone_from(lower);
+            }
+        }
+        config
+    }
 }
 
 impl Config {
@@ -223,6 +418,49 @@ impl Config {
         self.kotlin_version_is_at_least(1, 9, 0)
     }
 
+    /// The fully qualified name of the Kotlin type used for timestamps. Defaults to
+    /// `kotlin.time.Instant` when the target Kotlin version ships it.
+    pub fn timestamp_type_name(&self) -> &'static str {
+        let timestamp_type = self.timestamp_type.unwrap_or_else(|| {
+            if self.kotlin_version_is_at_least(2, 1, 20) {
+                TimestampType::KotlinTime
+            } else {
+                TimestampType::KotlinxDatetime
+            }
+        });
+        match timestamp_type {
+            TimestampType::KotlinTime => "kotlin.time.Instant",
+            TimestampType::KotlinxDatetime => "kotlinx.datetime.Instant",
+        }
+    }
+
+    /// Whether the enum `name` has an additional variant used when reading a variant added in a
+    /// newer version of the Rust library, instead of throwing an exception.
+    pub fn generate_unknown_enum_variant(&self, name: &str) -> bool {
+        self.generate_unknown_enum_variants.contains(name)
+    }
+
+    /// The name of the additional entry of flat enums used for unknown variants.
+    pub fn unknown_enum_entry_name(&self) -> String {
+        KotlinCodeOracle.enum_variant_name("Unknown", self)
+    }
+
+    /// The opt-in annotations of the bindings of `module_name`, rendered as a single
+    /// `@file:OptIn` since the annotation isn't repeatable.
+    pub fn opt_in_annotations(&self, ci: &ComponentInterface, module_name: &str) -> BTreeSet<&str> {
+        let mut annotations = self.custom_type_opt_ins();
+        if module_name == "native" {
+            annotations.insert("kotlinx.cinterop.ExperimentalForeignApi");
+        }
+        let uses_timestamps = ci
+            .iter_types()
+            .any(|type_| matches!(type_, Type::Timestamp));
+        if uses_timestamps && self.timestamp_type_name() == "kotlin.time.Instant" {
+            annotations.insert("kotlin.time.ExperimentalTime");
+        }
+        annotations
+    }
+
     pub fn generate_serializable(&self) -> bool {
         self.generate_serializable_types.unwrap_or(false)
     }
@@ -243,10 +481,219 @@ impl Config {
         match module_name {
             "jvm" => self.jvm_dynamic_library_dependencies(),
             "android" => self.android_dynamic_library_dependencies(),
+            "native" => self.native_dynamic_library_dependencies.clone(),
             _ => vec![],
         }
     }
 
+    pub fn android_jvm_has_dynamic_library_dependencies(&self) -> bool {
+        !self.jvm_dynamic_library_dependencies().is_empty()
+            || !self.android_dynamic_library_dependencies().is_empty()
+    }
+
+    /// Whether to generate the code shared by the JVM and Android targets in a separate
+    /// intermediate source set.
+    pub fn generate_jvm_common_bindings(&self) -> bool {
+        self.kotlin_multiplatform
+            && self.kotlin_source_sets.jvm_common.is_some()
+            && (self.has_jvm_target() || self.has_android_target())
+    }
+
+    /// Whether the bindings of the JVM target are generated, with JNA or the Foreign Function &
+    /// Memory API.
+    pub fn has_jvm_target(&self) -> bool {
+        self.kotlin_targets.contains(&ConfigKotlinTarget::Jvm) || self.jvm_uses_ffm()
+    }
+
+    /// Whether the bindings of the JVM target call Rust through the Foreign Function & Memory API
+    /// instead of JNA. Takes precedence over `jvm` when both targets are listed.
+    pub fn jvm_uses_ffm(&self) -> bool {
+        self.kotlin_targets.contains(&ConfigKotlinTarget::JvmFfm)
+    }
+
+    /// Whether the bindings of the Android target are generated, with JNA or JNI.
+    pub fn has_android_target(&self) -> bool {
+        self.kotlin_targets.contains(&ConfigKotlinTarget::Android) || self.android_uses_jni()
+    }
+
+    /// Whether the bindings of the Android target call Rust through generated JNI functions
+    /// instead of JNA. Takes precedence over `android` when both targets are listed.
+    pub fn android_uses_jni(&self) -> bool {
+        self.kotlin_targets
+            .contains(&ConfigKotlinTarget::AndroidJni)
+    }
+
+    /// The name of the Kotlin source set the bindings for `target` are written to.
+    pub fn source_set_name(&self, target: &str) -> String {
+        if !self.kotlin_multiplatform {
+            return String::from("main");
+        }
+        let source_sets = &self.kotlin_source_sets;
+        let source_set_name = match target {
+            "common" => &source_sets.common,
+            "jvm" => &source_sets.jvm,
+            "android" => &source_sets.android,
+            "jvmCommon" => &source_sets.jvm_common,
+            "native" => &source_sets.native,
+            "stub" => &source_sets.stub,
+            _ => &None,
+        };
+        source_set_name
+            .clone()
+            .unwrap_or_else(|| format!("{target}Main"))
+    }
+
+    /// The platform targets whose bindings may override the configuration of custom types and
+    /// errors.
+    fn custom_type_targets(&self) -> Vec<&'static str> {
+        [
+            (self.has_jvm_target(), "jvm"),
+            (self.has_android_target(), "android"),
+            (
+                self.kotlin_targets.contains(&ConfigKotlinTarget::Native),
+                "native",
+            ),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, target)| target)
+        .collect()
+    }
+
+    /// The configuration of the custom type `name` used by the bindings of `module_name`, with the
+    /// overrides of the target applied.
+    pub fn custom_type(&self, name: &str, module_name: &str) -> Option<CustomTypeConfig> {
+        let custom_type = self.custom_types.get(name)?;
+        Some(custom_type.for_target(self.override_target(module_name)))
+    }
+
+    /// The target whose overrides apply to the bindings of `module_name`.
+    fn override_target<'a>(&self, module_name: &'a str) -> &'a str {
+        match module_name {
+            // Unless an expect class is used, every target has the same overrides.
+            "common" => self
+                .custom_type_targets()
+                .first()
+                .copied()
+                .unwrap_or(module_name),
+            "jvmCommon" if self.has_jvm_target() => "jvm",
+            "jvmCommon" => "android",
+            target => target,
+        }
+    }
+
+    /// Whether the custom type `name` is mapped to a different Kotlin type on each target. The
+    /// common bindings then declare an expect class, and the bindings of each target an actual
+    /// typealias to the Kotlin type.
+    pub fn custom_type_is_expect_class(&self, name: &str) -> bool {
+        let Some(custom_type) = self.custom_types.get(name) else {
+            return false;
+        };
+        if !self.kotlin_multiplatform {
+            return false;
+        }
+        let mut targets = self.custom_type_targets();
+        if self.kotlin_targets.contains(&ConfigKotlinTarget::Stub) {
+            targets.push("stub");
+        }
+        let type_names: HashSet<_> = targets
+            .into_iter()
+            .map(|target| custom_type.for_target(target).type_name)
+            .collect();
+        type_names.len() > 1
+    }
+
+    /// The fully qualified name of the superclass of the error `name` in the bindings of
+    /// `module_name`. When [`Config::error_has_base_class`] is true, the common bindings use the
+    /// superclass shared by every target.
+    pub fn error_superclass(&self, name: &str, module_name: &str) -> &str {
+        let Some(error) = self.errors.get(name) else {
+            return "kotlin.Exception";
+        };
+        if module_name == "common" && self.error_has_base_class(name) {
+            return error.superclass("common");
+        }
+        error.superclass(self.override_target(module_name))
+    }
+
+    /// Whether the error `name` has a different superclass on each target. The common bindings
+    /// then declare an expect class extended by the error, and the bindings of each target an
+    /// actual class extending the superclass of the target.
+    pub fn error_has_base_class(&self, name: &str) -> bool {
+        let Some(error) = self.errors.get(name) else {
+            return false;
+        };
+        if !self.kotlin_multiplatform {
+            return false;
+        }
+        let mut targets = self.custom_type_targets();
+        if self.kotlin_targets.contains(&ConfigKotlinTarget::Stub) {
+            targets.push("stub");
+        }
+        let superclasses: HashSet<_> = targets
+            .into_iter()
+            .map(|target| error.superclass(target))
+            .collect();
+        superclasses.len() > 1
+    }
+
+    /// Whether the sync function or method `function_name` of the object `object_name`, or the
+    /// top-level function when `object_name` is empty, is a `suspend` function running on
+    /// [`Self::suspend_dispatcher`]. Constructors and the methods of the interfaces implemented in
+    /// Kotlin stay blocking, as Rust calls the latter synchronously.
+    pub fn is_suspend_sync_call(
+        &self,
+        ci: &ComponentInterface,
+        object_name: &str,
+        function_name: &str,
+        is_async: bool,
+    ) -> bool {
+        if is_async || self.suspend_sync_calls.is_empty() {
+            return false;
+        }
+        if !object_name.is_empty() {
+            let Some(obj) = ci.get_object_definition(object_name) else {
+                return false;
+            };
+            let is_method = obj
+                .methods()
+                .iter()
+                .any(|meth| meth.name() == function_name);
+            if obj.has_callback_interface() || !is_method {
+                return false;
+            }
+        }
+        self.suspend_sync_calls.iter().any(|entry| {
+            if entry == "*" {
+                true
+            } else if object_name.is_empty() {
+                entry == function_name
+            } else {
+                entry == object_name || entry.split_once('.') == Some((object_name, function_name))
+            }
+        })
+    }
+
+    /// The expression of the `CoroutineDispatcher` the calls in `suspend_sync_calls` run on.
+    pub fn suspend_dispatcher(&self) -> &str {
+        self.suspend_dispatcher
+            .as_deref()
+            .unwrap_or("kotlinx.coroutines.Dispatchers.Default")
+    }
+
+    /// Whether the JVM and Android bindings have a `<function>Async` companion returning a
+    /// `CompletableFuture` for each async function and method.
+    pub fn generate_completable_futures(&self) -> bool {
+        self.generate_completable_futures
+    }
+
+    /// The fully qualified names of the additional interfaces implemented by the error `name`.
+    pub fn error_interfaces(&self, name: &str) -> &[String] {
+        self.errors
+            .get(name)
+            .map_or(&[], |error| error.interfaces.as_slice())
+    }
+
     // Get the package name for an external type
     pub fn external_package_name(&self, module_path: &str, namespace: Option<&str>) -> String {
         // config overrides are keyed by the crate name, default fallback is the namespace.
@@ -264,11 +711,16 @@ impl Config {
 
 pub struct MultiplatformBindings {
     pub common: String,
+    pub jvm_common: Option<String>,
     pub jvm: Option<String>,
     pub android: Option<String>,
     pub native: Option<String>,
     pub stub: Option<String>,
     pub header: Option<String>,
+    /// The cinterop definition file of the header.
+    pub cinterop_def: Option<String>,
+    /// The Rust source file defining the JNI functions called by the `android-jni` bindings.
+    pub jni_shim: Option<String>,
 }
 
 // Generate kotlin bindings for the given ComponentInterface, as a string.
@@ -293,19 +745,84 @@ pub fn generate_bindings(
             .map_or(Ok(None), |v| v.map(Some))
     }
 
-    let jvm = run_with_target(config, ConfigKotlinTarget::Jvm, || {
-        AndroidJvmKotlinWrapper::new("jvm", Some(Visibility::Public), config.clone(), ci)
-            .context("failed to create a JVM binding generator")?
-            .render()
-            .context("failed to render Kotlin/JVM bindings")
-    })?;
+    let jvm_common = config
+        .generate_jvm_common_bindings()
+        .then(|| {
+            AndroidJvmKotlinWrapper::new("jvmCommon", Some(Visibility::Public), config.clone(), ci)
+                .context("failed to create a JVM common binding generator")?
+                .render()
+                .context("failed to render common Kotlin/JVM bindings")
+        })
+        .transpose()?;
 
-    let android = run_with_target(config, ConfigKotlinTarget::Android, || {
-        AndroidJvmKotlinWrapper::new("android", Some(Visibility::Public), config.clone(), ci)
-            .context("failed to create a Android binding generator")?
+    let jvm = if config.jvm_uses_ffm() {
+        let bindings = FfmKotlinWrapper::new("jvm", Some(Visibility::Public), config.clone(), ci)
+            .context("failed to create a JVM Foreign Function & Memory binding generator")?
             .render()
-            .context("failed to render Android Kotlin/JVM bindings")
-    })?;
+            .context("failed to render Kotlin/JVM Foreign Function & Memory bindings")?;
+        Some(bindings)
+    } else {
+        run_with_target(config, ConfigKotlinTarget::Jvm, || {
+            if jvm_common.is_some() {
+                AndroidJvmPlatformKotlinWrapper::new(
+                    "jvm",
+                    Some(Visibility::Public),
+                    config.clone(),
+                    ci,
+                )
+                .context("failed to create a JVM platform binding generator")?
+                .render()
+                .context("failed to render platform-specific Kotlin/JVM bindings")
+            } else {
+                AndroidJvmKotlinWrapper::new("jvm", Some(Visibility::Public), config.clone(), ci)
+                    .context("failed to create a JVM binding generator")?
+                    .render()
+                    .context("failed to render Kotlin/JVM bindings")
+            }
+        })?
+    };
+
+    let android = if config.android_uses_jni() {
+        let bindings =
+            JniKotlinWrapper::new("android", Some(Visibility::Public), config.clone(), ci)
+                .context("failed to create an Android JNI binding generator")?
+                .render()
+                .context("failed to render Android JNI Kotlin bindings")?;
+        Some(bindings)
+    } else {
+        run_with_target(config, ConfigKotlinTarget::Android, || {
+            if jvm_common.is_some() {
+                AndroidJvmPlatformKotlinWrapper::new(
+                    "android",
+                    Some(Visibility::Public),
+                    config.clone(),
+                    ci,
+                )
+                .context("failed to create a Android platform binding generator")?
+                .render()
+                .context("failed to render platform-specific Android Kotlin/JVM bindings")
+            } else {
+                AndroidJvmKotlinWrapper::new(
+                    "android",
+                    Some(Visibility::Public),
+                    config.clone(),
+                    ci,
+                )
+                .context("failed to create a Android binding generator")?
+                .render()
+                .context("failed to render Android Kotlin/JVM bindings")
+            }
+        })?
+    };
+
+    let jni_shim = config
+        .android_uses_jni()
+        .then(|| {
+            JniShim { config, ci }
+                .render()
+                .context("failed to render the Rust JNI functions of the Android bindings")
+        })
+        .transpose()?;
 
     let native = run_with_target(config, ConfigKotlinTarget::Native, || {
         NativeKotlinWrapper::new("native", Some(Visibility::Public), config.clone(), ci)
@@ -328,16 +845,55 @@ pub fn generate_bindings(
             .context("failed to render Kotlin/Native headers")
     })?;
 
+    let cinterop_def = run_with_target(config, ConfigKotlinTarget::Native, || {
+        Ok(render_cinterop_def(config, ci))
+    })?;
+
     Ok(MultiplatformBindings {
         common,
+        jvm_common,
         jvm,
         android,
         native,
         stub,
         header,
+        cinterop_def,
+        jni_shim,
     })
 }
 
+/// Renders the cinterop definition file of the header, named after the namespace and written next
+/// to it. Properties without a value are omitted.
+fn render_cinterop_def(config: &Config, ci: &ComponentInterface) -> String {
+    let cinterop = &config.cinterop;
+    let static_libraries = cinterop
+        .static_libraries
+        .clone()
+        .unwrap_or_else(|| vec![format!("lib{}.a", config.cdylib_name())]);
+    let mut linker_opts: Vec<String> = config
+        .dynamic_library_dependencies("native")
+        .iter()
+        .map(|library| format!("-l{library}"))
+        .collect();
+    linker_opts.extend_from_slice(&cinterop.linker_opts);
+
+    let properties = [
+        ("headers", vec![format!("{}.h", ci.namespace())]),
+        ("package", vec![format!("{}.cinterop", ci.namespace())]),
+        ("staticLibraries", static_libraries),
+        ("libraryPaths", cinterop.library_paths.clone()),
+        ("compilerOpts", cinterop.compiler_opts.clone()),
+        ("linkerOpts", linker_opts),
+    ];
+    let mut def = String::new();
+    for (name, values) in properties {
+        if !values.is_empty() {
+            def.push_str(&format!("{name} = {}\n", values.join(" ")));
+        }
+    }
+    def
+}
+
 /// A struct to record a Kotlin import statement.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum ImportRequirement {
@@ -524,6 +1080,37 @@ kotlin_wrapper!(
     "android+jvm/wrapper.kt"
 );
 
+kotlin_type_renderer!(
+    AndroidJvmPlatformTypeRenderer,
+    "android+jvm/PlatformTypes.kt"
+);
+kotlin_wrapper!(
+    AndroidJvmPlatformKotlinWrapper,
+    AndroidJvmPlatformTypeRenderer,
+    "android+jvm/platform_wrapper.kt"
+);
+
+kotlin_type_renderer!(FfmTypeRenderer, "jvm-ffm/Types.kt");
+kotlin_wrapper!(FfmKotlinWrapper, FfmTypeRenderer, "jvm-ffm/wrapper.kt");
+
+kotlin_type_renderer!(JniTypeRenderer, "android-jni/Types.kt");
+kotlin_wrapper!(JniKotlinWrapper, JniTypeRenderer, "android-jni/wrapper.kt");
+
+/// The Rust source file of the `android-jni` bindings, implementing the `external` functions of
+/// the Kotlin bindings by forwarding to the scaffolding functions.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "android-jni/shim.rs")]
+struct JniShim<'a> {
+    config: &'a Config,
+    ci: &'a ComponentInterface,
+}
+
+impl JniShim<'_> {
+    fn callback_slots(&self) -> Vec<jni::CallbackSlot> {
+        jni::callback_slots(self.ci)
+    }
+}
+
 kotlin_type_renderer!(NativeTypeRenderer, "native/Types.kt");
 kotlin_wrapper!(NativeKotlinWrapper, NativeTypeRenderer, "native/wrapper.kt");
 
@@ -537,6 +1124,45 @@ kotlin_wrapper!(
     "headers/wrapper.h"
 );
 
+/// A function, constructor or method of a component, named as in the configuration entries
+/// referring to it: `<function>` for top-level functions, `<Object>.<method>` for the others.
+pub(crate) struct NamedCallable {
+    pub name: String,
+    pub is_async: bool,
+    pub is_constructor: bool,
+    /// Whether the callable is a method of a trait which can be implemented in Kotlin.
+    pub is_foreign_implementable: bool,
+}
+
+/// The functions, constructors and methods of `ci`.
+pub(crate) fn named_callables(ci: &ComponentInterface) -> Vec<NamedCallable> {
+    let functions = ci.function_definitions().iter().map(|func| NamedCallable {
+        name: func.name().to_string(),
+        is_async: func.is_async(),
+        is_constructor: false,
+        is_foreign_implementable: false,
+    });
+    let members = ci.object_definitions().iter().flat_map(|obj| {
+        let constructors = obj
+            .constructors()
+            .into_iter()
+            .map(move |cons| NamedCallable {
+                name: format!("{}.{}", obj.name(), cons.name()),
+                is_async: cons.is_async(),
+                is_constructor: true,
+                is_foreign_implementable: false,
+            });
+        let methods = obj.methods().into_iter().map(move |meth| NamedCallable {
+            name: format!("{}.{}", obj.name(), meth.name()),
+            is_async: meth.is_async(),
+            is_constructor: false,
+            is_foreign_implementable: obj.has_callback_interface(),
+        });
+        constructors.chain(methods)
+    });
+    functions.chain(members).collect()
+}
+
 /// Get the name of the interface and class name for a trait.
 ///
 /// For a regular `struct Foo` or `trait Foo`, there's `FooInterface` with `Foo` as
@@ -1266,6 +1892,203 @@ mod filters {
         Ok(KotlinCodeOracle.ffi_default_value(&type_))
     }
 
+    /// The `MemoryLayout` of an FFI type in the `jvm-ffm` bindings.
+    pub fn ffm_layout(type_: &FfiType) -> Result<String, askama::Error> {
+        Ok(super::ffm::layout(type_))
+    }
+
+    /// The Kotlin type passing an FFI type to and from method handles in the `jvm-ffm` bindings.
+    pub fn ffm_carrier(type_: &FfiType) -> Result<&'static str, askama::Error> {
+        Ok(super::ffm::carrier(type_))
+    }
+
+    /// Whether an FFI type is a struct passed by value, needing an arena in the `jvm-ffm` bindings.
+    pub fn ffm_is_by_value_struct(type_: &FfiType) -> Result<bool, askama::Error> {
+        Ok(super::ffm::is_by_value_struct(type_))
+    }
+
+    /// Whether a call in the `jvm-ffm` bindings passes or returns structs by value, which are
+    /// allocated in an arena for the duration of the call.
+    pub fn ffm_needs_arena(
+        arguments: Vec<&FfiArgument>,
+        return_type: &Option<&FfiType>,
+    ) -> Result<bool, askama::Error> {
+        Ok(arguments
+            .iter()
+            .any(|arg| super::ffm::is_by_value_struct(&arg.type_()))
+            || return_type.is_some_and(super::ffm::is_by_value_struct))
+    }
+
+    pub fn ffm_lower(
+        type_: &FfiType,
+        value: &str,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(super::ffm::lower(type_, value, ci))
+    }
+
+    pub fn ffm_lift(
+        type_: &FfiType,
+        value: &str,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(super::ffm::lift(type_, value, ci))
+    }
+
+    pub fn ffm_read(
+        type_: &FfiType,
+        segment: &str,
+        offset: &str,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(super::ffm::read(type_, segment, offset, ci))
+    }
+
+    pub fn ffm_write(
+        type_: &FfiType,
+        segment: &str,
+        offset: &str,
+        value: &str,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(super::ffm::write(type_, segment, offset, value, ci))
+    }
+
+    pub fn ffm_default_carrier_value(type_: &FfiType) -> Result<String, askama::Error> {
+        Ok(super::ffm::default_carrier_value(type_))
+    }
+
+    /// The Kotlin type passing an FFI type from Kotlin to Rust in the `android-jni` bindings.
+    pub fn jni_carrier(type_: &FfiType, ci: &ComponentInterface) -> Result<String, askama::Error> {
+        Ok(super::jni::carrier(type_, ci))
+    }
+
+    /// The Kotlin type passing an FFI type from Rust to Kotlin in the `android-jni` bindings.
+    pub fn jni_carrier_from_rust(
+        type_: &FfiType,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(super::jni::carrier_from_rust(type_, ci))
+    }
+
+    pub fn jni_lower(
+        type_: &FfiType,
+        value: &str,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(super::jni::lower(type_, value, ci))
+    }
+
+    pub fn jni_lift(
+        type_: &FfiType,
+        value: &str,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(super::jni::lift(type_, value, ci))
+    }
+
+    pub fn jni_field_carrier(type_: &FfiType) -> Result<Option<String>, askama::Error> {
+        Ok(super::jni::field_carrier(type_))
+    }
+
+    pub fn jni_field_lower(type_: &FfiType, value: &str) -> Result<String, askama::Error> {
+        Ok(super::jni::field_lower(type_, value))
+    }
+
+    pub fn jni_field_lift(type_: &FfiType, value: &str) -> Result<String, askama::Error> {
+        Ok(super::jni::field_lift(type_, value))
+    }
+
+    pub fn jni_default_value(
+        type_: &FfiType,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(super::jni::default_value(type_, ci))
+    }
+
+    /// The symbol of a JNI function of the `android-jni` bindings.
+    pub fn jni_native_name<S: AsRef<str>>(
+        method: S,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        Ok(super::jni::native_name(method.as_ref(), config))
+    }
+
+    /// The binary name of a class of the `android-jni` bindings, as passed to `FindClass`.
+    pub fn jni_class_name<S: AsRef<str>>(
+        class_name: S,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        Ok(super::jni::class_name(class_name.as_ref(), config))
+    }
+
+    pub fn jni_reference_class(type_: &FfiType) -> Result<String, askama::Error> {
+        Ok(super::jni::reference_class(type_))
+    }
+
+    pub fn jni_field_descriptor(type_: &FfiType, config: &Config) -> Result<String, askama::Error> {
+        Ok(super::jni::field_descriptor(type_, config))
+    }
+
+    pub fn jni_invokable(callback: &FfiCallbackFunction) -> Result<bool, askama::Error> {
+        Ok(super::jni::is_invokable(callback))
+    }
+
+    pub fn jni_upcallable(callback: &FfiCallbackFunction) -> Result<bool, askama::Error> {
+        Ok(super::jni::is_upcallable(callback))
+    }
+
+    pub fn jni_upcall_descriptor(
+        callback: &FfiCallbackFunction,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        Ok(super::jni::upcall_descriptor(callback, config))
+    }
+
+    /// The Rust type of an FFI type in the Rust source file of the `android-jni` bindings.
+    pub fn jni_rust_type(type_: &FfiType) -> Result<String, askama::Error> {
+        Ok(super::jni::rust_type(type_))
+    }
+
+    pub fn jni_sys_type(type_: &FfiType) -> Result<&'static str, askama::Error> {
+        Ok(super::jni::sys_type(type_))
+    }
+
+    pub fn jni_sys_type_from_rust(type_: &FfiType) -> Result<&'static str, askama::Error> {
+        Ok(super::jni::sys_type_from_rust(type_))
+    }
+
+    pub fn jni_jvalue_field(type_: &FfiType) -> Result<&'static str, askama::Error> {
+        Ok(super::jni::jvalue_field(type_))
+    }
+
+    pub fn jni_field_getter(type_: &FfiType) -> Result<String, askama::Error> {
+        Ok(super::jni::field_getter(type_))
+    }
+
+    pub fn jni_call_method(return_type: Option<&FfiType>) -> Result<String, askama::Error> {
+        Ok(super::jni::call_method(return_type))
+    }
+
+    pub fn jni_from_java(
+        type_: &FfiType,
+        value: &str,
+        slot: &str,
+    ) -> Result<String, askama::Error> {
+        Ok(super::jni::from_java(type_, value, slot))
+    }
+
+    pub fn jni_from_java_by_reference(
+        type_: &FfiType,
+        value: &str,
+    ) -> Result<String, askama::Error> {
+        Ok(super::jni::from_java_by_reference(type_, value))
+    }
+
+    pub fn jni_to_java(type_: &FfiType, value: &str) -> Result<String, askama::Error> {
+        Ok(super::jni::to_java(type_, value))
+    }
+
     /// Get the idiomatic Kotlin rendering of a function name.
     pub fn class_name<S: AsRef<str>>(
         nm: S,
@@ -1279,6 +2102,11 @@ mod filters {
         Ok(KotlinCodeOracle.fn_name(nm.as_ref()))
     }
 
+    /// Get the name of the `CompletableFuture` companion of an async function.
+    pub fn completable_future_fn_name<S: AsRef<str>>(nm: S) -> Result<String, askama::Error> {
+        Ok(format!("`{}Async`", nm.as_ref().to_lower_camel_case()))
+    }
+
     /// Get the idiomatic Kotlin rendering of a variable name.
     pub fn var_name<S: AsRef<str>>(nm: S) -> Result<String, askama::Error> {
         Ok(KotlinCodeOracle.var_name(nm.as_ref()))
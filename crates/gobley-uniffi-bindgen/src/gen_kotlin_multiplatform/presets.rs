@@ -0,0 +1,169 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::BTreeSet;
+
+use anyhow::{bail, Result};
+use uniffi_bindgen::interface::Type;
+
+use super::validation::suggest;
+use super::{Config, CustomTypeConfig};
+
+/// A named `custom_types` entry mapping a builtin type to a well-known Kotlin type, e.g.
+/// `custom_types.Uuid = { preset = "kotlin.uuid.Uuid" }`.
+pub(super) struct CustomTypePreset {
+    pub(super) name: &'static str,
+    /// The builtin type the custom type must be declared with in Rust.
+    pub(super) builtin: Type,
+    /// Fully qualified, so the typealias named after the Rust type doesn't clash with an import.
+    type_name: &'static str,
+    lift: &'static str,
+    lower: &'static str,
+    /// The opt-in annotation required to use the Kotlin type.
+    opt_in: Option<&'static str>,
+    /// Whether the Kotlin type is only available on the JVM and Android.
+    pub(super) jvm_only: bool,
+}
+
+impl CustomTypePreset {
+    fn new(name: &'static str, builtin: Type, lift: &'static str, lower: &'static str) -> Self {
+        Self {
+            name,
+            builtin,
+            type_name: name,
+            lift,
+            lower,
+            opt_in: None,
+            jvm_only: false,
+        }
+    }
+
+    fn opt_in(mut self, opt_in: &'static str) -> Self {
+        self.opt_in = Some(opt_in);
+        self
+    }
+
+    fn jvm_only(mut self) -> Self {
+        self.jvm_only = true;
+        self
+    }
+}
+
+fn presets() -> [CustomTypePreset; 8] {
+    [
+        // uuid::Uuid
+        CustomTypePreset::new(
+            "kotlin.uuid.Uuid",
+            Type::String,
+            "kotlin.uuid.Uuid.parse({})",
+            "{}.toString()",
+        )
+        .opt_in("kotlin.uuid.ExperimentalUuidApi"),
+        CustomTypePreset::new(
+            "java.util.UUID",
+            Type::String,
+            "java.util.UUID.fromString({})",
+            "{}.toString()",
+        )
+        .jvm_only(),
+        // url::Url
+        CustomTypePreset::new(
+            "io.ktor.http.Url",
+            Type::String,
+            "io.ktor.http.Url({})",
+            "{}.toString()",
+        ),
+        CustomTypePreset::new(
+            "java.net.URI",
+            Type::String,
+            "java.net.URI({})",
+            "{}.toString()",
+        )
+        .jvm_only(),
+        // RFC 3339 date and time strings from chrono or time
+        CustomTypePreset::new(
+            "kotlinx.datetime.Instant",
+            Type::String,
+            "kotlinx.datetime.Instant.parse({})",
+            "{}.toString()",
+        ),
+        CustomTypePreset::new(
+            "kotlinx.datetime.LocalDate",
+            Type::String,
+            "kotlinx.datetime.LocalDate.parse({})",
+            "{}.toString()",
+        ),
+        // rust_decimal::Decimal
+        CustomTypePreset::new(
+            "java.math.BigDecimal",
+            Type::String,
+            "java.math.BigDecimal({})",
+            "{}.toPlainString()",
+        )
+        .jvm_only(),
+        // std::net::IpAddr. Parsing an IP address literal doesn't perform a DNS lookup.
+        CustomTypePreset::new(
+            "java.net.InetAddress",
+            Type::String,
+            "java.net.InetAddress.getByName({})",
+            "{}.hostAddress",
+        )
+        .jvm_only(),
+    ]
+}
+
+/// Finds the preset with the given name.
+pub(super) fn find_preset(name: &str) -> Option<CustomTypePreset> {
+    presets().into_iter().find(|preset| preset.name == name)
+}
+
+impl CustomTypeConfig {
+    /// Fills in the values not given by the user from the preset.
+    fn apply_preset(&mut self, preset: &CustomTypePreset) {
+        self.type_name
+            .get_or_insert_with(|| preset.type_name.to_string());
+        if self.lift.is_empty() && self.into_custom.is_empty() {
+            self.lift = preset.lift.to_string();
+        }
+        if self.lower.is_empty() && self.from_custom.is_empty() {
+            self.lower = preset.lower.to_string();
+        }
+        self.opt_in = preset.opt_in.map(str::to_string);
+    }
+}
+
+impl Config {
+    /// Expands the `preset` of each entry of `custom_types`.
+    pub(crate) fn apply_custom_type_presets(&mut self) -> Result<()> {
+        for (name, custom_type) in &mut self.custom_types {
+            let Some(preset_name) = &custom_type.preset else {
+                continue;
+            };
+            let Some(preset) = find_preset(preset_name) else {
+                let names = presets().map(|preset| preset.name);
+                match suggest(preset_name, names) {
+                    Some(suggestion) => bail!(
+                        "unknown preset `{preset_name}` in `custom_types.{name}`, did you mean `{suggestion}`?"
+                    ),
+                    None => bail!(
+                        "unknown preset `{preset_name}` in `custom_types.{name}`, expected one of {}",
+                        names.join(", ")
+                    ),
+                }
+            };
+            custom_type.apply_preset(&preset);
+        }
+        Ok(())
+    }
+
+    /// The opt-in annotations required by the Kotlin types of the custom types.
+    pub(super) fn custom_type_opt_ins(&self) -> BTreeSet<&str> {
+        self.custom_types
+            .values()
+            .filter_map(|custom_type| custom_type.opt_in.as_deref())
+            .collect()
+    }
+}
@@ -0,0 +1,705 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, Result};
+use heck::ToUpperCamelCase;
+use serde::de::{DeserializeOwned, Deserializer, Visitor};
+use toml::value::{Table, Value};
+
+use uniffi_bindgen::interface::{Enum, Type};
+use uniffi_meta::LiteralMetadata;
+
+use super::presets::find_preset;
+use super::{
+    CinteropConfig, Config, ConfigKotlinTarget, CustomTypeConfig, CustomTypeTargetConfig,
+    EnumSelection, ErrorConfig, ErrorTargetConfig, NamedCallable, SourceSetConfig,
+};
+
+/// Keys used by the other bindings generators sharing `uniffi.toml`.
+const IGNORED_KEYS: &[&str] = &["bindings"];
+
+/// The keys `T` accepts, as listed by its `Deserialize` implementation, so they can't get out of
+/// sync with the fields of the configuration.
+fn field_names<T: DeserializeOwned>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    // The deserializer fails once it has recorded the fields.
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+/// A deserializer recording the fields of the struct deserialized from it.
+struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for FieldNames<'_> {
+    type Error = serde::de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(serde::de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(serde::de::Error::custom("fields recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// Returns a message for each key in `config` not used by the bindgen, suggesting the key the
+/// user most likely meant.
+pub(crate) fn find_unknown_keys(config: &Value) -> Vec<String> {
+    let mut unknown_keys = Vec::new();
+    let Some(config) = config.as_table() else {
+        return unknown_keys;
+    };
+
+    check_keys(config, "", field_names::<Config>(), &mut unknown_keys);
+    if let Some(custom_types) = config.get("custom_types").and_then(Value::as_table) {
+        for (name, custom_type) in custom_types {
+            if let Some(custom_type) = custom_type.as_table() {
+                let prefix = format!("custom_types.{name}.");
+                check_keys(
+                    custom_type,
+                    &prefix,
+                    field_names::<CustomTypeConfig>(),
+                    &mut unknown_keys,
+                );
+                for target in ["jvm", "android", "native"] {
+                    if let Some(target_config) = custom_type.get(target).and_then(Value::as_table) {
+                        let prefix = format!("custom_types.{name}.{target}.");
+                        check_keys(
+                            target_config,
+                            &prefix,
+                            field_names::<CustomTypeTargetConfig>(),
+                            &mut unknown_keys,
+                        );
+                    }
+                }
+            }
+        }
+    }
+    if let Some(errors) = config.get("errors").and_then(Value::as_table) {
+        for (name, error) in errors {
+            if let Some(error) = error.as_table() {
+                let prefix = format!("errors.{name}.");
+                check_keys(
+                    error,
+                    &prefix,
+                    field_names::<ErrorConfig>(),
+                    &mut unknown_keys,
+                );
+                for target in ["jvm", "android", "native"] {
+                    if let Some(target_config) = error.get(target).and_then(Value::as_table) {
+                        let prefix = format!("errors.{name}.{target}.");
+                        check_keys(
+                            target_config,
+                            &prefix,
+                            field_names::<ErrorTargetConfig>(),
+                            &mut unknown_keys,
+                        );
+                    }
+                }
+            }
+        }
+    }
+    if let Some(source_sets) = config.get("kotlin_source_sets").and_then(Value::as_table) {
+        check_keys(
+            source_sets,
+            "kotlin_source_sets.",
+            field_names::<SourceSetConfig>(),
+            &mut unknown_keys,
+        );
+    }
+
+    if let Some(cinterop) = config.get("cinterop").and_then(Value::as_table) {
+        check_keys(
+            cinterop,
+            "cinterop.",
+            field_names::<CinteropConfig>(),
+            &mut unknown_keys,
+        );
+    }
+
+    unknown_keys
+}
+
+fn check_keys(table: &Table, prefix: &str, known_keys: &[&str], unknown_keys: &mut Vec<String>) {
+    for key in table.keys() {
+        // Properties starting with `__gradle_` are used by the Gradle plugins only.
+        if known_keys.contains(&key.as_str())
+            || (prefix.is_empty()
+                && (IGNORED_KEYS.contains(&key.as_str()) || key.starts_with("__gradle_")))
+        {
+            continue;
+        }
+        unknown_keys.push(match suggest(key, known_keys.iter().copied()) {
+            Some(suggestion) => {
+                format!("unknown key `{prefix}{key}`, did you mean `{prefix}{suggestion}`?")
+            }
+            None => format!("unknown key `{prefix}{key}`"),
+        });
+    }
+}
+
+/// Finds the candidate closest to `input`, if it is close enough to be a typo.
+pub(super) fn suggest<'a>(
+    input: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (input.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(input, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current_row = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous_row[j] + usize::from(a != *b);
+            let insertion = current_row[j] + 1;
+            let deletion = previous_row[j + 1] + 1;
+            current_row.push(substitution.min(insertion).min(deletion));
+        }
+        previous_row = current_row;
+    }
+    previous_row[b.len()]
+}
+
+/// The maximum value of the discriminant type of an enum, used as the discriminant of the unknown
+/// variant.
+fn max_discriminant(type_: &Type) -> Option<u64> {
+    Some(match type_ {
+        Type::UInt8 => u8::MAX.into(),
+        Type::Int8 => i8::MAX as u64,
+        Type::UInt16 => u16::MAX.into(),
+        Type::Int16 => i16::MAX as u64,
+        Type::UInt32 => u32::MAX.into(),
+        Type::Int32 => i32::MAX as u64,
+        Type::UInt64 => u64::MAX,
+        Type::Int64 => i64::MAX as u64,
+        _ => return None,
+    })
+}
+
+/// Whether `version` looks like a Kotlin version, e.g. `2.1.0`, `2.1` or `2.2.0-Beta1`.
+fn is_valid_kotlin_version(version: &str) -> bool {
+    let (numbers, suffix) = match version.split_once('-') {
+        Some((numbers, suffix)) => (numbers, Some(suffix)),
+        None => (version, None),
+    };
+    let numbers: Vec<&str> = numbers.split('.').collect();
+    (2..=3).contains(&numbers.len())
+        && numbers
+            .iter()
+            .all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        && suffix.map_or(true, |suffix| {
+            !suffix.is_empty()
+                && suffix
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        })
+}
+
+impl Config {
+    /// The bindings outside the JVM and Android source sets referring to the Kotlin type
+    /// `type_name` of the custom type `name`.
+    fn non_jvm_source_sets_using_type(&self, name: &str, type_name: &str) -> Vec<&'static str> {
+        let Some(custom_type) = self.custom_types.get(name) else {
+            return Vec::new();
+        };
+        let mut source_sets = Vec::new();
+        // The common bindings only declare an expect class when the targets use different types.
+        if self.kotlin_multiplatform && !self.custom_type_is_expect_class(name) {
+            source_sets.push("common");
+        }
+        for (target, enabled) in [
+            (
+                "native",
+                self.kotlin_targets.contains(&ConfigKotlinTarget::Native),
+            ),
+            (
+                "stub",
+                self.kotlin_targets.contains(&ConfigKotlinTarget::Stub),
+            ),
+        ] {
+            if enabled && custom_type.for_target(target).type_name.as_deref() == Some(type_name) {
+                source_sets.push(target);
+            }
+        }
+        source_sets
+    }
+
+    /// Records the unknown keys found by [`find_unknown_keys`] in the configuration of the crate,
+    /// reported by [`Config::validate`] once the crate name is known.
+    pub(crate) fn with_unknown_keys(mut self, unknown_keys: Vec<String>) -> Self {
+        self.unknown_keys = unknown_keys;
+        self
+    }
+
+    /// Checks the configuration of `origin`, e.g. ``crate `foo` in `foo/uniffi.toml` ``, against
+    /// the component interfaces the bindings are generated for. Problems that make the bindings
+    /// wrong are returned as an error, while unknown keys and the entries that are only unused are
+    /// reported as warnings.
+    ///
+    /// `custom_types`, the builtin types of the custom types by their names, `error_names`,
+    /// `crate_names`, `enums` and `callables` contain the custom types, the error types, the
+    /// crates, the enums and the functions of every component, as the same configuration file may
+    /// be shared by several crates.
+    pub(crate) fn validate(
+        &self,
+        origin: &str,
+        custom_types: &HashMap<String, Type>,
+        error_names: &HashSet<String>,
+        crate_names: &HashSet<String>,
+        enums: &HashMap<String, Enum>,
+        callables: &[NamedCallable],
+    ) -> Result<()> {
+        let mut errors = Vec::new();
+        if let Some(version) = &self.kotlin_target_version {
+            if !is_valid_kotlin_version(version) {
+                errors.push(format!(
+                    "invalid `kotlin_target_version` `{version}`, expected a Kotlin version such as `2.1.0`"
+                ));
+            }
+        }
+
+        // The intermediate source set calls Rust through JNA, which the `jvm-ffm` bindings don't use.
+        if self.generate_jvm_common_bindings() && self.jvm_uses_ffm() {
+            errors.push(String::from(
+                "the `jvm-ffm` target can't share `kotlin_source_sets.jvm_common` with Android, which uses JNA",
+            ));
+        }
+
+        // Likewise, the `android-jni` bindings don't use JNA.
+        if self.generate_jvm_common_bindings() && self.android_uses_jni() {
+            errors.push(String::from(
+                "the `android-jni` target can't share `kotlin_source_sets.jvm_common` with the JVM, which uses JNA",
+            ));
+        }
+
+        // With an intermediate source set, the JVM and Android targets share the conversions.
+        if self.generate_jvm_common_bindings() && self.has_jvm_target() && self.has_android_target()
+        {
+            let mut custom_type_names: Vec<&String> = self.custom_types.keys().collect();
+            custom_type_names.sort();
+            for name in custom_type_names {
+                let custom_type = &self.custom_types[name];
+                if custom_type.for_target("jvm") != custom_type.for_target("android") {
+                    errors.push(format!(
+                        "`custom_types.{name}` has different `jvm` and `android` overrides, which can't be shared by `kotlin_source_sets.jvm_common`"
+                    ));
+                }
+            }
+            let mut error_config_names: Vec<&String> = self.errors.keys().collect();
+            error_config_names.sort();
+            for name in error_config_names {
+                let error = &self.errors[name];
+                if error.superclass("jvm") != error.superclass("android") {
+                    errors.push(format!(
+                        "`errors.{name}` has different `jvm` and `android` superclasses, which can't be shared by `kotlin_source_sets.jvm_common`"
+                    ));
+                }
+            }
+        }
+
+        let mut preset_names: Vec<(&String, &String)> = self
+            .custom_types
+            .iter()
+            .filter_map(|(name, custom_type)| Some((name, custom_type.preset.as_ref()?)))
+            .collect();
+        preset_names.sort();
+        for (name, preset_name) in preset_names {
+            // Unknown presets are rejected when the configuration is loaded.
+            let Some(preset) = find_preset(preset_name) else {
+                continue;
+            };
+            if let Some(builtin) = custom_types.get(name) {
+                if *builtin != preset.builtin {
+                    errors.push(format!(
+                        "`custom_types.{name}` uses the preset `{}` expecting the builtin type `{:?}`, but the custom type is declared with `{builtin:?}`",
+                        preset.name, preset.builtin
+                    ));
+                }
+            }
+            if preset.jvm_only {
+                let source_sets = self.non_jvm_source_sets_using_type(name, preset.name);
+                if !source_sets.is_empty() {
+                    errors.push(format!(
+                        "`custom_types.{name}` uses the preset `{}` available only on the JVM and Android, but the {} bindings refer to it",
+                        preset.name,
+                        source_sets.join(" and ")
+                    ));
+                }
+            }
+        }
+
+        // The variant added for unknown values must not clash with the variants of the enum.
+        let mut unknown_variant_enum_names: Vec<&String> = enums
+            .keys()
+            .filter(|name| self.generate_unknown_enum_variant(name) && !error_names.contains(*name))
+            .collect();
+        unknown_variant_enum_names.sort();
+        for name in unknown_variant_enum_names {
+            let e = &enums[name];
+            if e.variants()
+                .iter()
+                .any(|variant| variant.name().to_upper_camel_case() == "Unknown")
+            {
+                errors.push(format!(
+                    "`generate_unknown_enum_variants` selects `{name}`, which already has an `Unknown` variant"
+                ));
+            }
+            let max = match e.variant_discr_type() {
+                Some(discr_type) if e.is_flat() => max_discriminant(discr_type),
+                _ => None,
+            };
+            if let Some(max) = max {
+                for (index, variant) in e.variants().iter().enumerate() {
+                    let is_max = match e.variant_discr(index)? {
+                        LiteralMetadata::UInt(value, ..) => value == max,
+                        LiteralMetadata::Int(value, ..) => u64::try_from(value) == Ok(max),
+                        _ => false,
+                    };
+                    if is_max {
+                        errors.push(format!(
+                            "`generate_unknown_enum_variants` selects `{name}`, whose variant `{}` has the discriminant `MAX_VALUE` used by the unknown variant",
+                            variant.name()
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Unknown keys are only reported as warnings until the next minor release.
+        let mut warnings = self.unknown_keys.clone();
+        for entry in &self.suspend_sync_calls {
+            if entry == "*" {
+                continue;
+            }
+            // `<Object>` selects every method of the object.
+            let matching: Vec<&NamedCallable> = callables
+                .iter()
+                .filter(|callable| {
+                    callable.name == *entry
+                        || callable
+                            .name
+                            .split_once('.')
+                            .is_some_and(|(object_name, _)| object_name == entry)
+                })
+                .collect();
+            if matching.is_empty() {
+                let mut names: Vec<&str> = callables
+                    .iter()
+                    .flat_map(|callable| {
+                        let object_name = callable.name.split_once('.').map(|(name, _)| name);
+                        [Some(callable.name.as_str()), object_name]
+                    })
+                    .flatten()
+                    .collect();
+                names.sort();
+                names.dedup();
+                warnings.push(match suggest(entry, names) {
+                    Some(suggestion) => format!(
+                        "`suspend_sync_calls` contains `{entry}`, which doesn't match any function, object or method, did you mean `{suggestion}`?"
+                    ),
+                    None => format!(
+                        "`suspend_sync_calls` contains `{entry}`, which doesn't match any function, object or method"
+                    ),
+                });
+            } else if !matching.iter().any(|callable| {
+                !callable.is_async && !callable.is_constructor && !callable.is_foreign_implementable
+            }) {
+                warnings.push(format!(
+                    "`suspend_sync_calls` contains `{entry}`, but only sync functions and methods of objects not implemented in Kotlin become `suspend` functions"
+                ));
+            }
+        }
+        if let EnumSelection::Only(names) = &self.generate_unknown_enum_variants {
+            for name in names {
+                if enums.contains_key(name) {
+                    continue;
+                }
+                warnings.push(
+                    match suggest(name, enums.keys().map(String::as_str)) {
+                        Some(suggestion) => format!(
+                            "`generate_unknown_enum_variants` contains `{name}`, which doesn't match any enum, did you mean `{suggestion}`?"
+                        ),
+                        None => format!(
+                            "`generate_unknown_enum_variants` contains `{name}`, which doesn't match any enum"
+                        ),
+                    },
+                );
+            }
+        }
+        let mut custom_type_names: Vec<&String> = self.custom_types.keys().collect();
+        custom_type_names.sort();
+        for name in custom_type_names {
+            if custom_types.contains_key(name) {
+                continue;
+            }
+            warnings.push(
+                match suggest(name, custom_types.keys().map(String::as_str)) {
+                    Some(suggestion) => format!(
+                        "`custom_types.{name}` doesn't match any custom type, did you mean `{suggestion}`?"
+                    ),
+                    None => format!("`custom_types.{name}` doesn't match any custom type"),
+                },
+            );
+        }
+        let mut error_config_names: Vec<&String> = self.errors.keys().collect();
+        error_config_names.sort();
+        for name in error_config_names {
+            if error_names.contains(name) {
+                continue;
+            }
+            warnings.push(
+                match suggest(name, error_names.iter().map(String::as_str)) {
+                    Some(suggestion) => format!(
+                        "`errors.{name}` doesn't match any error type, did you mean `{suggestion}`?"
+                    ),
+                    None => format!("`errors.{name}` doesn't match any error type"),
+                },
+            );
+        }
+        let mut external_crate_names: Vec<&String> = self.external_packages.keys().collect();
+        external_crate_names.sort();
+        for name in external_crate_names {
+            if crate_names.contains(name) {
+                continue;
+            }
+            warnings.push(
+                match suggest(name, crate_names.iter().map(String::as_str)) {
+                    Some(suggestion) => format!(
+                        "`external_packages.{name}` doesn't match any crate, did you mean `{suggestion}`?"
+                    ),
+                    None => format!("`external_packages.{name}` doesn't match any crate"),
+                },
+            );
+        }
+
+        for warning in warnings {
+            eprintln!("Warning: configuration of {origin}: {warning}");
+        }
+        if !errors.is_empty() {
+            bail!(
+                "invalid configuration of {origin}:\n{}",
+                errors
+                    .iter()
+                    .map(|error| format!("  - {error}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(contents: &str) -> Config {
+        let mut config: Config = toml::from_str(contents).unwrap();
+        config.apply_custom_type_presets().unwrap();
+        config
+    }
+
+    /// Validates `config` against a component declaring the custom type `Url` of `String`.
+    fn validate(config: &Config) -> Result<()> {
+        let custom_types = HashMap::from([(String::from("Url"), Type::String)]);
+        config.validate(
+            "crate `component`",
+            &custom_types,
+            &HashSet::new(),
+            &HashSet::new(),
+            &HashMap::new(),
+            &[],
+        )
+    }
+
+    fn validation_error(config: &Config) -> String {
+        validate(config).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn jvm_common_is_shared_by_jna_targets() {
+        let config = config(
+            r#"
+            kotlin_multiplatform = true
+            kotlin_targets = ["jvm", "android"]
+            [kotlin_source_sets]
+            jvm_common = "jvmCommonMain"
+            "#,
+        );
+        validate(&config).unwrap();
+    }
+
+    #[test]
+    fn jvm_common_rejects_jvm_ffm() {
+        let config = config(
+            r#"
+            kotlin_multiplatform = true
+            kotlin_targets = ["jvm-ffm", "android"]
+            [kotlin_source_sets]
+            jvm_common = "jvmCommonMain"
+            "#,
+        );
+        assert!(validation_error(&config).contains(
+            "the `jvm-ffm` target can't share `kotlin_source_sets.jvm_common` with Android"
+        ));
+    }
+
+    #[test]
+    fn jvm_common_rejects_android_jni() {
+        let config = config(
+            r#"
+            kotlin_multiplatform = true
+            kotlin_targets = ["jvm", "android-jni"]
+            [kotlin_source_sets]
+            jvm_common = "jvmCommonMain"
+            "#,
+        );
+        assert!(validation_error(&config).contains(
+            "the `android-jni` target can't share `kotlin_source_sets.jvm_common` with the JVM"
+        ));
+    }
+
+    #[test]
+    fn jvm_common_rejects_different_jvm_and_android_overrides() {
+        let config = config(
+            r#"
+            kotlin_multiplatform = true
+            kotlin_targets = ["jvm", "android"]
+            [kotlin_source_sets]
+            jvm_common = "jvmCommonMain"
+            [custom_types.Url]
+            type_name = "String"
+            [custom_types.Url.jvm]
+            type_name = "java.net.URI"
+            "#,
+        );
+        assert!(validation_error(&config)
+            .contains("`custom_types.Url` has different `jvm` and `android` overrides"));
+    }
+
+    #[test]
+    fn jvm_only_preset_is_rejected_on_native() {
+        let native_config = config(
+            r#"
+            kotlin_multiplatform = true
+            kotlin_targets = ["jvm", "native"]
+            [custom_types.Url]
+            preset = "java.net.URI"
+            "#,
+        );
+        assert!(validation_error(&native_config).contains(
+            "`custom_types.Url` uses the preset `java.net.URI` available only on the JVM and Android, but the common and native bindings refer to it"
+        ));
+
+        let jvm_config = config(
+            r#"
+            kotlin_multiplatform = true
+            kotlin_targets = ["jvm", "android"]
+            [custom_types.Url]
+            preset = "java.net.URI"
+            "#,
+        );
+        validate(&jvm_config).unwrap();
+    }
+
+    #[test]
+    fn preset_must_match_builtin_type() {
+        let config = config(
+            r#"
+            [custom_types.Url]
+            preset = "kotlinx.datetime.Instant"
+            "#,
+        );
+        let custom_types = HashMap::from([(String::from("Url"), Type::Int64)]);
+        let error = config
+            .validate(
+                "crate `component`",
+                &custom_types,
+                &HashSet::new(),
+                &HashSet::new(),
+                &HashMap::new(),
+                &[],
+            )
+            .unwrap_err();
+        assert!(error.to_string().contains(
+            "`custom_types.Url` uses the preset `kotlinx.datetime.Instant` expecting the builtin type `String`, but the custom type is declared with `Int64`"
+        ));
+    }
+
+    #[test]
+    fn invalid_kotlin_target_version_is_rejected() {
+        let config = config("kotlin_target_version = \"latest\"\n");
+        assert!(validation_error(&config).contains("invalid `kotlin_target_version` `latest`"));
+
+        for version in ["2.1.0", "2.1", "2.2.0-Beta1"] {
+            assert!(is_valid_kotlin_version(version), "{version}");
+        }
+        for version in ["2", "2.1.", "v2.1.0", "2.1.0-"] {
+            assert!(!is_valid_kotlin_version(version), "{version}");
+        }
+    }
+
+    #[test]
+    fn unknown_keys_suggest_known_keys() {
+        let config: Value = toml::from_str(
+            r#"
+            kotlin_target = ["jvm"]
+            __gradle_android_target = "android"
+            [bindings.kotlin]
+            package_name = "component"
+            [custom_types.Url]
+            type_nmae = "String"
+            [custom_types.Url.jvm]
+            completely_unrelated = true
+            [kotlin_source_sets]
+            jvmcommon = "jvmCommonMain"
+            "#,
+        )
+        .unwrap();
+        let mut unknown_keys = find_unknown_keys(&config);
+        unknown_keys.sort();
+        assert_eq!(
+            unknown_keys,
+            vec![
+                "unknown key `custom_types.Url.jvm.completely_unrelated`",
+                "unknown key `custom_types.Url.type_nmae`, did you mean `custom_types.Url.type_name`?",
+                "unknown key `kotlin_source_sets.jvmcommon`, did you mean `kotlin_source_sets.jvm_common`?",
+                "unknown key `kotlin_target`, did you mean `kotlin_targets`?",
+            ],
+        );
+    }
+
+    #[test]
+    fn suggest_ignores_distant_candidates() {
+        assert_eq!(
+            suggest("Uuid", ["Url", "Uuid2", "Timestamp"]),
+            Some("Uuid2")
+        );
+        assert_eq!(suggest("Duration", ["Url", "Uuid"]), None);
+    }
+}
@@ -4,22 +4,39 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-use std::{collections::HashMap, fs::File, io::Write, process::Command};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, HashSet},
+    process::Command,
+};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use camino::{Utf8Path, Utf8PathBuf};
 use fs_err as fs;
-use uniffi_bindgen::{BindingGenerator, Component, ComponentInterface, GenerationSettings};
+use uniffi_bindgen::{
+    interface::Type, BindgenCrateConfigSupplier, BindingGenerator, Component, ComponentInterface,
+    EmptyCrateConfigSupplier, GenerationSettings,
+};
 
 mod gen_kotlin_multiplatform;
-use gen_kotlin_multiplatform::{generate_bindings, Config};
+use gen_kotlin_multiplatform::{
+    generate_bindings, named_callables, validation::find_unknown_keys, Config,
+};
 
+mod workspace_config;
+pub use workspace_config::{WorkspaceConfig, WORKSPACE_CONFIG_FILE_NAME};
+
+/// Generates the Kotlin Multiplatform bindings using the configuration of each crate only. Use
+/// [`ConfiguredKotlinBindingGenerator`] to apply workspace-level defaults or to print the
+/// configuration instead.
+#[derive(Debug, Clone, Copy, Default)]
 pub struct KotlinBindingGenerator;
+
 impl BindingGenerator for KotlinBindingGenerator {
     type Config = Config;
 
     fn new_config(&self, root_toml: &toml::value::Value) -> Result<Self::Config> {
-        Ok(root_toml.clone().try_into()?)
+        ConfiguredKotlinBindingGenerator::default().new_config(root_toml)
     }
 
     fn update_component_configs(
@@ -27,6 +44,131 @@ impl BindingGenerator for KotlinBindingGenerator {
         settings: &GenerationSettings,
         components: &mut Vec<Component<Self::Config>>,
     ) -> Result<()> {
+        ConfiguredKotlinBindingGenerator::default().update_component_configs(settings, components)
+    }
+
+    fn write_bindings(
+        &self,
+        settings: &GenerationSettings,
+        components: &[Component<Self::Config>],
+    ) -> Result<()> {
+        ConfiguredKotlinBindingGenerator::default().write_bindings(settings, components)
+    }
+}
+
+/// A [`KotlinBindingGenerator`] with the options of the `gobley-uniffi-bindgen` CLI.
+#[derive(Debug, Clone, Default)]
+pub struct ConfiguredKotlinBindingGenerator {
+    workspace_config: Option<WorkspaceConfig>,
+    print_config: bool,
+    config_paths: HashMap<String, Utf8PathBuf>,
+    config_override: Option<Utf8PathBuf>,
+}
+
+impl ConfiguredKotlinBindingGenerator {
+    /// Uses the given workspace-level defaults for every crate.
+    pub fn with_workspace_config(mut self, workspace_config: Option<WorkspaceConfig>) -> Self {
+        self.workspace_config = workspace_config;
+        self
+    }
+
+    /// Prints the effective configuration of each crate to the standard output instead of
+    /// writing the bindings.
+    pub fn with_print_config(mut self, print_config: bool) -> Self {
+        self.print_config = print_config;
+        self
+    }
+
+    /// Names the configuration files in the errors and warnings about the configuration of each
+    /// crate. `config_paths` contains the `uniffi.toml` of each crate by the crate name, and
+    /// `config_override` the file given with `--config`, which applies to every crate.
+    pub fn with_config_paths(
+        mut self,
+        config_paths: HashMap<String, Utf8PathBuf>,
+        config_override: Option<Utf8PathBuf>,
+    ) -> Self {
+        self.config_paths = config_paths;
+        self.config_override = config_override;
+        self
+    }
+
+    /// Describes the configuration of `crate_name` in the errors and warnings about it.
+    fn config_origin(&self, crate_name: &str) -> String {
+        let paths: Vec<String> = self
+            .config_paths
+            .get(crate_name)
+            .into_iter()
+            .chain(&self.config_override)
+            .map(|path| format!("`{path}`"))
+            .collect();
+        if paths.is_empty() {
+            format!("crate `{crate_name}`")
+        } else {
+            format!("crate `{crate_name}` in {}", paths.join(" and "))
+        }
+    }
+}
+
+impl BindingGenerator for ConfiguredKotlinBindingGenerator {
+    type Config = Config;
+
+    fn new_config(&self, root_toml: &toml::value::Value) -> Result<Self::Config> {
+        // Keys in the workspace configuration are checked when it is loaded.
+        let unknown_keys = find_unknown_keys(root_toml);
+        let mut config: Config = match &self.workspace_config {
+            Some(workspace_config) => workspace_config.merge(root_toml).try_into()?,
+            None => root_toml.clone().try_into()?,
+        };
+        config.apply_custom_type_presets()?;
+        Ok(config.with_unknown_keys(unknown_keys))
+    }
+
+    fn update_component_configs(
+        &self,
+        settings: &GenerationSettings,
+        components: &mut Vec<Component<Self::Config>>,
+    ) -> Result<()> {
+        // Check the configuration before filling in the defaults below, so only the values given
+        // by the user are validated.
+        let mut custom_types = HashMap::new();
+        let mut error_names = HashSet::new();
+        let mut crate_names = HashSet::new();
+        let mut enums = HashMap::new();
+        for c in &*components {
+            crate_names.insert(c.ci.crate_name().to_string());
+            for e in c.ci.enum_definitions() {
+                enums.insert(e.name().to_string(), e.clone());
+            }
+            for ty in c.ci.iter_types() {
+                if let Type::Custom { name, builtin, .. } = ty {
+                    custom_types.insert(name.clone(), (**builtin).clone());
+                }
+                if let Some(name) = ty.name() {
+                    if c.ci.is_name_used_as_error(name) {
+                        error_names.insert(name.to_string());
+                    }
+                }
+                if let Some(module_path) = ty.module_path() {
+                    let crate_name = module_path.split("::").next().unwrap();
+                    crate_names.insert(crate_name.to_string());
+                }
+            }
+        }
+        let callables: Vec<_> = components
+            .iter()
+            .flat_map(|c| named_callables(&c.ci))
+            .collect();
+        for c in &*components {
+            c.config.validate(
+                &self.config_origin(c.ci.crate_name()),
+                &custom_types,
+                &error_names,
+                &crate_names,
+                &enums,
+                &callables,
+            )?;
+        }
+
         for c in &mut *components {
             c.config
                 .package_name
@@ -63,75 +205,388 @@ impl BindingGenerator for KotlinBindingGenerator {
         settings: &GenerationSettings,
         components: &[Component<Self::Config>],
     ) -> Result<()> {
-        for Component { ci, config, .. } in components {
-            let bindings = generate_bindings(config, ci)?;
+        if self.print_config {
+            for Component { ci, config, .. } in components {
+                println!("{}", render_config(ci.crate_name(), config)?);
+            }
+            return Ok(());
+        }
 
-            write_bindings_target(ci, settings, config, "common", bindings.common);
+        let mut kotlin_files = Vec::new();
+        for Component { ci, config, .. } in components {
+            for (path, content) in render_bindings(ci, config)? {
+                let file_path = settings.out_dir.join(path);
+                if let Some(dest_dir) = file_path.parent() {
+                    fs::create_dir_all(dest_dir)?;
+                }
+                fs::write(&file_path, content)?;
 
-            if let Some(jvm) = bindings.jvm {
-                write_bindings_target(ci, settings, config, "jvm", jvm);
-            }
-            if let Some(android) = bindings.android {
-                write_bindings_target(ci, settings, config, "android", android);
-            }
-            if let Some(native) = bindings.native {
-                write_bindings_target(ci, settings, config, "native", native);
-            }
-            if let Some(stub) = bindings.stub {
-                write_bindings_target(ci, settings, config, "stub", stub);
+                if file_path.extension() == Some("kt") {
+                    kotlin_files.push(file_path);
+                }
             }
+        }
 
-            if let Some(header) = bindings.header {
-                write_cinterop(ci, &settings.out_dir, header);
+        if settings.try_format_code && !kotlin_files.is_empty() {
+            println!(
+                "Code generation complete, formatting with ktlint (use --no-format to disable)"
+            );
+            for file_path in &kotlin_files {
+                format_code(file_path);
             }
         }
         Ok(())
     }
 }
 
-fn write_bindings_target(
+/// Renders the effective configuration of a crate printed with `--print-config`.
+fn render_config(crate_name: &str, config: &Config) -> Result<String> {
+    let config = toml::Value::try_from(config)?;
+    Ok(format!(
+        "# {crate_name}\n{}",
+        toml::to_string_pretty(&config)?
+    ))
+}
+
+/// Renders the bindings of a component, returning the paths of the files relative to the output
+/// directory and their contents.
+fn render_bindings(ci: &ComponentInterface, config: &Config) -> Result<Vec<(Utf8PathBuf, String)>> {
+    let bindings = generate_bindings(config, ci)?;
+    let mut files = vec![bindings_target_file(ci, config, "common", bindings.common)];
+
+    if let Some(jvm_common) = bindings.jvm_common {
+        files.push(bindings_target_file(ci, config, "jvmCommon", jvm_common));
+    }
+    if let Some(jvm) = bindings.jvm {
+        files.push(bindings_target_file(ci, config, "jvm", jvm));
+    }
+    if let Some(android) = bindings.android {
+        files.push(bindings_target_file(ci, config, "android", android));
+    }
+    if let Some(native) = bindings.native {
+        files.push(bindings_target_file(ci, config, "native", native));
+    }
+    if let Some(stub) = bindings.stub {
+        files.push(bindings_target_file(ci, config, "stub", stub));
+    }
+
+    if let Some(header) = bindings.header {
+        files.push(cinterop_file(ci, "h", header));
+    }
+    if let Some(cinterop_def) = bindings.cinterop_def {
+        files.push(cinterop_file(ci, "def", cinterop_def));
+    }
+    if let Some(jni_shim) = bindings.jni_shim {
+        // Included by the crate the library is built from, see `include_jni_shim`.
+        let file_path = Utf8PathBuf::from("jni").join(format!("{}.rs", ci.namespace()));
+        files.push((file_path, jni_shim));
+    }
+    Ok(files)
+}
+
+fn bindings_target_file(
     ci: &ComponentInterface,
-    settings: &GenerationSettings,
     config: &Config,
     target: &str,
     content: String,
-) {
-    let source_set_name = if config.kotlin_multiplatform {
-        format!("{}Main", target)
-    } else {
-        String::from("main")
-    };
+) -> (Utf8PathBuf, String) {
+    let source_set_name = config.source_set_name(target);
     let package_path: Utf8PathBuf = config.package_name().split('.').collect();
     let file_name = format!("{}.{}.kt", ci.namespace(), target);
 
-    let dest_dir = Utf8PathBuf::from(&settings.out_dir)
-        .join(source_set_name)
+    let file_path = Utf8PathBuf::from(source_set_name)
         .join("kotlin")
-        .join(package_path);
-    let file_path = Utf8PathBuf::from(&dest_dir).join(file_name);
+        .join(package_path)
+        .join(file_name);
+    (file_path, content)
+}
 
-    fs::create_dir_all(dest_dir).unwrap();
-    fs::write(&file_path, content).unwrap();
+fn cinterop_file(
+    ci: &ComponentInterface,
+    extension: &str,
+    content: String,
+) -> (Utf8PathBuf, String) {
+    let file_path = Utf8PathBuf::from("nativeInterop")
+        .join("cinterop")
+        .join("headers")
+        .join(ci.namespace())
+        .join(format!("{}.{extension}", ci.namespace()));
+    (file_path, content)
+}
 
-    if settings.try_format_code {
-        println!("Code generation complete, formatting with ktlint (use --no-format to disable)");
-        if let Err(e) = Command::new("ktlint").arg("-F").arg(&file_path).output() {
-            println!(
-                "Warning: Unable to auto-format {} using ktlint: {e:?}",
-                file_path.file_name().unwrap(),
-            );
+fn format_code(file_path: &Utf8Path) {
+    if let Err(e) = Command::new("ktlint").arg("-F").arg(file_path).output() {
+        println!(
+            "Warning: Unable to auto-format {} using ktlint: {e:?}",
+            file_path.file_name().unwrap(),
+        );
+    }
+}
+
+/// Where to read the component interfaces to generate the bindings for.
+#[derive(Debug, Clone)]
+pub enum BindingsSource {
+    /// A cdylib or a staticlib built from crates using UniFFI. Bindings are generated for every
+    /// crate in the library, unless [`GenerateOptions::crate_name`] is given.
+    Library(Utf8PathBuf),
+    /// A UDL file, optionally with a library to extract the proc-macro metadata from.
+    Udl {
+        udl_file: Utf8PathBuf,
+        lib_file: Option<Utf8PathBuf>,
+    },
+}
+
+/// Options used by [`generate`].
+#[derive(Default)]
+pub struct GenerateOptions<'a> {
+    /// Path to the config file to use instead of the crate's `uniffi.toml`.
+    pub config: Option<Utf8PathBuf>,
+    /// With [`BindingsSource::Library`], only generate the bindings for this crate. With
+    /// [`BindingsSource::Udl`], use this as the crate name instead of reading `Cargo.toml`.
+    pub crate_name: Option<String>,
+    /// Supplies the `uniffi.toml` and the UDL files of each crate with
+    /// [`BindingsSource::Library`]. No configuration is used when not given.
+    pub crate_config_supplier: Option<&'a dyn BindgenCrateConfigSupplier>,
+    /// Defaults shared by every crate, overridden by the configuration of each crate.
+    pub workspace_config: Option<WorkspaceConfig>,
+}
+
+/// Generates the Kotlin Multiplatform bindings without writing them to the file system.
+///
+/// Returns the contents of every generated file, including the cinterop header, keyed by the path
+/// relative to the output directory the `gobley-uniffi-bindgen` CLI would write to (e.g.
+/// `commonMain/kotlin/uniffi/foo/foo.common.kt`). This can be used in a Cargo build script or an
+/// xtask when Gradle is not used.
+///
+/// ```no_run
+/// use gobley_uniffi_bindgen::{generate, BindingsSource, GenerateOptions};
+///
+/// let files = generate(
+///     &BindingsSource::Library("target/debug/libfoo.so".into()),
+///     &GenerateOptions::default(),
+/// )?;
+/// for (path, content) in files {
+///     println!("{path}: {} bytes", content.len());
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn generate(
+    source: &BindingsSource,
+    options: &GenerateOptions,
+) -> Result<BTreeMap<Utf8PathBuf, String>> {
+    let generator = InMemoryBindingGenerator {
+        generator: ConfiguredKotlinBindingGenerator::default()
+            .with_workspace_config(options.workspace_config.clone())
+            .with_config_paths(HashMap::new(), options.config.clone()),
+        files: RefCell::default(),
+    };
+    // UniFFI requires an output directory even though nothing is written to it. The temporary
+    // directory always exists, so UniFFI leaves it untouched.
+    let out_dir = Utf8PathBuf::try_from(std::env::temp_dir())
+        .context("the temporary directory path is not valid UTF-8")?;
+
+    match source {
+        BindingsSource::Library(library_path) => {
+            uniffi_bindgen::library_mode::generate_bindings(
+                library_path,
+                options.crate_name.clone(),
+                &generator,
+                options
+                    .crate_config_supplier
+                    .unwrap_or(&EmptyCrateConfigSupplier),
+                options.config.as_deref(),
+                &out_dir,
+                false,
+            )?;
+        }
+        BindingsSource::Udl { udl_file, lib_file } => {
+            uniffi_bindgen::generate_external_bindings(
+                &generator,
+                udl_file,
+                options.config.as_ref(),
+                Some(&out_dir),
+                lib_file.as_ref(),
+                options.crate_name.as_deref(),
+                false,
+            )?;
         }
     }
+
+    Ok(generator.files.into_inner())
 }
 
-fn write_cinterop(ci: &ComponentInterface, out_dir: &Utf8Path, content: String) {
-    let dst_dir = Utf8PathBuf::from(out_dir)
-        .join("nativeInterop")
-        .join("cinterop")
-        .join("headers")
-        .join(ci.namespace());
-    fs::create_dir_all(&dst_dir).unwrap();
-    let file_path = dst_dir.join(format!("{}.h", ci.namespace()));
-    let mut f = File::create(file_path).unwrap();
-    write!(f, "{}", content).unwrap();
+/// The environment variable holding the directory of the JNI shims generated for the
+/// `android-jni` target, read by [`include_jni_shim`]. The Gradle plugin sets it to
+/// `build/generated/uniffi/jni` when building the Rust library for Android.
+pub const JNI_SHIM_DIR_ENV: &str = "GOBLEY_UNIFFI_JNI_SHIM_DIR";
+
+/// Copies the JNI shim of the `android-jni` bindings of `namespace` to
+/// `$OUT_DIR/uniffi_jni_<namespace>.rs`, to be included by the crate the Android library is built
+/// from. Call it from the build script of the crate.
+///
+/// The shim is generated with the bindings, so it is read from the directory in
+/// [`JNI_SHIM_DIR_ENV`] when the variable is set. Otherwise, e.g. when building the library the
+/// bindings are generated from, the file is left empty.
+///
+/// ```no_run
+/// // build.rs
+/// gobley_uniffi_bindgen::include_jni_shim("my_crate")?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// ```ignore
+/// // lib.rs
+/// #[cfg(target_os = "android")]
+/// include!(concat!(env!("OUT_DIR"), "/uniffi_jni_my_crate.rs"));
+/// ```
+pub fn include_jni_shim(namespace: &str) -> Result<()> {
+    println!("cargo:rerun-if-env-changed={JNI_SHIM_DIR_ENV}");
+    let out_dir =
+        std::env::var("OUT_DIR").context("OUT_DIR is not set, call this in a build script")?;
+    let content = match std::env::var(JNI_SHIM_DIR_ENV) {
+        Ok(shim_dir) => {
+            let shim_file = Utf8PathBuf::from(shim_dir).join(format!("{namespace}.rs"));
+            println!("cargo:rerun-if-changed={shim_file}");
+            fs::read_to_string(&shim_file)?
+        }
+        Err(_) => String::new(),
+    };
+    fs::write(
+        Utf8PathBuf::from(out_dir).join(format!("uniffi_jni_{namespace}.rs")),
+        content,
+    )?;
+    Ok(())
+}
+
+/// A [`BindingGenerator`] collecting the generated files instead of writing them.
+struct InMemoryBindingGenerator {
+    generator: ConfiguredKotlinBindingGenerator,
+    files: RefCell<BTreeMap<Utf8PathBuf, String>>,
+}
+
+impl BindingGenerator for InMemoryBindingGenerator {
+    type Config = Config;
+
+    fn new_config(&self, root_toml: &toml::value::Value) -> Result<Self::Config> {
+        self.generator.new_config(root_toml)
+    }
+
+    fn update_component_configs(
+        &self,
+        settings: &GenerationSettings,
+        components: &mut Vec<Component<Self::Config>>,
+    ) -> Result<()> {
+        self.generator
+            .update_component_configs(settings, components)
+    }
+
+    fn write_bindings(
+        &self,
+        _settings: &GenerationSettings,
+        components: &[Component<Self::Config>],
+    ) -> Result<()> {
+        let mut files = self.files.borrow_mut();
+        for Component { ci, config, .. } in components {
+            files.extend(render_bindings(ci, config)?);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_returns_files_by_path() {
+        let crate_root = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!(
+                "gobley-uniffi-bindgen-generate-{}",
+                std::process::id()
+            ));
+        let _ = fs::remove_dir_all(&crate_root);
+        fs::create_dir_all(crate_root.join("src")).unwrap();
+        fs::write(
+            crate_root.join("Cargo.toml"),
+            "[package]\nname = \"foo\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        let udl_file = crate_root.join("src").join("foo.udl");
+        fs::write(
+            &udl_file,
+            "namespace foo {\n    string hello(string name);\n};\n",
+        )
+        .unwrap();
+        let config_file = crate_root.join("uniffi.toml");
+        fs::write(
+            &config_file,
+            "[bindings.kotlin]\npackage_name = \"com.example.foo\"\nkotlin_multiplatform = true\nkotlin_targets = [\"jvm\", \"native\"]\n",
+        )
+        .unwrap();
+
+        let files = generate(
+            &BindingsSource::Udl {
+                udl_file,
+                lib_file: None,
+            },
+            &GenerateOptions {
+                config: Some(config_file),
+                crate_name: Some(String::from("foo")),
+                ..GenerateOptions::default()
+            },
+        )
+        .unwrap();
+
+        let common = &files[Utf8Path::new("commonMain/kotlin/com/example/foo/foo.common.kt")];
+        assert!(common.contains("package com.example.foo"));
+        assert!(common.contains("fun `hello`("));
+        assert!(files.contains_key(Utf8Path::new("jvmMain/kotlin/com/example/foo/foo.jvm.kt")));
+        assert!(files.contains_key(Utf8Path::new(
+            "nativeMain/kotlin/com/example/foo/foo.native.kt"
+        )));
+        assert!(files.contains_key(Utf8Path::new("nativeInterop/cinterop/headers/foo/foo.h")));
+        assert!(!files.keys().any(|path| path.starts_with("androidMain")));
+    }
+
+    #[test]
+    fn print_config_includes_workspace_defaults() {
+        let workspace_config = WorkspaceConfig {
+            path: Utf8PathBuf::from(WORKSPACE_CONFIG_FILE_NAME),
+            table: toml::from_str(
+                "kotlin_multiplatform = false\ngenerate_immutable_records = true\n",
+            )
+            .unwrap(),
+        };
+        let generator = ConfiguredKotlinBindingGenerator::default()
+            .with_workspace_config(Some(workspace_config));
+        let config = generator
+            .new_config(
+                &toml::from_str("package_name = \"component\"\nkotlin_multiplatform = true\n")
+                    .unwrap(),
+            )
+            .unwrap();
+
+        let rendered: toml::Value =
+            toml::from_str(&render_config("component", &config).unwrap()).unwrap();
+        assert_eq!(
+            rendered.get("package_name").and_then(toml::Value::as_str),
+            Some("component")
+        );
+        assert_eq!(
+            rendered
+                .get("kotlin_multiplatform")
+                .and_then(toml::Value::as_bool),
+            Some(true)
+        );
+        assert_eq!(
+            rendered
+                .get("generate_immutable_records")
+                .and_then(toml::Value::as_bool),
+            Some(true)
+        );
+        assert!(render_config("component", &config)
+            .unwrap()
+            .starts_with("# component\n"));
+    }
 }
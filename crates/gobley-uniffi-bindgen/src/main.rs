@@ -4,6 +4,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fs;
@@ -11,7 +12,7 @@ use std::fs;
 use anyhow::Context as _;
 use camino::{Utf8Path, Utf8PathBuf};
 use clap::Parser;
-use gobley_uniffi_bindgen::KotlinBindingGenerator;
+use gobley_uniffi_bindgen::{ConfiguredKotlinBindingGenerator, WorkspaceConfig};
 use uniffi_bindgen::BindgenCrateConfigSupplier;
 
 #[derive(Parser)]
@@ -36,6 +37,16 @@ struct Cli {
     #[clap(long, value_parser = parse_key_val::<String, Utf8PathBuf>)]
     crate_paths: Vec<(String, Utf8PathBuf)>,
 
+    /// When `--library` is passed, run `cargo metadata --offline` to locate the packages of crates
+    /// not given with `--crate-configs` or `--crate-paths`.
+    #[clap(long)]
+    cargo_metadata: bool,
+
+    /// Path to the Cargo.toml used with `--cargo-metadata`. Defaults to the one in the current
+    /// directory or its parents.
+    #[clap(long, requires = "cargo_metadata")]
+    manifest_path: Option<Utf8PathBuf>,
+
     /// Extract proc-macro metadata from a native lib (cdylib or staticlib) for this crate.
     #[clap(long, short)]
     lib_file: Option<Utf8PathBuf>,
@@ -53,6 +64,17 @@ struct Cli {
     #[clap(long = "format", default_value_t = false)]
     try_format_code: bool,
 
+    /// Path to the `gobley.toml` or the workspace `Cargo.toml` containing the defaults of every
+    /// crate. If not provided, it is searched from the directory of `--manifest-path`, or the
+    /// current directory, up to the workspace root or the package root outside of a workspace.
+    #[clap(long)]
+    workspace_config: Option<Utf8PathBuf>,
+
+    /// Print the configuration of each crate merged with the workspace defaults instead of
+    /// generating the bindings.
+    #[clap(long)]
+    print_config: bool,
+
     /// Path to the UDL file, or cdylib if `library-mode` is specified.
     source: Utf8PathBuf,
 }
@@ -76,13 +98,31 @@ pub struct CliCrateConfigSupplier {
     crate_pths: HashMap<String, Utf8PathBuf>,
 }
 
+impl CliCrateConfigSupplier {
+    /// The configuration file of each crate that has one, by the crate name.
+    fn config_paths(&self) -> HashMap<String, Utf8PathBuf> {
+        let mut config_paths: HashMap<String, Utf8PathBuf> = self
+            .crate_pths
+            .iter()
+            .map(|(crate_name, crate_path)| (crate_name.clone(), crate_path.join("uniffi.toml")))
+            .filter(|(_, path)| path.exists())
+            .collect();
+        config_paths.extend(self.crate_configs.clone());
+        config_paths
+    }
+}
+
 impl BindgenCrateConfigSupplier for CliCrateConfigSupplier {
     fn get_toml(&self, crate_name: &str) -> anyhow::Result<Option<toml::value::Table>> {
         if let Some(path) = self.crate_configs.get(crate_name) {
             return load_toml_file(path);
         }
         if let Some(crate_path) = self.crate_pths.get(crate_name) {
-            return load_toml_file(&crate_path.join("uniffi.toml"));
+            // Crates found by cargo metadata don't need to have a configuration file.
+            let path = crate_path.join("uniffi.toml");
+            if path.exists() {
+                return load_toml_file(&path);
+            }
         }
         Ok(None)
     }
@@ -102,6 +142,63 @@ impl BindgenCrateConfigSupplier for CliCrateConfigSupplier {
     }
 }
 
+/// Find the package paths of all library crates in the Cargo workspace and its dependencies.
+fn crate_paths_from_cargo_metadata(
+    manifest_path: Option<&Utf8Path>,
+) -> anyhow::Result<HashMap<String, Utf8PathBuf>> {
+    let mut command = cargo_metadata::MetadataCommand::new();
+    if let Some(manifest_path) = manifest_path {
+        command.manifest_path(manifest_path);
+    }
+    let metadata = command
+        .other_options(vec![String::from("--offline")])
+        .exec()
+        .context("failed to run cargo metadata")?;
+
+    // A crate can be in the dependency graph in several versions. Workspace members come first, so
+    // they take precedence over the dependencies with the same crate name.
+    let mut packages = metadata.packages.iter().collect::<Vec<_>>();
+    packages.sort_by_key(|package| !metadata.workspace_members.contains(&package.id));
+
+    let mut crate_paths = HashMap::<String, Utf8PathBuf>::new();
+    for package in packages {
+        let Some(package_path) = package.manifest_path.parent() else {
+            continue;
+        };
+        for target in &package.targets {
+            if target.is_bin()
+                || target.is_example()
+                || target.is_test()
+                || target.is_bench()
+                || target.is_custom_build()
+            {
+                continue;
+            }
+            // Crate names in the component interfaces use underscores.
+            let crate_name = target.name.replace('-', "_");
+            match crate_paths.entry(crate_name) {
+                Entry::Vacant(entry) => {
+                    entry.insert(package_path.to_owned());
+                }
+                Entry::Occupied(entry) if entry.get() != package_path => {
+                    eprintln!(
+                        "Warning: crate `{}` is provided by several packages; using {}, not {} {} \
+                        at {package_path}. Pass --crate-paths {}=<package-directory> to choose \
+                        the package.",
+                        entry.key(),
+                        entry.get(),
+                        package.name,
+                        package.version,
+                        entry.key(),
+                    );
+                }
+                Entry::Occupied(_) => {}
+            }
+        }
+    }
+    Ok(crate_paths)
+}
+
 fn load_toml_file(path: &Utf8Path) -> anyhow::Result<Option<toml::value::Table>> {
     let contents = fs::read_to_string(path).with_context(|| format!("read file: {:?}", path))?;
     Ok(Some(
@@ -115,36 +212,67 @@ fn main() -> anyhow::Result<()> {
         config,
         crate_configs,
         crate_paths,
+        cargo_metadata,
+        manifest_path,
         lib_file,
         library_mode,
         crate_name,
         source,
         try_format_code,
+        workspace_config,
+        print_config,
     } = Cli::parse();
 
-    let binding_generator = KotlinBindingGenerator;
+    let workspace_config = match workspace_config {
+        Some(path) => Some(WorkspaceConfig::load(&path)?),
+        None => {
+            let current_dir = Utf8PathBuf::try_from(std::env::current_dir()?)?;
+            let search_dir = match manifest_path.as_deref().and_then(Utf8Path::parent) {
+                Some(manifest_dir) => current_dir.join(manifest_dir),
+                None => current_dir,
+            };
+            WorkspaceConfig::find(&search_dir)?
+        }
+    };
+    let binding_generator = ConfiguredKotlinBindingGenerator::default()
+        .with_workspace_config(workspace_config)
+        .with_print_config(print_config);
 
     if library_mode {
         if lib_file.is_some() {
             panic!("--lib-file is not compatible with --library.")
         }
-        let out_dir = out_dir.expect("--out-dir is required when using --library");
+        let out_dir = match out_dir {
+            Some(out_dir) => out_dir,
+            // Nothing is written when only printing the configuration.
+            None if print_config => Utf8PathBuf::from("."),
+            None => panic!("--out-dir is required when using --library"),
+        };
+
+        let mut crate_pths = if cargo_metadata {
+            crate_paths_from_cargo_metadata(manifest_path.as_deref())?
+        } else {
+            HashMap::new()
+        };
+        // Paths given explicitly take priority over the ones found by cargo metadata.
+        crate_pths.extend(crate_paths);
+        let config_supplier = CliCrateConfigSupplier {
+            crate_configs: crate_configs.into_iter().collect(),
+            crate_pths,
+        };
 
         uniffi_bindgen::library_mode::generate_bindings(
             &source,
             crate_name,
-            &binding_generator,
-            &CliCrateConfigSupplier {
-                crate_configs: crate_configs.into_iter().collect(),
-                crate_pths: crate_paths.into_iter().collect(),
-            },
+            &binding_generator.with_config_paths(config_supplier.config_paths(), config.clone()),
+            &config_supplier,
             config.as_deref(),
             &out_dir,
             try_format_code,
         )?;
     } else {
         uniffi_bindgen::generate_external_bindings(
-            &binding_generator,
+            &binding_generator.with_config_paths(HashMap::new(), config.clone()),
             source,
             config,
             out_dir,
@@ -156,3 +284,66 @@ fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a Cargo workspace with the library crates `component`, which has a `uniffi.toml`,
+    /// and `dependency`, which doesn't.
+    fn create_workspace(name: &str) -> Utf8PathBuf {
+        let root = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!(
+                "gobley-uniffi-bindgen-{name}-{}",
+                std::process::id()
+            ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"component\", \"dependency\"]\nresolver = \"2\"\n",
+        )
+        .unwrap();
+        for crate_name in ["component", "dependency"] {
+            let crate_path = root.join(crate_name);
+            fs::create_dir_all(crate_path.join("src")).unwrap();
+            fs::write(
+                crate_path.join("Cargo.toml"),
+                format!(
+                    "[package]\nname = \"{crate_name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n"
+                ),
+            )
+            .unwrap();
+            fs::write(crate_path.join("src").join("lib.rs"), "").unwrap();
+        }
+        fs::write(
+            root.join("component").join("uniffi.toml"),
+            "[bindings.kotlin]\npackage_name = \"component\"\n",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn crates_without_uniffi_toml_have_no_config() {
+        let root = create_workspace("no-uniffi-toml");
+        let crate_pths = crate_paths_from_cargo_metadata(Some(&root.join("Cargo.toml"))).unwrap();
+        assert_eq!(crate_pths["dependency"], root.join("dependency"));
+        let config_supplier = CliCrateConfigSupplier {
+            crate_configs: HashMap::new(),
+            crate_pths,
+        };
+
+        assert!(config_supplier.get_toml("component").unwrap().is_some());
+        assert!(config_supplier.get_toml("dependency").unwrap().is_none());
+        assert!(config_supplier.get_toml("unknown").unwrap().is_none());
+        let config_paths = config_supplier.config_paths();
+        assert_eq!(
+            config_paths.keys().collect::<Vec<_>>(),
+            [&String::from("component")]
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -17,4 +17,15 @@ internal object uniffiForeignFutureFreeImpl: UniffiForeignFutureFree {
     }
 }
 
+{%- endif %}
+
+{%- if config.generate_completable_futures() %}
+
+// Runs the `suspend` function called by `block` as a `CompletableFuture`. Cancelling the future
+// cancels the coroutine, which cancels the Rust future through `uniffiRustCallAsync`.
+@OptIn(DelicateCoroutinesApi::class)
+internal fun<T> uniffiCompletableFuture(block: suspend () -> T): java.util.concurrent.CompletableFuture<T> {
+    return GlobalScope.future { block() }
+}
+
 {%- endif %}
\ No newline at end of file
@@ -1,3 +1,17 @@
+// Encoders keep state while encoding, so each thread reuses its own.
+private val uniffiUtf8Encoder = object : ThreadLocal<java.nio.charset.CharsetEncoder>() {
+    override fun initialValue(): java.nio.charset.CharsetEncoder = Charsets.UTF_8.newEncoder()
+        .onMalformedInput(java.nio.charset.CodingErrorAction.REPORT)
+        .onUnmappableCharacter(java.nio.charset.CodingErrorAction.REPORT)
+}
+
+// Strings of up to this many bytes are lifted through an array reused by each thread.
+private const val UNIFFI_STRING_SCRATCH_SIZE = 16 * 1024
+
+private val uniffiStringScratch = object : ThreadLocal<ByteArray>() {
+    override fun initialValue(): ByteArray = ByteArray(UNIFFI_STRING_SCRATCH_SIZE)
+}
+
 
 @kotlin.jvm.JvmInline
 {{ visibility() }}value class ByteBuffer(private val inner: java.nio.ByteBuffer) {
@@ -13,10 +27,32 @@
 
     {{ visibility() }}fun hasRemaining(): Boolean = inner.hasRemaining()
 
+    {{ visibility() }}fun skipRemaining() {
+        inner.position(inner.limit())
+    }
+
     {{ visibility() }}fun get(): Byte = inner.get()
 
     {{ visibility() }}fun get(bytesToRead: Int): ByteArray = ByteArray(bytesToRead).apply(inner::get)
 
+    // Decodes the UTF-8 bytes straight into the String, without the UTF-16 `CharBuffer` of
+    // `Charset.decode`. The bytes of a direct buffer are first copied into an array reused by the
+    // thread, so short strings don't allocate an intermediate array either.
+    {{ visibility() }}fun getString(length: Int): String {
+        if (inner.hasArray()) {
+            val offset = inner.arrayOffset() + inner.position()
+            inner.position(inner.position() + length)
+            return String(inner.array(), offset, length, Charsets.UTF_8)
+        }
+        val bytes = if (length <= UNIFFI_STRING_SCRATCH_SIZE) {
+            uniffiStringScratch.get()
+        } else {
+            ByteArray(length)
+        }
+        inner.get(bytes, 0, length)
+        return String(bytes, 0, length, Charsets.UTF_8)
+    }
+
     {{ visibility() }}fun getShort(): Short = inner.getShort()
 
     {{ visibility() }}fun getInt(): Int = inner.getInt()
@@ -35,6 +71,20 @@
         inner.put(src)
     }
 
+    // Encodes `value` directly into the buffer, which must have room for `uniffiUtf8Length(value)`
+    // bytes.
+    {{ visibility() }}fun putString(value: String) {
+        val encoder = uniffiUtf8Encoder.get().reset()
+        val result = encoder.encode(java.nio.CharBuffer.wrap(value), inner, true)
+        if (!result.isUnderflow) {
+            result.throwException()
+        }
+        val flushResult = encoder.flush(inner)
+        if (!flushResult.isUnderflow) {
+            flushResult.throwException()
+        }
+    }
+
     {{ visibility() }}fun putShort(value: Short) {
         inner.putShort(value)
     }
@@ -43,6 +93,11 @@
         inner.putInt(value)
     }
 
+    // Writes `value` at `index` without moving the position.
+    {{ visibility() }}fun putInt(index: Int, value: Int) {
+        inner.putInt(index, value)
+    }
+
     {{ visibility() }}fun putLong(value: Long) {
         inner.putLong(value)
     }
@@ -12,7 +12,7 @@ internal object {{ trait_impl }} {
         {%- endif %} {
             val uniffiObj = {{ ffi_converter_name }}.handleMap.get(uniffiHandle)
             val makeCall = {% if meth.is_async() %}suspend {% endif %}{ ->
-                uniffiObj.{{ meth.name()|fn_name() }}(
+                {% call kt::instrument_call(meth.name()|fn_name, name, true) %}uniffiObj.{{ meth.name()|fn_name() }}(
                     {%- for arg in meth.arguments() %}
                     {%- if arg|as_ffi_type|ref|need_non_null_assertion %}
                     {{ arg|lift_fn }}({{ arg.name()|var_name }}!!),
@@ -20,7 +20,7 @@ internal object {{ trait_impl }} {
                     {{ arg|lift_fn }}({{ arg.name()|var_name }}),
                     {%- endif -%}
                     {%- endfor %}
-                )
+                ){% call kt::end_instrument_call() %}
             }
             {%- if !meth.is_async() %}
 
@@ -0,0 +1,58 @@
+// Finds the libraries bundled in JAR files on the Desktop JVM. Android loads them from the APK
+// instead, and doesn't have `java.nio.file.Files` before API level 26.
+
+/**
+ * Extracts the dynamic library `libraryName` bundled in a JAR file to a temporary directory, keeping
+ * the file name, and returns its absolute path. Returns the path of the library itself when it's
+ * not in a JAR file, or `null` when it's not on the class path. Can be used in
+ * [uniffiLibraryLoader].
+ */
+{{ visibility() }}fun uniffiExtractLibraryFromClassPath(libraryName: String): String? {
+    val extractionDestination = java.nio.file.Files.createTempDirectory("gobley-jna").toFile().apply {
+        deleteOnExit()
+    }
+    val classLoader = UniffiLib::class.java.classLoader!!
+    return findLibraryInClassPath(libraryName, classLoader, extractionDestination)?.absolutePath
+}
+
+internal fun findLibraryInClassPath(
+    library: String,
+    classLoader: ClassLoader,
+    extractionDestination: java.io.File,
+): java.io.File? {
+    var libraryName = System.mapLibraryName(library)
+    if (com.sun.jna.Platform.isMac()) {
+        if (libraryName.endsWith(".jnilib")) {
+            libraryName = libraryName.removeSuffix(".jnilib") + ".dylib"
+        }
+    }
+    val resourcePath = "${com.sun.jna.Platform.RESOURCE_PREFIX}/$libraryName"
+    var url = classLoader.getResource(resourcePath)
+    if (com.sun.jna.Platform.isMac() && url == null) {
+        url = classLoader.getResource("darwin/$libraryName")
+    }
+    if (url == null) {
+        url = classLoader.getResource(libraryName)
+    }
+    if (url == null) {
+        return null
+    }
+    if (url.protocol.equals("file", ignoreCase = true)) {
+        val file = try {
+            java.io.File(url.toURI())
+        } catch (_: java.net.URISyntaxException) {
+            java.io.File(url.path)
+        }
+        return file.takeIf { it.exists() }
+    }
+    val destination = extractionDestination.resolve(resourcePath).apply {
+        parentFile?.mkdirs()
+        deleteOnExit()
+    }
+    url.openStream().use { inputStream ->
+        destination.outputStream().use { outputStream ->
+            inputStream.copyTo(outputStream)
+        }
+    }
+    return destination
+}
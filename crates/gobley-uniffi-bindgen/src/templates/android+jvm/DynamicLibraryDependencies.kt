@@ -0,0 +1,67 @@
+{%- if module_name == "jvm" %}
+// Dynamic library dependency loading code.
+//
+// Load dynamic libraries that the main Rust library depends on. They may
+// reside inside a .jar file, or already be installed in the file system.
+// The reason for this custom handling is that JNA copies the extracted
+// library to a temporary file with a random name, resulting in a dynamic
+// link error on Windows. This logic ensures that the destination temporary
+// file has the same base name as the original library file in the JAR file.
+//
+// First, try loading the library without searching the directories
+// specified in CLASSPATH.
+@Suppress("SameParameterValue")
+private fun loadDynamicLibraryDependencies(vararg dependencies: String) {
+    val nilClasspathClassLoader = java.net.URLClassLoader(emptyArray(), Any::class.java.classLoader)
+    val dependenciesRequiringExtraction = mutableListOf<String>()
+    for (dependency in dependencies) {
+        try {
+            com.sun.jna.NativeLibrary.getInstance(dependency, nilClasspathClassLoader)
+        } catch (_: UnsatisfiedLinkError) {
+            dependenciesRequiringExtraction.add(dependency)
+        }
+    }
+    loadDynamicLibraryDependenciesByExtraction(dependenciesRequiringExtraction, nilClasspathClassLoader)
+}
+
+// Second, try extracting the library from a .jar file.
+private fun loadDynamicLibraryDependenciesByExtraction(
+    dependencies: List<String>,
+    nilClasspathClassLoader: ClassLoader,
+) {
+    if (dependencies.isEmpty()) return
+    // The directory where the dynamic library dependencies in zipped JAR files will be extracted
+    val extractionDestination = java.nio.file.Files.createTempDirectory("gobley-jna").toFile()
+    val classLoader = UniffiLib::class.java.classLoader!!
+    val dependenciesRequiringJnaHandling = mutableListOf<String>()
+    for (dependency in dependencies) {
+        val libraryFile = findLibraryInClassPath(dependency, classLoader, extractionDestination)
+        if (libraryFile == null) {
+            dependenciesRequiringJnaHandling.add(dependency)
+            continue
+        }
+        try {
+            com.sun.jna.NativeLibrary.addSearchPath(
+                dependency,
+                libraryFile.parentFile.absolutePath,
+            )
+            com.sun.jna.NativeLibrary.getInstance(
+                dependency,
+                nilClasspathClassLoader,
+            )
+        } catch (_: UnsatisfiedLinkError) {
+            dependenciesRequiringJnaHandling.add(dependency)
+        }
+    }
+    // Lastly, if all the logic above fails, try loading the library using JNA.
+    for (dependency in dependenciesRequiringJnaHandling) {
+        com.sun.jna.NativeLibrary.getInstance(dependency)
+    }
+}
+{%- else %}
+private fun loadDynamicLibraryDependencies(vararg dependencies: String) {
+    for (dependency in dependencies) {
+        com.sun.jna.NativeLibrary.getInstance(dependency)
+    }
+}
+{%- endif %}
\ No newline at end of file
@@ -1,4 +1,6 @@
-
+// Maps Kotlin objects passed to Rust, like callback interface implementations and futures, to
+// handles. Handles come from a counter and are never reused, so a handle that was issued but is no
+// longer in the map is stale, and lookups don't take a lock.
 internal class UniffiHandleMap<T: Any> {
     private val map = java.util.concurrent.ConcurrentHashMap<Long, T>()
     private val counter: kotlinx.atomicfu.AtomicLong = kotlinx.atomicfu.atomic(1L)
@@ -15,11 +17,21 @@ internal class UniffiHandleMap<T: Any> {
 
     // Get an object from the handle map
     internal fun get(handle: Long): T {
-        return map[handle] ?: throw InternalException("UniffiHandleMap.get: Invalid handle")
+        return map[handle] ?: throw missingHandle(handle, "get")
     }
 
     // Remove an entry from the handlemap and get the Kotlin object back
     internal fun remove(handle: Long): T {
-        return map.remove(handle) ?: throw InternalException("UniffiHandleMap.remove: Invalid handle")
+        return map.remove(handle) ?: throw missingHandle(handle, "remove")
+    }
+
+    private fun missingHandle(handle: Long, operation: String): InternalException {
+        if (handle > 0 && handle < counter.value) {
+            return UniffiStaleHandleException(
+                "UniffiHandleMap.$operation: Stale handle $handle, its object was already removed",
+                handle,
+            )
+        }
+        return InternalException("UniffiHandleMap.$operation: Invalid handle $handle")
     }
 }
@@ -57,14 +57,34 @@ internal typealias {{ ffi_struct.name()|ffi_struct_name }}UniffiByValue = {{ ffi
 {%- endmatch %}
 {%- endfor %}
 
+/**
+ * Chooses the dynamic library loaded by the bindings, e.g. to load it from an application data
+ * directory. Receives the namespace of the component and the default library name, and returns the
+ * name or the absolute path of the library, or `null` to use the default. Must be set before the
+ * bindings are used for the first time.
+ */
+{{ visibility() }}var uniffiLibraryLoader: ((componentName: String, libraryName: String) -> String?)? = null
+
+private var uniffiLibraryName: String? = null
+
+// Checked in order: `uniffiLibraryLoader`, the `uniffi.component.<namespace>.libraryOverride`
+// system property, the `UNIFFI_COMPONENT_<NAMESPACE>_LIBRARY_OVERRIDE` environment variable, and
+// the `cdylib_name`, which JNA looks up in its search paths.
 @Synchronized
 private fun findLibraryName(componentName: String): String {
-    val libOverride = System.getProperty("uniffi.component.$componentName.libraryOverride")
-    if (libOverride != null) {
-        return libOverride
-    }
-    return "{{ config.cdylib_name() }}"
+    uniffiLibraryName?.let { return it }
+    val defaultLibraryName = "{{ config.cdylib_name() }}"
+    val name = uniffiLibraryLoader?.invoke(componentName, defaultLibraryName)
+        ?: System.getProperty("uniffi.component.$componentName.libraryOverride")
+        ?: System.getenv("UNIFFI_COMPONENT_${componentName.uppercase()}_LIBRARY_OVERRIDE")
+        ?: defaultLibraryName
+    uniffiLibraryName = name
+    return name
 }
+{%- if module_name == "jvm" %}
+
+{% include "ClassPathLibrary.kt" %}
+{%- endif %}
 
 // For large crates we prevent `MethodTooLargeException` (see #2340)
 // N.B. the name of the extension is very misleading, since it is 
@@ -82,30 +102,44 @@ private fun findLibraryName(componentName: String): String {
 internal object IntegrityCheckingUniffiLib : Library {
     init {
         Native.register(IntegrityCheckingUniffiLib::class.java, findLibraryName("{{ ci.namespace() }}"))
-        uniffiCheckContractApiVersion()
-        {%- if !config.omit_checksums %}
-        uniffiCheckApiChecksums()
-        {%- endif %}
+        uniffiCheckContractApiVersionAndChecksums()
     }
 
-    private fun uniffiCheckContractApiVersion() {
+    private fun uniffiCheckContractApiVersionAndChecksums() {
         // Get the bindings contract version from our ComponentInterface
         val bindingsContractVersion = {{ ci.uniffi_contract_version() }}
         // Get the scaffolding contract version by calling the into the dylib
         val scaffoldingContractVersion = {{ ci.ffi_uniffi_contract_version().name() }}()
-        if (bindingsContractVersion != scaffoldingContractVersion) {
-            throw RuntimeException("UniFFI contract version mismatch: try cleaning and rebuilding your project")
+        val checksumMismatches = mutableListOf<UniffiChecksumMismatch>()
+        {%- if !config.omit_checksums %}
+        if (bindingsContractVersion == scaffoldingContractVersion) {
+            uniffiCheckApiChecksums(checksumMismatches)
         }
+        {%- endif %}
+        uniffiCheckLibraryIntegrity(
+            uniffiLoadedLibraryPath(),
+            bindingsContractVersion,
+            scaffoldingContractVersion,
+            checksumMismatches,
+        )
+    }
+
+    private fun uniffiLoadedLibraryPath(): String? = try {
+        com.sun.jna.NativeLibrary.getInstance(findLibraryName("{{ ci.namespace() }}")).file?.absolutePath
+    } catch (_: UnsatisfiedLinkError) {
+        null
     }
 
     {%- if !config.omit_checksums %}
     {%- if ci.iter_checksums().next().is_none() %}
     @Suppress("UNUSED_PARAMETER")
     {%- endif %}
-    private fun uniffiCheckApiChecksums() {
+    private fun uniffiCheckApiChecksums(checksumMismatches: MutableList<UniffiChecksumMismatch>) {
         {%- for (name, expected_checksum) in ci.iter_checksums() %}
-        if ({{ name }}() != {{ expected_checksum }}.toShort()) {
-            throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+        {{ name }}().let { checksum ->
+            if (checksum != {{ expected_checksum }}.toShort()) {
+                checksumMismatches.add(UniffiChecksumMismatch("{{ name }}", {{ expected_checksum }}.toShort(), checksum))
+            }
         }
         {%- endfor %}
     }
@@ -120,122 +154,19 @@ internal object IntegrityCheckingUniffiLib : Library {
     {%- endfor %}
 }
 
+{%- let dynamic_library_dependencies = config.dynamic_library_dependencies(module_name) %}
+{%- if !dynamic_library_dependencies.is_empty() %}
+{% include "DynamicLibraryDependencies.kt" %}
+{%- else if module_name == "jvmCommon" && config.android_jvm_has_dynamic_library_dependencies() %}
+
+// Loads the dynamic libraries the main Rust library depends on. Implemented in the
+// platform-specific source sets, since the loading logic differs between Android and the JVM.
+internal expect fun uniffiLoadDynamicLibraryDependencies(libraryName: String)
+{%- endif %}
+
 // A JNA Library to expose the extern-C FFI definitions.
 // This is an implementation detail which will be called internally by the public API.
 internal object UniffiLib : Library {
-    {%- let dynamic_library_dependencies = config.dynamic_library_dependencies(module_name) %}
-    {%- if !dynamic_library_dependencies.is_empty() %}
-    {%- if module_name == "jvm" %}
-    // Dynamic library dependency loading code.
-    //
-    // Load dynamic libraries that the main Rust library depends on. They may
-    // reside inside a .jar file, or already be installed in the file system.
-    // The reason for this custom handling is that JNA copies the extracted
-    // library to a temporary file with a random name, resulting in a dynamic
-    // link error on Windows. This logic ensures that the destination temporary
-    // file has the same base name as the original library file in the JAR file.
-    //
-    // First, try loading the library without searching the directories
-    // specified in CLASSPATH.
-    @Suppress("SameParameterValue")
-    private fun loadDynamicLibraryDependencies(vararg dependencies: String) {
-        val nilClasspathClassLoader = java.net.URLClassLoader(emptyArray(), Any::class.java.classLoader)
-        val dependenciesRequiringExtraction = mutableListOf<String>()
-        for (dependency in dependencies) {
-            try {
-                com.sun.jna.NativeLibrary.getInstance(dependency, nilClasspathClassLoader)
-            } catch (_: UnsatisfiedLinkError) {
-                dependenciesRequiringExtraction.add(dependency)
-            }
-        }
-        loadDynamicLibraryDependenciesByExtraction(dependenciesRequiringExtraction, nilClasspathClassLoader)
-    }
-
-    // Second, try extracting the library from a .jar file.
-    private fun loadDynamicLibraryDependenciesByExtraction(
-        dependencies: List<String>,
-        nilClasspathClassLoader: ClassLoader,
-    ) {
-        if (dependencies.isEmpty()) return
-        // The directory where the dynamic library dependencies in zipped JAR files will be extracted
-        val extractionDestination = java.nio.file.Files.createTempDirectory("gobley-jna").toFile()
-        val classLoader = UniffiLib::class.java.classLoader!!
-        val dependenciesRequiringJnaHandling = mutableListOf<String>()
-        for (dependency in dependencies) {
-            val libraryFile = findLibraryInClassPath(dependency, classLoader, extractionDestination)
-            if (libraryFile == null) {
-                dependenciesRequiringJnaHandling.add(dependency)
-                continue
-            }
-            try {
-                com.sun.jna.NativeLibrary.addSearchPath(
-                    dependency,
-                    libraryFile.parentFile.absolutePath,
-                )
-                com.sun.jna.NativeLibrary.getInstance(
-                    dependency,
-                    nilClasspathClassLoader,
-                )
-            } catch (_: UnsatisfiedLinkError) {
-                dependenciesRequiringJnaHandling.add(dependency)
-            }
-        }
-        // Lastly, if all the logic above fails, try loading the library using JNA.
-        for (dependency in dependenciesRequiringJnaHandling) {
-            com.sun.jna.NativeLibrary.getInstance(dependency)
-        }
-    }
-
-    private fun findLibraryInClassPath(
-        library: String,
-        classLoader: ClassLoader,
-        extractionDestination: java.io.File,
-    ): java.io.File? {
-        var libraryName = System.mapLibraryName(library)
-        if (com.sun.jna.Platform.isMac()) {
-            if (libraryName.endsWith(".jnilib")) {
-                libraryName = libraryName.removeSuffix(".jnilib") + ".dylib"
-            }
-        }
-        val resourcePath = "${com.sun.jna.Platform.RESOURCE_PREFIX}/$libraryName"
-        var url = classLoader.getResource(resourcePath)
-        if (com.sun.jna.Platform.isMac() && url == null) {
-            url = classLoader.getResource("darwin/$libraryName")
-        }
-        if (url == null) {
-            url = classLoader.getResource(libraryName)
-        }
-        if (url == null) {
-            return null
-        }
-        if (url.protocol.equals("file", ignoreCase = true)) {
-            val file = try {
-                java.io.File(url.toURI())
-            } catch (_: java.net.URISyntaxException) {
-                java.io.File(url.path)
-            }
-            return file.takeIf { it.exists() }
-        }
-        val destination = extractionDestination.resolve(resourcePath).apply {
-            parentFile?.mkdirs()
-            deleteOnExit()
-        }
-        url.openStream().use { inputStream ->
-            destination.outputStream().use { outputStream ->
-                inputStream.copyTo(outputStream)
-            }
-        }
-        return destination
-    }
-    {%- else %}
-    private fun loadDynamicLibraryDependencies(vararg dependencies: String) {
-        for (dependency in dependencies) {
-            com.sun.jna.NativeLibrary.getInstance(dependency)
-        }
-    }
-    {%- endif %}
-    {%- endif %}
-
     init {
         {%- if !dynamic_library_dependencies.is_empty() %}
         loadDynamicLibraryDependencies(
@@ -245,6 +176,8 @@ internal object UniffiLib : Library {
             // Load the main library as well
             findLibraryName("{{ ci.namespace() }}"),
         )
+        {%- else if module_name == "jvmCommon" && config.android_jvm_has_dynamic_library_dependencies() %}
+        uniffiLoadDynamicLibraryDependencies(findLibraryName("{{ ci.namespace() }}"))
         {%- endif %}
         IntegrityCheckingUniffiLib
         Native.register(UniffiLib::class.java, findLibraryName("{{ ci.namespace() }}"))
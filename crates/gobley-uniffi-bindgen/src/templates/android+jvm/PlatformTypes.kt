@@ -0,0 +1,3 @@
+{%- if ci.has_object_definitions() %}
+{%- include "PlatformObjectCleanerHelper.kt" %}
+{%- endif %}
@@ -36,6 +36,23 @@
 {{ visibility() }}typealias RustBuffer = RustBufferStruct
 {{ visibility() }}typealias RustBufferByValue = RustBufferStruct.ByValue
 
+{%- if config.pool_rust_buffers %}
+
+private val uniffiRustBufferPools = object : ThreadLocal<UniffiRustBufferPool>() {
+    override fun initialValue(): UniffiRustBufferPool {
+        val pool = UniffiRustBufferPool()
+        // Free the pooled buffers once the thread, and therefore the pool, is gone.
+        val buffers = pool.buffers
+        com.sun.jna.internal.Cleaner.getCleaner().register(pool) {
+            buffers.forEach(RustBufferHelper::destroy)
+        }
+        return pool
+    }
+}
+
+internal fun uniffiRustBufferPool(): UniffiRustBufferPool = uniffiRustBufferPools.get()
+{%- endif %}
+
 internal fun RustBuffer.asByteBuffer(): ByteBuffer? {
     {% call kt::check_rust_buffer_length("this.len") %}
     return ByteBuffer(data?.getByteBuffer(0L, this.len) ?: return null)
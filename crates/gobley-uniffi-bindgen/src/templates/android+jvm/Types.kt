@@ -1,5 +1,6 @@
 
 {%- import "macros.kt" as kt %}
+{%- let completable_futures = config.generate_completable_futures() %}
 
 {%- if ci.has_callback_definitions() %}
 {%- include "ffi/CallbackInterfaceRuntime.kt" %}
@@ -131,3 +132,7 @@
 {{ self.add_import("kotlinx.coroutines.IO") }}
 {{ self.add_import("kotlinx.coroutines.Dispatchers") }}
 {%- endif %}
+
+{%- if ci.has_async_fns() && config.generate_completable_futures() %}
+{{ self.add_import("kotlinx.coroutines.future.future") }}
+{%- endif %}
@@ -0,0 +1,42 @@
+@file:Suppress("RemoveRedundantBackticks")
+
+package {{ config.package_name() }}
+
+// Platform-specific helper code.
+//
+// The rest of the bindings are shared between the JVM and Android targets, and
+// are generated in the `{{ config.source_set_name("jvmCommon") }}` source set.
+// Only the code that differs between the two platforms lives here.
+
+{%- for req in self.imports() %}
+{{ req.render() }}
+{%- endfor %}
+
+{{ type_helper_code }}
+{%- if module_name == "jvm" %}
+
+{% include "ClassPathLibrary.kt" %}
+{%- endif %}
+
+{%- if config.android_jvm_has_dynamic_library_dependencies() %}
+{%- let dynamic_library_dependencies = config.dynamic_library_dependencies(module_name) %}
+{%- if !dynamic_library_dependencies.is_empty() %}
+{% include "DynamicLibraryDependencies.kt" %}
+{% endif %}
+{%- if dynamic_library_dependencies.is_empty() %}
+@Suppress("UNUSED_PARAMETER")
+{%- endif %}
+internal actual fun uniffiLoadDynamicLibraryDependencies(libraryName: String) {
+    {%- if !dynamic_library_dependencies.is_empty() %}
+    loadDynamicLibraryDependencies(
+        {%- for dynamic_library in dynamic_library_dependencies %}
+        "{{ dynamic_library }}",
+        {%- endfor %}
+        // Load the main library as well
+        libraryName,
+    )
+    {%- else %}
+    // There are no dynamic library dependencies on this platform.
+    {%- endif %}
+}
+{%- endif %}
@@ -1,6 +1,7 @@
 {%- call kt::docstring_value(ci.namespace_docstring(), 0) %}
 
 @file:Suppress("RemoveRedundantBackticks")
+{%- call kt::file_opt_in(module_name) %}
 
 package {{ config.package_name() }}
 
@@ -46,6 +47,14 @@ import com.sun.jna.Structure
 {%- include "ffi/TopLevelFunctionTemplate.kt" %}
 {%- endfor %}
 
+{%- if config.generate_completable_futures() %}
+{%- for func in ci.function_definitions() %}
+{%- if func.is_async() %}
+{% call kt::completable_future(func, 0) %}
+{%- endif %}
+{%- endfor %}
+{%- endif %}
+
 // Async support
 {%- if ci.has_async_fns() %}
 {% include "Async.kt" %}
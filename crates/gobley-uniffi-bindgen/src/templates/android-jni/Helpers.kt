@@ -0,0 +1,44 @@
+{% include "ffi/Helpers.kt" %}
+
+// The fields are read and written by the JNI functions, which pass `RustCallStatus`es as objects.
+@androidx.annotation.Keep
+internal open class UniffiRustCallStatusStruct(
+    @JvmField public var code: Byte,
+    @JvmField public var errorBuf: RustBufferByValue,
+) {
+    internal constructor(): this(0.toByte(), RustBufferByValue())
+
+    @androidx.annotation.Keep
+    internal class ByValue(
+        code: Byte,
+        errorBuf: RustBufferByValue,
+    ): UniffiRustCallStatusStruct(code, errorBuf) {
+        internal constructor(): this(0.toByte(), RustBufferByValue())
+    }
+
+    @androidx.annotation.Keep
+    internal class ByReference(
+        code: Byte,
+        errorBuf: RustBufferByValue,
+    ): UniffiRustCallStatusStruct(code, errorBuf) {
+        internal constructor(): this(0.toByte(), RustBufferByValue())
+    }
+}
+
+internal typealias UniffiRustCallStatus = UniffiRustCallStatusStruct.ByReference
+internal typealias UniffiRustCallStatusByValue = UniffiRustCallStatusStruct.ByValue
+
+internal object UniffiRustCallStatusHelper {
+    internal fun allocValue() = UniffiRustCallStatusByValue()
+    internal fun <U> withReference(block: (UniffiRustCallStatus) -> U): U {
+        val status = UniffiRustCallStatus()
+        return block(status)
+    }
+}
+
+// Exceptions can't be thrown from upcalls back into Rust, so they're reported to the handler of
+// uncaught exceptions of the thread instead, like JNA does for its callbacks.
+internal fun uniffiReportUpcallException(e: Throwable) {
+    val thread = Thread.currentThread()
+    thread.uncaughtExceptionHandler?.uncaughtException(thread, e)
+}
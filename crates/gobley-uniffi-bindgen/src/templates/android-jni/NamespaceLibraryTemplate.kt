@@ -0,0 +1,330 @@
+{#
+// The `external` declaration of the JNI function `name` calling `func`. `address` is whether the
+// function takes the function pointer of a callback.
+#}
+{%- macro jni_external(func, name, address) %}
+
+    @JvmStatic
+    external fun {{ name }}(
+        {%- if address %}
+        uniffiAddress: Long,
+        {%- endif %}
+        {%- for arg in func.arguments() %}
+        {{ arg.name()|var_name }}: {{ arg.type_().borrow()|jni_carrier(ci) }},
+        {%- endfor %}
+        {%- if func.has_rust_call_status_arg() %}
+        uniffiCallStatus: UniffiRustCallStatus,
+        {%- endif %}
+    ){% if let Some(return_type) = func.return_type() %}: {{ return_type|jni_carrier_from_rust(ci) }}{% endif %}
+{%- endmacro %}
+
+{#
+// The body of a Kotlin function calling `func` through the JNI function `name`, converting the
+// arguments and the result to and from their carriers. `address` is the function pointer of
+// callbacks.
+#}
+{%- macro jni_downcall(func, name, address, indent) %}
+{{ " "|repeat(indent) }}{% if func.return_type().is_some() %}val uniffiResult = {% endif %}UniffiJni.{{ name }}(
+{%- if !address.is_empty() %}
+{{ " "|repeat(indent + 4) }}{{ address }},
+{%- endif %}
+{%- for arg in func.arguments() %}
+{%- let arg_name = arg.name()|var_name %}
+{{ " "|repeat(indent + 4) }}{{ arg.type_().borrow()|jni_lower(arg_name, ci) }},
+{%- endfor %}
+{%- if func.has_rust_call_status_arg() %}
+{{ " "|repeat(indent + 4) }}uniffiCallStatus,
+{%- endif %}
+{{ " "|repeat(indent) }})
+{%- if let Some(return_type) = func.return_type() %}
+{{ " "|repeat(indent) }}return {{ return_type|jni_lift("uniffiResult", ci) }}
+{%- endif %}
+{%- endmacro %}
+
+// Define FFI callback types
+
+// The upcall adapters of the Kotlin callbacks passed to Rust. Callbacks are singletons, so their
+// adapters are created once and live as long as the program.
+private val uniffiUpcalls = java.util.concurrent.ConcurrentHashMap<Any, Any>()
+
+{%- for def in ci.ffi_definitions() %}
+{%- match def %}
+{%- when FfiDefinition::CallbackFunction(callback) %}
+{%- let callback_name = callback.name()|ffi_callback_name %}
+internal interface {{ callback_name }} {
+    public fun callback(
+        {%- for arg in callback.arguments() -%}
+        {{ arg.name().borrow()|var_name }}: {{ arg.type_().borrow()|ffi_type_name_by_value(ci) }},
+        {%- endfor -%}
+        {%- if callback.has_rust_call_status_arg() -%}
+        uniffiCallStatus: UniffiRustCallStatus,
+        {%- endif -%}
+    )
+    {%- if let Some(return_type) = callback.return_type() -%}
+    : {{ return_type|ffi_type_name_by_value(ci) }}
+    {%- endif %}
+}
+
+{%- if callback|jni_invokable %}
+
+// Calls a function pointer received from Rust.
+internal class {{ callback_name }}Native(private val uniffiAddress: Long) : {{ callback_name }} {
+    override fun callback(
+        {%- call kt::arg_list_ffi_decl(callback, 8) %}
+    )
+    {%- if let Some(return_type) = callback.return_type() -%}
+    : {{ return_type|ffi_type_name_by_value(ci) }}
+    {%- endif %} {
+        {%- call jni_downcall(callback, "{}Invoke"|format(callback_name), "uniffiAddress", 8) %}
+    }
+}
+{%- endif %}
+
+// Receives the calls of Rust to a Kotlin callback, converting the carriers of the arguments.
+@androidx.annotation.Keep
+internal class {{ callback_name }}Upcall(internal val uniffiTarget: {{ callback_name }}) {
+    fun callback(
+        {%- for arg in callback.arguments() %}
+        {{ arg.name()|var_name }}: {{ arg.type_().borrow()|jni_carrier_from_rust(ci) }},
+        {%- endfor %}
+        {%- if callback.has_rust_call_status_arg() %}
+        uniffiCallStatus: UniffiRustCallStatus,
+        {%- endif %}
+    )
+    {%- if let Some(return_type) = callback.return_type() -%}
+    : {{ return_type|jni_carrier(ci) }}
+    {%- endif %} {
+        try {
+            {% if callback.return_type().is_some() %}val uniffiResult = {% endif %}uniffiTarget.callback(
+                {%- for arg in callback.arguments() %}
+                {%- let arg_name = arg.name()|var_name %}
+                {{ arg.type_().borrow()|jni_lift(arg_name, ci) }},
+                {%- endfor %}
+                {%- if callback.has_rust_call_status_arg() %}
+                uniffiCallStatus,
+                {%- endif %}
+            )
+            {%- if let Some(return_type) = callback.return_type() %}
+            return {{ return_type|jni_lower("uniffiResult", ci) }}
+            {%- endif %}
+        } catch (e: Throwable) {
+            uniffiReportUpcallException(e)
+            {%- if let Some(return_type) = callback.return_type() %}
+            {%- let default_value = return_type|jni_default_value(ci) %}
+            return {{ return_type|jni_lower(default_value, ci) }}
+            {%- endif %}
+        }
+    }
+}
+
+internal fun {{ callback_name }}.uniffiUpcall(): {{ callback_name }}Upcall =
+    uniffiUpcalls.getOrPut(this) { {{ callback_name }}Upcall(this) } as {{ callback_name }}Upcall
+{%- when FfiDefinition::Struct(ffi_struct) %}
+{%- let struct_name = ffi_struct.name()|ffi_struct_name %}
+
+// The fields are read and written by the JNI functions, which pass structs as objects. Pointers
+// and callbacks are stored as their carriers, so that the JNI functions don't call Kotlin.
+@androidx.annotation.Keep
+internal open class {{ struct_name }}Struct(
+    {%- for field in ffi_struct.fields() %}
+    {{ field.name()|var_name }}: {{ field.type_().borrow()|ffi_type_name_for_ffi_struct(ci) }},
+    {%- endfor %}
+) {
+    internal constructor(): this(
+        {%- for field in ffi_struct.fields() %}
+        {{ field.name()|var_name }} = {{ field.type_().borrow()|jni_default_value(ci) }},
+        {%- endfor %}
+    )
+    {%- for field in ffi_struct.fields() %}
+    {%- let field_name = field.name()|var_name %}
+    {%- let field_type = field.type_().borrow()|ffi_type_name_for_ffi_struct(ci) %}
+    {%- match field.type_().borrow()|jni_field_carrier %}
+    {%- when Some(field_carrier) %}
+    {%- let carrier_name = "{}Carrier"|format(field.name()|var_name_raw) %}
+
+    @JvmField internal var {{ carrier_name }}: {{ field_carrier }} = {{ field.type_().borrow()|jni_field_lower(field_name) }}
+
+    var {{ field_name }}: {{ field_type }}
+        get() = {{ field.type_().borrow()|jni_field_lift(carrier_name) }}
+        set(value) {
+            {{ carrier_name }} = {{ field.type_().borrow()|jni_field_lower("value") }}
+        }
+    {%- when None %}
+
+    @JvmField var {{ field_name }}: {{ field_type }} = {{ field_name }}
+    {%- endmatch %}
+    {%- endfor %}
+
+    @androidx.annotation.Keep
+    internal class UniffiByValue(
+        {%- for field in ffi_struct.fields() %}
+        {{ field.name()|var_name }}: {{ field.type_().borrow()|ffi_type_name_for_ffi_struct(ci) }},
+        {%- endfor %}
+    ): {{ struct_name }}Struct({%- for field in ffi_struct.fields() %}{{ field.name()|var_name }}, {%- endfor %}) {
+        // By-value structs are read by the JNI functions when they're passed to Rust. Kept for the
+        // code shared with the JNA bindings.
+        fun write() {}
+    }
+}
+
+internal typealias {{ struct_name }} = {{ struct_name }}Struct
+
+internal fun {{ struct_name }}.uniffiSetValue(other: {{ struct_name }}) {
+    {%- for field in ffi_struct.fields() %}
+    {{ field.name()|var_name }} = other.{{ field.name()|var_name }}
+    {%- endfor %}
+}
+internal fun {{ struct_name }}.uniffiSetValue(other: {{ struct_name }}UniffiByValue) {
+    {%- for field in ffi_struct.fields() %}
+    {{ field.name()|var_name }} = other.{{ field.name()|var_name }}
+    {%- endfor %}
+}
+
+internal typealias {{ struct_name }}UniffiByValue = {{ struct_name }}Struct.UniffiByValue
+
+{%- when FfiDefinition::Function(_) %}
+{# functions are handled below #}
+{%- endmatch %}
+{%- endfor %}
+
+/**
+ * Chooses the dynamic library loaded by the bindings, e.g. to load it from an application data
+ * directory. Receives the namespace of the component and the default library name, and returns the
+ * name or the absolute path of the library, or `null` to use the default. Must be set before the
+ * bindings are used for the first time.
+ */
+{{ visibility() }}var uniffiLibraryLoader: ((componentName: String, libraryName: String) -> String?)? = null
+
+private var uniffiLibraryName: String? = null
+
+// Checked in order: `uniffiLibraryLoader`, the `uniffi.component.<namespace>.libraryOverride`
+// system property, the `UNIFFI_COMPONENT_<NAMESPACE>_LIBRARY_OVERRIDE` environment variable, and
+// the `cdylib_name`, which `System.loadLibrary` looks up in the libraries of the app.
+@Synchronized
+private fun findLibraryName(componentName: String): String {
+    uniffiLibraryName?.let { return it }
+    val defaultLibraryName = "{{ config.cdylib_name() }}"
+    val name = uniffiLibraryLoader?.invoke(componentName, defaultLibraryName)
+        ?: System.getProperty("uniffi.component.$componentName.libraryOverride")
+        ?: System.getenv("UNIFFI_COMPONENT_${componentName.uppercase()}_LIBRARY_OVERRIDE")
+        ?: defaultLibraryName
+    uniffiLibraryName = name
+    return name
+}
+
+// Loads the library at the absolute path `name`, or the library named `name`.
+private fun uniffiLoadLibrary(name: String) {
+    if (java.io.File(name).isAbsolute) {
+        System.load(name)
+    } else {
+        System.loadLibrary(name)
+    }
+}
+
+{%- let dynamic_library_dependencies = config.dynamic_library_dependencies(module_name) %}
+{%- if !dynamic_library_dependencies.is_empty() %}
+
+// Loads the dynamic libraries the main Rust library depends on before the main library, so that
+// the dynamic linker finds them already loaded.
+@Suppress("SameParameterValue")
+private fun loadDynamicLibraryDependencies(vararg dependencies: String) {
+    for (dependency in dependencies) {
+        uniffiLoadLibrary(dependency)
+    }
+}
+{%- endif %}
+
+// The JNI functions implemented by the generated Rust source file of the bindings, which forward
+// to the extern-C FFI functions. Loads the library when it's first used.
+@androidx.annotation.Keep
+internal object UniffiJni {
+    init {
+        {%- if !dynamic_library_dependencies.is_empty() %}
+        loadDynamicLibraryDependencies(
+            {%- for dynamic_library in dynamic_library_dependencies %}
+            "{{ dynamic_library }}",
+            {%- endfor %}
+        )
+        {%- endif %}
+        uniffiLoadLibrary(findLibraryName("{{ ci.namespace() }}"))
+        uniffiInit()
+    }
+
+    // Looks up the classes and fields used by the JNI functions.
+    @JvmStatic
+    private external fun uniffiInit()
+
+    // Wraps `len` bytes at `address` in a direct `java.nio.ByteBuffer`.
+    @JvmStatic
+    external fun uniffiNewByteBuffer(address: Long, len: Long): java.nio.ByteBuffer
+
+    {%- for func in ci.iter_ffi_function_definitions() %}
+    {%- call jni_external(func, func.name(), false) %}
+    {%- endfor %}
+    {%- for def in ci.ffi_definitions() %}
+    {%- match def %}
+    {%- when FfiDefinition::CallbackFunction(callback) %}
+    {%- let callback_name = callback.name()|ffi_callback_name %}
+    {%- if callback|jni_invokable %}
+    {%- call jni_external(callback, "{}Invoke"|format(callback_name), true) %}
+    {%- endif %}
+    {%- when FfiDefinition::Struct(_) %}
+    {%- when FfiDefinition::Function(_) %}
+    {%- endmatch %}
+    {%- endfor %}
+}
+
+// The extern-C FFI functions, called through JNI.
+// This is an implementation detail which will be called internally by the public API.
+internal object UniffiLib {
+    {%- if ci.contains_object_types() %}
+    // The Cleaner for the whole library
+    internal val CLEANER: UniffiCleaner by lazy {
+        UniffiCleaner.create()
+    }
+    {%- endif %}
+    {%- for func in ci.iter_ffi_function_definitions() %}
+
+    fun {{ func.name() }}(
+        {%- call kt::arg_list_ffi_decl(func, 8) %}
+    ): {% match func.return_type() %}{% when Some(return_type) %}{{ return_type.borrow()|ffi_type_name_by_value(ci) }}{% when None %}Unit{% endmatch %} {
+        {%- call jni_downcall(func, func.name(), "", 8) %}
+    }
+    {%- endfor %}
+
+    init {
+        uniffiCheckContractApiVersionAndChecksums()
+        {%- for init_fn in self.initialization_fns(ci) %}
+        {{ init_fn }}
+        {%- endfor %}
+    }
+
+    private fun uniffiCheckContractApiVersionAndChecksums() {
+        // Get the bindings contract version from our ComponentInterface
+        val bindingsContractVersion = {{ ci.uniffi_contract_version() }}
+        // Get the scaffolding contract version by calling the into the dylib
+        val scaffoldingContractVersion = {{ ci.ffi_uniffi_contract_version().name() }}()
+        val checksumMismatches = mutableListOf<UniffiChecksumMismatch>()
+        {%- if !config.omit_checksums %}
+        if (bindingsContractVersion == scaffoldingContractVersion) {
+            {%- for (name, expected_checksum) in ci.iter_checksums() %}
+            {{ name }}().let { checksum ->
+                if (checksum != {{ expected_checksum }}.toShort()) {
+                    checksumMismatches.add(UniffiChecksumMismatch("{{ name }}", {{ expected_checksum }}.toShort(), checksum))
+                }
+            }
+            {%- endfor %}
+        }
+        {%- endif %}
+        uniffiCheckLibraryIntegrity(
+            findLibraryName("{{ ci.namespace() }}"),
+            bindingsContractVersion,
+            scaffoldingContractVersion,
+            checksumMismatches,
+        )
+    }
+}
+
+{{ visibility() }}fun uniffiEnsureInitialized() {
+    UniffiLib
+}
@@ -0,0 +1,56 @@
+{% include "ffi/ObjectCleanerHelper.kt" %}
+
+// The fallback cleaner, replacing the cleaner of JNA, which the JNI bindings don't depend on.
+private class UniffiPhantomReferenceCleaner : UniffiCleaner {
+    override fun register(resource: Any, disposable: Disposable): UniffiCleaner.Cleanable =
+        UniffiPhantomReferenceCleanable(UniffiPhantomCleaner.INSTANCE.register(resource, UniffiCleanerAction(disposable)))
+}
+
+private class UniffiPhantomReferenceCleanable(
+    private val reference: UniffiPhantomCleaner.Reference,
+) : UniffiCleaner.Cleanable {
+    override fun clean() = reference.clean()
+}
+
+private class UniffiCleanerAction(private val disposable: Disposable): Runnable {
+    override fun run() {
+        disposable.destroy()
+    }
+}
+
+{%- if config.disable_java_cleaner %}
+
+private fun UniffiCleaner.Companion.create(): UniffiCleaner = UniffiPhantomReferenceCleaner()
+{%- else %}
+{{- self.add_import("android.os.Build") }}
+{{- self.add_import("androidx.annotation.RequiresApi") }}
+
+// The SystemCleaner, available from API Level 33.
+// Some API Level 33 OSes do not support using it, so we require API Level 34.
+@RequiresApi(Build.VERSION_CODES.UPSIDE_DOWN_CAKE)
+private class AndroidSystemCleaner : UniffiCleaner {
+    private val cleaner = android.system.SystemCleaner.cleaner()
+
+    override fun register(resource: Any, disposable: Disposable): UniffiCleaner.Cleanable =
+        AndroidSystemCleanable(cleaner.register(resource, UniffiCleanerAction(disposable)))
+}
+
+@RequiresApi(Build.VERSION_CODES.UPSIDE_DOWN_CAKE)
+private class AndroidSystemCleanable(
+    private val cleanable: java.lang.ref.Cleaner.Cleanable,
+) : UniffiCleaner.Cleanable {
+    override fun clean() = cleanable.clean()
+}
+
+private fun UniffiCleaner.Companion.create(): UniffiCleaner {
+    if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.UPSIDE_DOWN_CAKE) {
+        try {
+            return AndroidSystemCleaner()
+        } catch (_: IllegalAccessError) {
+            // (For Compose preview) Fallback to UniffiPhantomReferenceCleaner if
+            // AndroidSystemCleaner is unavailable, even for API level 34 or higher.
+        }
+    }
+    return UniffiPhantomReferenceCleaner()
+}
+{%- endif %}
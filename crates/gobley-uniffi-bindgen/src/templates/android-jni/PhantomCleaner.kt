@@ -0,0 +1,49 @@
+// Runs actions once their objects are unreachable, on a daemon thread. Replaces the cleaner of
+// JNA, which the JNI bindings don't depend on, where `android.system.SystemCleaner` isn't
+// available.
+internal class UniffiPhantomCleaner private constructor() {
+    private val queue = java.lang.ref.ReferenceQueue<Any>()
+
+    // The references must stay reachable until they're enqueued.
+    private val references: MutableSet<Reference> =
+        java.util.Collections.newSetFromMap(java.util.concurrent.ConcurrentHashMap())
+
+    init {
+        val thread = Thread({
+            while (true) {
+                try {
+                    (queue.remove() as Reference).clean()
+                } catch (_: InterruptedException) {
+                    // Keep cleaning; the thread lives as long as the program.
+                } catch (e: Throwable) {
+                    uniffiReportUpcallException(e)
+                }
+            }
+        }, "uniffi-cleaner")
+        thread.isDaemon = true
+        thread.start()
+    }
+
+    fun register(resource: Any, action: Runnable): Reference =
+        Reference(resource, queue, references, action).also { references.add(it) }
+
+    internal class Reference(
+        resource: Any,
+        queue: java.lang.ref.ReferenceQueue<Any>,
+        private val references: MutableSet<Reference>,
+        private val action: Runnable,
+    ) : java.lang.ref.PhantomReference<Any>(resource, queue) {
+        private val cleaned = java.util.concurrent.atomic.AtomicBoolean(false)
+
+        fun clean() {
+            if (cleaned.compareAndSet(false, true)) {
+                references.remove(this)
+                action.run()
+            }
+        }
+    }
+
+    internal companion object {
+        val INSTANCE: UniffiPhantomCleaner by lazy { UniffiPhantomCleaner() }
+    }
+}
@@ -0,0 +1,20 @@
+/**
+ * The address of native memory. JNI functions pass pointers as `Long`s, which are wrapped so that
+ * they can't be confused with other numbers.
+ */
+{{ visibility() }}class UniffiPointer(@JvmField {{ visibility() }}val address: Long) {
+    override fun equals(other: Any?): Boolean = other is UniffiPointer && other.address == address
+
+    override fun hashCode(): Int = address.hashCode()
+
+    override fun toString(): String = "native@0x${address.toString(16)}"
+}
+
+internal typealias Pointer = UniffiPointer
+internal val NullPointer: Pointer? = null
+internal fun Pointer.toLong(): Long = address
+internal fun kotlin.Long.toPointer(): Pointer = Pointer(this)
+
+// JNI functions return `0L` for null pointers, which are `null` in Kotlin like with JNA.
+internal fun uniffiNullablePointer(address: Long): Pointer? =
+    if (address != 0L) Pointer(address) else null
@@ -0,0 +1,65 @@
+// Out pointers passed to callbacks, e.g. for the return values of callback interface methods. The
+// JNI functions create them before calling Kotlin and read `uniffiValue` afterwards.
+
+@androidx.annotation.Keep
+internal class ByteByReference(@JvmField internal var uniffiValue: Byte = 0.toByte()) {
+    fun getValue(): Byte = uniffiValue
+
+    fun setValue(value: Byte) {
+        uniffiValue = value
+    }
+}
+
+@androidx.annotation.Keep
+internal class DoubleByReference(@JvmField internal var uniffiValue: Double = 0.0) {
+    fun getValue(): Double = uniffiValue
+
+    fun setValue(value: Double) {
+        uniffiValue = value
+    }
+}
+
+@androidx.annotation.Keep
+internal class FloatByReference(@JvmField internal var uniffiValue: Float = 0.0f) {
+    fun getValue(): Float = uniffiValue
+
+    fun setValue(value: Float) {
+        uniffiValue = value
+    }
+}
+
+@androidx.annotation.Keep
+internal class IntByReference(@JvmField internal var uniffiValue: Int = 0) {
+    fun getValue(): Int = uniffiValue
+
+    fun setValue(value: Int) {
+        uniffiValue = value
+    }
+}
+
+@androidx.annotation.Keep
+internal class LongByReference(@JvmField internal var uniffiValue: Long = 0L) {
+    fun getValue(): Long = uniffiValue
+
+    fun setValue(value: Long) {
+        uniffiValue = value
+    }
+}
+
+@androidx.annotation.Keep
+internal class ShortByReference(@JvmField internal var uniffiValue: Short = 0.toShort()) {
+    fun getValue(): Short = uniffiValue
+
+    fun setValue(value: Short) {
+        uniffiValue = value
+    }
+}
+
+@androidx.annotation.Keep
+internal class PointerByReference(@JvmField internal var uniffiValue: Long = 0L) {
+    fun getValue(): Pointer? = uniffiNullablePointer(uniffiValue)
+
+    fun setValue(value: Pointer?) {
+        uniffiValue = value?.address ?: 0L
+    }
+}
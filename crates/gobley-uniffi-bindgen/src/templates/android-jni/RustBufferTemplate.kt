@@ -0,0 +1,85 @@
+{% include "ffi/RustBufferTemplate.kt" %}
+
+// The fields are read and written by the JNI functions, which pass `RustBuffer`s as objects.
+@androidx.annotation.Keep
+{{ visibility() }}open class RustBufferStruct(
+    // Note: `capacity` and `len` are actually `ULong` values, but JVM only supports signed values.
+    // When dealing with these fields, make sure to call `toULong()`.
+    @JvmField {{ visibility() }}var capacity: Long,
+    @JvmField {{ visibility() }}var len: Long,
+    data: Pointer?,
+) {
+    @JvmField internal var uniffiData: Long = data?.address ?: 0L
+
+    {{ visibility() }}var data: Pointer?
+        get() = uniffiNullablePointer(uniffiData)
+        set(value) {
+            uniffiData = value?.address ?: 0L
+        }
+
+    {{ visibility() }}constructor(): this(0.toLong(), 0.toLong(), null)
+
+    @androidx.annotation.Keep
+    {{ visibility() }}class ByValue(
+        capacity: Long,
+        len: Long,
+        data: Pointer?,
+    ): RustBuffer(capacity, len, data) {
+        {{ visibility() }}constructor(): this(0.toLong(), 0.toLong(), null)
+    }
+}
+
+{{ visibility() }}typealias RustBuffer = RustBufferStruct
+{{ visibility() }}typealias RustBufferByValue = RustBufferStruct.ByValue
+
+{%- if config.pool_rust_buffers %}
+
+private val uniffiRustBufferPools = object : ThreadLocal<UniffiRustBufferPool>() {
+    override fun initialValue(): UniffiRustBufferPool {
+        val pool = UniffiRustBufferPool()
+        // Free the pooled buffers once the thread, and therefore the pool, is gone.
+        val buffers = pool.buffers
+        UniffiPhantomCleaner.INSTANCE.register(pool) {
+            buffers.forEach(RustBufferHelper::destroy)
+        }
+        return pool
+    }
+}
+
+internal fun uniffiRustBufferPool(): UniffiRustBufferPool = uniffiRustBufferPools.get()
+{%- endif %}
+
+internal fun RustBuffer.asByteBuffer(): ByteBuffer? {
+    {% call kt::check_rust_buffer_length("this.len") %}
+    val address = data?.address ?: return null
+    return ByteBuffer(UniffiJni.uniffiNewByteBuffer(address, this.len))
+}
+
+// This is a helper for safely passing byte references into the rust code.
+// It's not actually used at the moment, because there aren't many things that you
+// can take a direct pointer to in the JVM, and if we're going to copy something
+// then we might as well copy it into a `RustBuffer`. But it's here for API
+// completeness.
+
+@androidx.annotation.Keep
+internal open class ForeignBytesStruct(
+    @JvmField var len: Int,
+    data: Pointer?,
+) {
+    @JvmField internal var uniffiData: Long = data?.address ?: 0L
+
+    var data: Pointer?
+        get() = uniffiNullablePointer(uniffiData)
+        set(value) {
+            uniffiData = value?.address ?: 0L
+        }
+
+    internal constructor(): this(0, null)
+
+    @androidx.annotation.Keep
+    internal class ByValue(len: Int, data: Pointer?) : ForeignBytes(len, data) {
+        internal constructor(): this(0, null)
+    }
+}
+internal typealias ForeignBytes = ForeignBytesStruct
+internal typealias ForeignBytesByValue = ForeignBytesStruct.ByValue
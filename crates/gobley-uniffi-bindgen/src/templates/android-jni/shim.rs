@@ -0,0 +1,695 @@
+// This file was autogenerated by gobley-uniffi-bindgen. Do not edit it manually.
+//
+// The JNI functions called by the `android-jni` Kotlin bindings of `{{ ci.namespace() }}`, which
+// forward to the UniFFI scaffolding functions. Included by the crate the Android library is built
+// from with `gobley_uniffi_bindgen::include_jni_shim`, and must depend on `jni-sys`.
+
+#[allow(
+    non_camel_case_types,
+    non_snake_case,
+    non_upper_case_globals,
+    dead_code,
+    unused_unsafe,
+    unused_variables,
+    clippy::all
+)]
+mod uniffi_jni_{{ ci.namespace() }} {
+    use ::jni_sys::{
+        jbyte, jclass, jfieldID, jint, jlong, jmethodID, jobject, jvalue, JNIEnv,
+        JavaVM, JNI_EDETACHED, JNI_FALSE, JNI_OK, JNI_VERSION_1_6,
+    };
+    #[allow(unused_imports)]
+    use ::jni_sys::{jdouble, jfloat, jshort};
+    use ::std::ffi::{c_void, CString};
+    use ::std::ptr;
+    use ::std::sync::atomic::{AtomicPtr, Ordering};
+    use ::std::sync::{Mutex, OnceLock, PoisonError};
+
+    // Calls the function `$name` of the `JNIEnv` or the `JavaVM` `$env`.
+    macro_rules! jni {
+        ($env:expr, $name:ident $(, $arg:expr)* $(,)?) => {{
+            let env = $env;
+            ((**env).$name.unwrap())(env $(, $arg)*)
+        }};
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct RustBuffer {
+        capacity: u64,
+        len: u64,
+        data: *mut u8,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct ForeignBytes {
+        len: i32,
+        data: *const u8,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct RustCallStatus {
+        code: i8,
+        error_buf: RustBuffer,
+    }
+
+    // The code of `RustCallStatus` for unexpected errors.
+    const CALL_UNEXPECTED_ERROR: i8 = 2;
+
+    {%- for def in ci.ffi_definitions() %}
+    {%- match def %}
+    {%- when FfiDefinition::CallbackFunction(callback) %}
+
+    type {{ callback.name()|ffi_callback_name }} = Option<
+        unsafe extern "C" fn(
+            {%- for arg in callback.arguments() %}
+            {{ arg.type_().borrow()|jni_rust_type }},
+            {%- endfor %}
+            {%- if callback.has_rust_call_status_arg() %}
+            *mut RustCallStatus,
+            {%- endif %}
+        ){% if let Some(return_type) = callback.return_type() %} -> {{ return_type|jni_rust_type }}{% endif %},
+    >;
+    {%- when FfiDefinition::Struct(ffi_struct) %}
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct {{ ffi_struct.name()|ffi_struct_name }} {
+        {%- for field in ffi_struct.fields() %}
+        {{ field.name() }}: {{ field.type_().borrow()|jni_rust_type }},
+        {%- endfor %}
+    }
+    {%- when FfiDefinition::Function(_) %}
+    {%- endmatch %}
+    {%- endfor %}
+
+    extern "C" {
+        {%- for func in ci.iter_ffi_function_definitions() %}
+        fn {{ func.name() }}(
+            {%- for arg in func.arguments() %}
+            _: {{ arg.type_().borrow()|jni_rust_type }},
+            {%- endfor %}
+            {%- if func.has_rust_call_status_arg() %}
+            _: *mut RustCallStatus,
+            {%- endif %}
+        ){% if let Some(return_type) = func.return_type() %} -> {{ return_type|jni_rust_type }}{% endif %};
+        {%- endfor %}
+    }
+
+    // A class of the bindings and its constructor without arguments.
+    struct Class {
+        class: jclass,
+        init: jmethodID,
+    }
+
+    // The classes, fields and methods of the bindings, looked up once by `uniffiInit`. The classes
+    // are global references, so the IDs stay valid.
+    struct Cache {
+        vm: *mut JavaVM,
+        RustBufferStruct: Class,
+        RustBufferStruct_ByValue: Class,
+        RustBufferStruct_capacity: jfieldID,
+        RustBufferStruct_len: jfieldID,
+        RustBufferStruct_data: jfieldID,
+        ForeignBytesStruct_len: jfieldID,
+        ForeignBytesStruct_data: jfieldID,
+        UniffiRustCallStatusStruct_ByReference: Class,
+        UniffiRustCallStatusStruct_code: jfieldID,
+        UniffiRustCallStatusStruct_errorBuf: jfieldID,
+        ByteByReference: Class,
+        ByteByReference_value: jfieldID,
+        ShortByReference: Class,
+        ShortByReference_value: jfieldID,
+        IntByReference: Class,
+        IntByReference_value: jfieldID,
+        LongByReference: Class,
+        LongByReference_value: jfieldID,
+        FloatByReference: Class,
+        FloatByReference_value: jfieldID,
+        DoubleByReference: Class,
+        DoubleByReference_value: jfieldID,
+        PointerByReference: Class,
+        PointerByReference_value: jfieldID,
+        {%- for def in ci.ffi_definitions() %}
+        {%- match def %}
+        {%- when FfiDefinition::CallbackFunction(callback) %}
+        {{ callback.name()|ffi_callback_name }}Upcall_callback: jmethodID,
+        {%- when FfiDefinition::Struct(ffi_struct) %}
+        {%- let struct_name = ffi_struct.name()|ffi_struct_name %}
+        {{ struct_name }}Struct: Class,
+        {%- for field in ffi_struct.fields() %}
+        {{ struct_name }}Struct_{{ field.name() }}: jfieldID,
+        {%- endfor %}
+        {%- when FfiDefinition::Function(_) %}
+        {%- endmatch %}
+        {%- endfor %}
+    }
+
+    unsafe impl Send for Cache {}
+    unsafe impl Sync for Cache {}
+
+    static CACHE: OnceLock<Cache> = OnceLock::new();
+
+    fn cache() -> &'static Cache {
+        CACHE
+            .get()
+            .expect("the JNI functions are called before `UniffiJni.uniffiInit`")
+    }
+
+    // A global reference to the class `name`. On failure, returns `None` with the exception pending.
+    unsafe fn find_class(env: *mut JNIEnv, name: &str) -> Option<jclass> {
+        let name = CString::new(name).ok()?;
+        let local = jni!(env, FindClass, name.as_ptr());
+        if local.is_null() {
+            return None;
+        }
+        let class = jni!(env, NewGlobalRef, local);
+        jni!(env, DeleteLocalRef, local);
+        Some(class)
+    }
+
+    unsafe fn find_constructible_class(env: *mut JNIEnv, name: &str) -> Option<Class> {
+        let class = find_class(env, name)?;
+        let init = find_method(env, class, "<init>", "()V")?;
+        Some(Class { class, init })
+    }
+
+    unsafe fn find_field(
+        env: *mut JNIEnv,
+        class: jclass,
+        name: &str,
+        descriptor: &str,
+    ) -> Option<jfieldID> {
+        let name = CString::new(name).ok()?;
+        let descriptor = CString::new(descriptor).ok()?;
+        let field = jni!(env, GetFieldID, class, name.as_ptr(), descriptor.as_ptr());
+        (!field.is_null()).then_some(field)
+    }
+
+    unsafe fn find_method(
+        env: *mut JNIEnv,
+        class: jclass,
+        name: &str,
+        descriptor: &str,
+    ) -> Option<jmethodID> {
+        let name = CString::new(name).ok()?;
+        let descriptor = CString::new(descriptor).ok()?;
+        let method = jni!(env, GetMethodID, class, name.as_ptr(), descriptor.as_ptr());
+        (!method.is_null()).then_some(method)
+    }
+
+    unsafe fn load_cache(env: *mut JNIEnv) -> Option<Cache> {
+        let mut vm: *mut JavaVM = ptr::null_mut();
+        if jni!(env, GetJavaVM, &mut vm) != JNI_OK {
+            return None;
+        }
+        let RustBufferStruct = find_constructible_class(env, "{{ "RustBufferStruct"|jni_class_name(config) }}")?;
+        let ForeignBytesStruct = find_class(env, "{{ "ForeignBytesStruct"|jni_class_name(config) }}")?;
+        let UniffiRustCallStatusStruct_ByReference = find_constructible_class(
+            env,
+            "{{ "UniffiRustCallStatusStruct$ByReference"|jni_class_name(config) }}",
+        )?;
+        let ByteByReference =
+            find_constructible_class(env, "{{ "ByteByReference"|jni_class_name(config) }}")?;
+        let ShortByReference =
+            find_constructible_class(env, "{{ "ShortByReference"|jni_class_name(config) }}")?;
+        let IntByReference =
+            find_constructible_class(env, "{{ "IntByReference"|jni_class_name(config) }}")?;
+        let LongByReference =
+            find_constructible_class(env, "{{ "LongByReference"|jni_class_name(config) }}")?;
+        let FloatByReference =
+            find_constructible_class(env, "{{ "FloatByReference"|jni_class_name(config) }}")?;
+        let DoubleByReference =
+            find_constructible_class(env, "{{ "DoubleByReference"|jni_class_name(config) }}")?;
+        let PointerByReference =
+            find_constructible_class(env, "{{ "PointerByReference"|jni_class_name(config) }}")?;
+        {%- for def in ci.ffi_definitions() %}
+        {%- match def %}
+        {%- when FfiDefinition::Struct(ffi_struct) %}
+        {%- let struct_name = ffi_struct.name()|ffi_struct_name %}
+        let {{ struct_name }}Struct = find_constructible_class(
+            env,
+            "{{ "{}Struct"|format(struct_name)|jni_class_name(config) }}",
+        )?;
+        {%- when FfiDefinition::CallbackFunction(_) %}
+        {%- when FfiDefinition::Function(_) %}
+        {%- endmatch %}
+        {%- endfor %}
+        Some(Cache {
+            vm,
+            RustBufferStruct_ByValue: find_constructible_class(
+                env,
+                "{{ "RustBufferStruct$ByValue"|jni_class_name(config) }}",
+            )?,
+            RustBufferStruct_capacity: find_field(env, RustBufferStruct.class, "capacity", "J")?,
+            RustBufferStruct_len: find_field(env, RustBufferStruct.class, "len", "J")?,
+            RustBufferStruct_data: find_field(env, RustBufferStruct.class, "uniffiData", "J")?,
+            RustBufferStruct,
+            ForeignBytesStruct_len: find_field(env, ForeignBytesStruct, "len", "I")?,
+            ForeignBytesStruct_data: find_field(env, ForeignBytesStruct, "uniffiData", "J")?,
+            UniffiRustCallStatusStruct_code: find_field(
+                env,
+                UniffiRustCallStatusStruct_ByReference.class,
+                "code",
+                "B",
+            )?,
+            UniffiRustCallStatusStruct_errorBuf: find_field(
+                env,
+                UniffiRustCallStatusStruct_ByReference.class,
+                "errorBuf",
+                "L{{ "RustBufferStruct$ByValue"|jni_class_name(config) }};",
+            )?,
+            UniffiRustCallStatusStruct_ByReference,
+            ByteByReference_value: find_field(env, ByteByReference.class, "uniffiValue", "B")?,
+            ByteByReference,
+            ShortByReference_value: find_field(env, ShortByReference.class, "uniffiValue", "S")?,
+            ShortByReference,
+            IntByReference_value: find_field(env, IntByReference.class, "uniffiValue", "I")?,
+            IntByReference,
+            LongByReference_value: find_field(env, LongByReference.class, "uniffiValue", "J")?,
+            LongByReference,
+            FloatByReference_value: find_field(env, FloatByReference.class, "uniffiValue", "F")?,
+            FloatByReference,
+            DoubleByReference_value: find_field(env, DoubleByReference.class, "uniffiValue", "D")?,
+            DoubleByReference,
+            PointerByReference_value: find_field(env, PointerByReference.class, "uniffiValue", "J")?,
+            PointerByReference,
+            {%- for def in ci.ffi_definitions() %}
+            {%- match def %}
+            {%- when FfiDefinition::CallbackFunction(callback) %}
+            {%- let callback_name = callback.name()|ffi_callback_name %}
+            {{ callback_name }}Upcall_callback: find_method(
+                env,
+                find_class(env, "{{ "{}Upcall"|format(callback_name)|jni_class_name(config) }}")?,
+                "callback",
+                "{{ callback|jni_upcall_descriptor(config) }}",
+            )?,
+            {%- when FfiDefinition::Struct(ffi_struct) %}
+            {%- let struct_name = ffi_struct.name()|ffi_struct_name %}
+            {%- for field in ffi_struct.fields() %}
+            {%- let field_name = field.name()|var_name_raw %}
+            {{ struct_name }}Struct_{{ field.name() }}: find_field(
+                env,
+                {{ struct_name }}Struct.class,
+                "{{ field_name }}{% match field.type_().borrow()|jni_field_carrier %}{% when Some(_) %}Carrier{% when None %}{% endmatch %}",
+                "{{ field.type_().borrow()|jni_field_descriptor(config) }}",
+            )?,
+            {%- endfor %}
+            {{ struct_name }}Struct,
+            {%- when FfiDefinition::Function(_) %}
+            {%- endmatch %}
+            {%- endfor %}
+        })
+    }
+
+    // The environment of the current thread, attaching it to the JVM when Rust calls Kotlin from a
+    // thread it created. Such threads are detached when they exit.
+    unsafe fn attach() -> *mut JNIEnv {
+        struct DetachOnExit(*mut JavaVM);
+
+        impl Drop for DetachOnExit {
+            fn drop(&mut self) {
+                unsafe {
+                    jni!(self.0, DetachCurrentThread);
+                }
+            }
+        }
+
+        thread_local! {
+            static DETACH_ON_EXIT: std::cell::Cell<Option<DetachOnExit>> = const { std::cell::Cell::new(None) };
+        }
+
+        let vm = cache().vm;
+        let mut env: *mut c_void = ptr::null_mut();
+        match jni!(vm, GetEnv, &mut env, JNI_VERSION_1_6) {
+            JNI_OK => env.cast(),
+            JNI_EDETACHED => {
+                if jni!(vm, AttachCurrentThreadAsDaemon, &mut env, ptr::null_mut()) != JNI_OK {
+                    return ptr::null_mut();
+                }
+                DETACH_ON_EXIT.with(|detach| detach.set(Some(DetachOnExit(vm))));
+                env.cast()
+            }
+            _ => ptr::null_mut(),
+        }
+    }
+
+    // Kotlin callbacks catch their exceptions, but the pending ones must be cleared before the next
+    // JNI call. Returns whether there was one.
+    unsafe fn clear_exception(env: *mut JNIEnv) -> bool {
+        if jni!(env, ExceptionCheck) == JNI_FALSE {
+            return false;
+        }
+        jni!(env, ExceptionDescribe);
+        jni!(env, ExceptionClear);
+        true
+    }
+
+    unsafe fn new_object(env: *mut JNIEnv, class: &Class) -> jobject {
+        jni!(env, NewObjectA, class.class, class.init, ptr::null())
+    }
+
+    unsafe fn read_rust_buffer(env: *mut JNIEnv, buffer: jobject) -> RustBuffer {
+        if buffer.is_null() {
+            return RustBuffer {
+                capacity: 0,
+                len: 0,
+                data: ptr::null_mut(),
+            };
+        }
+        let cache = cache();
+        RustBuffer {
+            capacity: jni!(env, GetLongField, buffer, cache.RustBufferStruct_capacity) as u64,
+            len: jni!(env, GetLongField, buffer, cache.RustBufferStruct_len) as u64,
+            data: jni!(env, GetLongField, buffer, cache.RustBufferStruct_data) as usize as *mut u8,
+        }
+    }
+
+    unsafe fn new_rust_buffer(env: *mut JNIEnv, buffer: RustBuffer) -> jobject {
+        let cache = cache();
+        let object = new_object(env, &cache.RustBufferStruct_ByValue);
+        if !object.is_null() {
+            write_rust_buffer(env, object, buffer);
+        }
+        object
+    }
+
+    unsafe fn write_rust_buffer(env: *mut JNIEnv, object: jobject, buffer: RustBuffer) {
+        let cache = cache();
+        jni!(env, SetLongField, object, cache.RustBufferStruct_capacity, buffer.capacity as jlong);
+        jni!(env, SetLongField, object, cache.RustBufferStruct_len, buffer.len as jlong);
+        jni!(env, SetLongField, object, cache.RustBufferStruct_data, buffer.data as usize as jlong);
+    }
+
+    unsafe fn read_foreign_bytes(env: *mut JNIEnv, bytes: jobject) -> ForeignBytes {
+        let cache = cache();
+        ForeignBytes {
+            len: jni!(env, GetIntField, bytes, cache.ForeignBytesStruct_len),
+            data: jni!(env, GetLongField, bytes, cache.ForeignBytesStruct_data) as usize as *const u8,
+        }
+    }
+
+    unsafe fn read_call_status(env: *mut JNIEnv, status: jobject) -> RustCallStatus {
+        let cache = cache();
+        let error_buf = jni!(env, GetObjectField, status, cache.UniffiRustCallStatusStruct_errorBuf);
+        let status = RustCallStatus {
+            code: jni!(env, GetByteField, status, cache.UniffiRustCallStatusStruct_code) as i8,
+            error_buf: read_rust_buffer(env, error_buf),
+        };
+        jni!(env, DeleteLocalRef, error_buf);
+        status
+    }
+
+    // Reports the outcome of a call to the `UniffiRustCallStatus` passed by Kotlin. Successful calls
+    // leave it untouched, which spares the JNI calls.
+    unsafe fn write_call_status(env: *mut JNIEnv, object: jobject, status: &RustCallStatus) {
+        if status.code == 0 || object.is_null() {
+            return;
+        }
+        let cache = cache();
+        jni!(env, SetByteField, object, cache.UniffiRustCallStatusStruct_code, status.code as jbyte);
+        let error_buf = new_rust_buffer(env, status.error_buf);
+        jni!(env, SetObjectField, object, cache.UniffiRustCallStatusStruct_errorBuf, error_buf);
+        jni!(env, DeleteLocalRef, error_buf);
+    }
+
+    // The global references of the upcall adapters stored in the slots. They are never deleted,
+    // since a forwarding function may still be calling the previous adapter of a slot when it's
+    // replaced. Kotlin passes the same adapter objects every time, so there's one per adapter.
+    static CALLBACKS: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+    // Keeps the upcall adapter `callback` in `slot` for its forwarding function. Returns whether
+    // there's a callback.
+    unsafe fn store_callback(env: *mut JNIEnv, slot: &AtomicPtr<c_void>, callback: jobject) -> bool {
+        if callback.is_null() {
+            return false;
+        }
+        let current = slot.load(Ordering::Acquire) as jobject;
+        if !current.is_null() && jni!(env, IsSameObject, current, callback) != JNI_FALSE {
+            return true;
+        }
+        let mut callbacks = CALLBACKS.lock().unwrap_or_else(PoisonError::into_inner);
+        let retained = callbacks
+            .iter()
+            .map(|&retained| retained as jobject)
+            .find(|&retained| jni!(env, IsSameObject, retained, callback) != JNI_FALSE);
+        let callback = match retained {
+            Some(retained) => retained,
+            None => {
+                let callback = jni!(env, NewGlobalRef, callback);
+                callbacks.push(callback as usize);
+                callback
+            }
+        };
+        slot.store(callback as *mut c_void, Ordering::Release);
+        true
+    }
+
+    {%- for def in ci.ffi_definitions() %}
+    {%- match def %}
+    {%- when FfiDefinition::Struct(ffi_struct) %}
+    {%- let struct_name = ffi_struct.name()|ffi_struct_name %}
+
+    unsafe fn read_{{ struct_name }}(env: *mut JNIEnv, object: jobject) -> {{ struct_name }} {
+        let cache = cache();
+        {{ struct_name }} {
+            {%- for field in ffi_struct.fields() %}
+            {%- let getter = "jni!(env, {}, object, cache.{}Struct_{})"|format(field.type_().borrow()|jni_field_getter, struct_name, field.name()) %}
+            {%- let slot = "{}_{}"|format(struct_name, field.name()) %}
+            {{ field.name() }}: {{ field.type_().borrow()|jni_from_java(getter, slot) }},
+            {%- endfor %}
+        }
+    }
+    {%- when FfiDefinition::CallbackFunction(callback) %}
+    {%- if callback|jni_upcallable %}
+    {%- let callback_name = callback.name()|ffi_callback_name %}
+
+    // Calls the `{{ callback_name }}Upcall` `target`.
+    unsafe fn upcall_{{ callback_name }}(
+        target: jobject,
+        {%- for arg in callback.arguments() %}
+        arg{{ loop.index0 }}: {{ arg.type_().borrow()|jni_rust_type }},
+        {%- endfor %}
+        {%- if callback.has_rust_call_status_arg() %}
+        out_status: *mut RustCallStatus,
+        {%- endif %}
+    ){% if let Some(return_type) = callback.return_type() %} -> {{ return_type|jni_rust_type }}{% endif %} {
+        let env = attach();
+        if env.is_null() || target.is_null() || jni!(env, PushLocalFrame, 16) != JNI_OK {
+            {%- if callback.has_rust_call_status_arg() %}
+            (*out_status).code = CALL_UNEXPECTED_ERROR;
+            {%- endif %}
+            return{% if callback.return_type().is_some() %} ::std::mem::zeroed(){% endif %};
+        }
+        let cache = cache();
+        {%- for arg in callback.arguments() %}
+        {%- match arg.type_() %}
+        {%- when FfiType::MutReference(inner) %}
+        let out{{ loop.index0 }} = new_object(env, &cache.{{ inner|jni_reference_class }});
+        {%- when _ %}
+        {%- endmatch %}
+        {%- endfor %}
+        {%- if callback.has_rust_call_status_arg() %}
+        let status = new_object(env, &cache.UniffiRustCallStatusStruct_ByReference);
+        {%- endif %}
+        let args: &[jvalue] = &[
+            {%- for arg in callback.arguments() %}
+            {%- match arg.type_() %}
+            {%- when FfiType::MutReference(_) %}
+            jvalue { l: out{{ loop.index0 }} },
+            {%- when _ %}
+            {%- let arg_name = "arg{}"|format(loop.index0) %}
+            jvalue { {{ arg.type_().borrow()|jni_jvalue_field }}: {{ arg.type_().borrow()|jni_to_java(arg_name) }} },
+            {%- endmatch %}
+            {%- endfor %}
+            {%- if callback.has_rust_call_status_arg() %}
+            jvalue { l: status },
+            {%- endif %}
+        ];
+        let result = jni!(
+            env,
+            {{ callback.return_type()|jni_call_method }},
+            target,
+            cache.{{ callback_name }}Upcall_callback,
+            args.as_ptr(),
+        );
+        let failed = clear_exception(env);
+        {%- for arg in callback.arguments() %}
+        {%- match arg.type_() %}
+        {%- when FfiType::MutReference(inner) %}
+        {%- let out_name = "out{}"|format(loop.index0) %}
+        if !failed {
+            *arg{{ loop.index0 }} = {{ inner|jni_from_java_by_reference(out_name) }};
+        }
+        {%- when _ %}
+        {%- endmatch %}
+        {%- endfor %}
+        {%- if callback.has_rust_call_status_arg() %}
+        if failed {
+            (*out_status).code = CALL_UNEXPECTED_ERROR;
+        } else {
+            *out_status = read_call_status(env, status);
+        }
+        {%- endif %}
+        {%- match callback.return_type() %}
+        {%- when Some(return_type) %}
+        let result = if failed {
+            ::std::mem::zeroed()
+        } else {
+            {{ return_type|jni_from_java("result", "") }}
+        };
+        jni!(env, PopLocalFrame, ptr::null_mut());
+        result
+        {%- when None %}
+        let _ = result;
+        jni!(env, PopLocalFrame, ptr::null_mut());
+        {%- endmatch %}
+    }
+    {%- endif %}
+    {%- when FfiDefinition::Function(_) %}
+    {%- endmatch %}
+    {%- endfor %}
+    {%- for slot in self.callback_slots() %}
+    {%- let callback_name = slot.callback.name()|ffi_callback_name %}
+
+    static {{ slot.name }}: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
+
+    unsafe extern "C" fn {{ slot.name }}_upcall(
+        {%- for arg in slot.callback.arguments() %}
+        arg{{ loop.index0 }}: {{ arg.type_().borrow()|jni_rust_type }},
+        {%- endfor %}
+        {%- if slot.callback.has_rust_call_status_arg() %}
+        out_status: *mut RustCallStatus,
+        {%- endif %}
+    ){% if let Some(return_type) = slot.callback.return_type() %} -> {{ return_type|jni_rust_type }}{% endif %} {
+        upcall_{{ callback_name }}(
+            {{ slot.name }}.load(Ordering::Acquire) as jobject,
+            {%- for arg in slot.callback.arguments() %}
+            arg{{ loop.index0 }},
+            {%- endfor %}
+            {%- if slot.callback.has_rust_call_status_arg() %}
+            out_status,
+            {%- endif %}
+        )
+    }
+    {%- endfor %}
+
+    #[no_mangle]
+    pub unsafe extern "system" fn {{ "uniffiInit"|jni_native_name(config) }}(env: *mut JNIEnv, _class: jclass) {
+        if CACHE.get().is_some() {
+            return;
+        }
+        // On failure, the `NoClassDefFoundError` or `NoSuchFieldError` is thrown by `uniffiInit`.
+        if let Some(cache) = load_cache(env) {
+            let _ = CACHE.set(cache);
+        }
+    }
+
+    #[no_mangle]
+    pub unsafe extern "system" fn {{ "uniffiNewByteBuffer"|jni_native_name(config) }}(
+        env: *mut JNIEnv,
+        _class: jclass,
+        address: jlong,
+        len: jlong,
+    ) -> jobject {
+        jni!(env, NewDirectByteBuffer, address as usize as *mut c_void, len)
+    }
+
+    {%- for func in ci.iter_ffi_function_definitions() %}
+
+    #[no_mangle]
+    pub unsafe extern "system" fn {{ func.name()|jni_native_name(config) }}(
+        env: *mut JNIEnv,
+        _class: jclass,
+        {%- for arg in func.arguments() %}
+        arg{{ loop.index0 }}: {{ arg.type_().borrow()|jni_sys_type }},
+        {%- endfor %}
+        {%- if func.has_rust_call_status_arg() %}
+        call_status: jobject,
+        {%- endif %}
+    ){% if let Some(return_type) = func.return_type() %} -> {{ return_type|jni_sys_type_from_rust }}{% endif %} {
+        {%- for arg in func.arguments() %}
+        {%- let arg_name = "arg{}"|format(loop.index0) %}
+        {%- let slot = "{}_{}"|format(func.name(), arg.name()) %}
+        let {{ arg_name }}: {{ arg.type_().borrow()|jni_rust_type }} = {{ arg.type_().borrow()|jni_from_java(arg_name, slot) }};
+        {%- endfor %}
+        {%- if func.has_rust_call_status_arg() %}
+        let mut status: RustCallStatus = ::std::mem::zeroed();
+        {%- endif %}
+        let result = {{ func.name() }}(
+            {%- for arg in func.arguments() %}
+            arg{{ loop.index0 }},
+            {%- endfor %}
+            {%- if func.has_rust_call_status_arg() %}
+            &mut status,
+            {%- endif %}
+        );
+        {%- if func.has_rust_call_status_arg() %}
+        write_call_status(env, call_status, &status);
+        {%- endif %}
+        {%- match func.return_type() %}
+        {%- when Some(return_type) %}
+        {{ return_type|jni_to_java("result") }}
+        {%- when None %}
+        let _ = result;
+        {%- endmatch %}
+    }
+    {%- endfor %}
+
+    {%- for def in ci.ffi_definitions() %}
+    {%- match def %}
+    {%- when FfiDefinition::CallbackFunction(callback) %}
+    {%- if callback|jni_invokable %}
+    {%- let callback_name = callback.name()|ffi_callback_name %}
+
+    // Calls the function pointer `address` for `{{ callback_name }}Native`.
+    #[no_mangle]
+    pub unsafe extern "system" fn {{ "{}Invoke"|format(callback_name)|jni_native_name(config) }}(
+        env: *mut JNIEnv,
+        _class: jclass,
+        address: jlong,
+        {%- for arg in callback.arguments() %}
+        arg{{ loop.index0 }}: {{ arg.type_().borrow()|jni_sys_type }},
+        {%- endfor %}
+        {%- if callback.has_rust_call_status_arg() %}
+        call_status: jobject,
+        {%- endif %}
+    ){% if let Some(return_type) = callback.return_type() %} -> {{ return_type|jni_sys_type_from_rust }}{% endif %} {
+        let callback: {{ callback_name }} = ::std::mem::transmute(address as usize);
+        let Some(callback) = callback else {
+            return{% if callback.return_type().is_some() %} ::std::mem::zeroed(){% endif %};
+        };
+        {%- for arg in callback.arguments() %}
+        {%- let arg_name = "arg{}"|format(loop.index0) %}
+        {%- let slot = "{}_{}"|format(callback_name, arg.name()) %}
+        let {{ arg_name }}: {{ arg.type_().borrow()|jni_rust_type }} = {{ arg.type_().borrow()|jni_from_java(arg_name, slot) }};
+        {%- endfor %}
+        {%- if callback.has_rust_call_status_arg() %}
+        let mut status: RustCallStatus = ::std::mem::zeroed();
+        {%- endif %}
+        let result = callback(
+            {%- for arg in callback.arguments() %}
+            arg{{ loop.index0 }},
+            {%- endfor %}
+            {%- if callback.has_rust_call_status_arg() %}
+            &mut status,
+            {%- endif %}
+        );
+        {%- if callback.has_rust_call_status_arg() %}
+        write_call_status(env, call_status, &status);
+        {%- endif %}
+        {%- match callback.return_type() %}
+        {%- when Some(return_type) %}
+        {{ return_type|jni_to_java("result") }}
+        {%- when None %}
+        let _ = result;
+        {%- endmatch %}
+    }
+    {%- endif %}
+    {%- when FfiDefinition::Struct(_) %}
+    {%- when FfiDefinition::Function(_) %}
+    {%- endmatch %}
+    {%- endfor %}
+}
@@ -1,5 +1,13 @@
 
-{%- match config.custom_types.get(name.as_str())  %}
+{%- if config.custom_type_is_expect_class(name) %}
+/**
+ * The custom type is mapped to a different Kotlin type on each target, so the bindings of each
+ * target define this class with an actual typealias.
+ */
+{{ visibility() }}expect class {{ type_name }}
+
+{%- else %}
+{%- match config.custom_type(name, module_name) %}
 {%- when None %}
 {#- Define the type using typealiases to the builtin #}
 /**
@@ -34,3 +42,4 @@
 {%- endmatch %}
 
 {%- endmatch %}
+{%- endif %}
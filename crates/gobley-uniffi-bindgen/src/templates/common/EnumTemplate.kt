@@ -7,6 +7,7 @@
 #}
 
 {%- let should_generate_serializable = config.generate_serializable() && e|serializable_enum(ci) -%}
+{%- let has_unknown_variant = config.generate_unknown_enum_variant(e.name()) -%}
 
 {%- if e.is_flat() %}
 
@@ -17,8 +18,14 @@
 {{ visibility() }}enum class {{ type_name }} {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
-    {{ variant|variant_name(config) }}{% if loop.last %};{% else %},{% endif %}
+    {{ variant|variant_name(config) }}{% if loop.last && !has_unknown_variant %};{% else %},{% endif %}
     {%- endfor %}
+    {%- if has_unknown_variant %}
+    /**
+     * A variant added in a newer version of the library, unknown to these bindings.
+     */
+    {{ config.unknown_enum_entry_name() }};
+    {%- endif %}
     {{ visibility() }}companion object
 }
 {% when Some(variant_discr_type) %}
@@ -26,8 +33,15 @@
 {{ visibility() }}enum class {{ type_name }}(public val value: {{ variant_discr_type|type_name(ci) }}) {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
-    {{ variant|variant_name(config) }}({{ e|variant_discr_literal(loop.index0) }}){% if loop.last %};{% else %},{% endif %}
+    {{ variant|variant_name(config) }}({{ e|variant_discr_literal(loop.index0) }}){% if loop.last && !has_unknown_variant %};{% else %},{% endif %}
     {%- endfor %}
+    {%- if has_unknown_variant %}
+    /**
+     * A variant added in a newer version of the library, unknown to these bindings. [value] is
+     * always the maximum value of its type.
+     */
+    {{ config.unknown_enum_entry_name() }}({{ variant_discr_type|type_name(ci) }}.MAX_VALUE);
+    {%- endif %}
     {{ visibility() }}companion object
 }
 {% endmatch %}
@@ -70,6 +84,17 @@
     }
     {%- endif %}
     {% endfor %}
+    {%- if has_unknown_variant %}
+    /**
+     * A variant added in a newer version of the library, unknown to these bindings. [rawValue] is
+     * the index of the variant used by UniFFI.
+     */
+    {% if should_generate_serializable %}@kotlinx.serialization.Serializable{% endif %}
+    {{ visibility() }}data class Unknown(val rawValue: Int) : {{ type_name }}() {% if contains_object_references %} {
+        override fun destroy(): Unit = Unit
+    }
+    {% endif %}
+    {%- endif %}
 }
 
 {% endif %}
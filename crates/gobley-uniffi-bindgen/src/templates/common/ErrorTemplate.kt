@@ -3,9 +3,10 @@
 {%- let ffi_converter_name = type_|ffi_converter_name %}
 {%- let canonical_type_name = type_|canonical_name %}
 
+{%- call kt::error_base_class(e.name(), type_name) %}
 {% if e.is_flat() %}
 {%- call kt::docstring(e, 0) %}
-{{ visibility() }}sealed class {{ type_name }}(message: String): kotlin.Exception(message){% if contains_object_references %}, Disposable {% endif %} {
+{{ visibility() }}sealed class {{ type_name }}(message: String): {% call kt::error_superclass(e.name(), type_name) %}(message){% call kt::error_interfaces(e.name()) %}{% if contains_object_references %}, Disposable {% endif %} {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
     {{ visibility() }}class {{ variant|error_variant_name }}(message: String) : {{ type_name }}(message)
@@ -13,7 +14,7 @@
 }
 {%- else %}
 {%- call kt::docstring(e, 0) %}
-{{ visibility() }}sealed class {{ type_name }}: kotlin.Exception(){% if contains_object_references %}, Disposable {% endif %} {
+{{ visibility() }}sealed class {{ type_name }}: {% call kt::error_superclass(e.name(), type_name) %}(){% call kt::error_interfaces(e.name()) %}{% if contains_object_references %}, Disposable {% endif %} {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
     {%- let variant_name = variant|error_variant_name %}
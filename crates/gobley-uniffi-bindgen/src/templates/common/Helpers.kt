@@ -1 +1,210 @@
-{{ visibility() }}class InternalException(message: String) : kotlin.Exception(message)
\ No newline at end of file
+{{ visibility() }}open class InternalException(message: String) : kotlin.Exception(message)
+
+/**
+ * Thrown when the Rust code panics.
+ *
+ * @property functionName The name of the FFI function that panicked, if known.
+ */
+{{ visibility() }}class RustPanicException(
+    message: String,
+    {{ visibility() }}val functionName: String?,
+) : InternalException(message)
+
+/**
+ * Called with every panic of the Rust code before the [RustPanicException] is thrown, e.g. to
+ * report it to a crash reporter. Exceptions thrown by the handler propagate instead of the
+ * [RustPanicException].
+ */
+{{ visibility() }}var uniffiRustPanicHandler: ((RustPanicException) -> Unit)? = null
+
+/**
+ * Thrown when Rust uses the handle of a callback interface implementation or a future after it was
+ * released, e.g. when a callback is invoked after the object was freed.
+ *
+ * @property handle The stale handle.
+ */
+{{ visibility() }}class UniffiStaleHandleException(
+    message: String,
+    {{ visibility() }}val handle: Long,
+) : InternalException(message)
+
+
+/**
+ * Thrown when the loaded Rust library doesn't match the bindings, usually because a stale build of
+ * the library was loaded.
+ *
+ * @property libraryPath The path of the loaded library, if known.
+ * @property checksumMismatches The API checksums returned by the library that differ from the
+ * bindings. Empty when the contract versions differ, as the checksums are not checked then.
+ */
+{{ visibility() }}class UniffiLibraryMismatchException(
+    message: String,
+    {{ visibility() }}val libraryPath: String?,
+    {{ visibility() }}val bindingsContractVersion: Int,
+    {{ visibility() }}val libraryContractVersion: Int,
+    {{ visibility() }}val checksumMismatches: List<UniffiChecksumMismatch>,
+) : InternalException(message)
+
+/**
+ * An API checksum of the loaded Rust library that differs from the bindings.
+ *
+ * @property functionName The name of the FFI function returning the checksum.
+ */
+{{ visibility() }}data class UniffiChecksumMismatch(
+    {{ visibility() }}val functionName: String,
+    {{ visibility() }}val expected: Short,
+    {{ visibility() }}val actual: Short,
+)
+{%- if config.instrument_calls %}
+
+/**
+ * A call across the FFI, reported to [uniffiCallInterceptor] when `instrument_calls` is enabled.
+ *
+ * @property functionName The Kotlin name of the function or method. The class name for primary
+ * constructors.
+ * @property objectType The Kotlin name of the object or callback interface the method belongs to,
+ * or `null` for top-level functions.
+ * @property isCallback Whether Rust called a method of a callback interface implemented in Kotlin.
+ * @property duration How long the call took, including the suspensions of `suspend` functions.
+ * @property exception The exception thrown by the call, or `null` when it returned normally.
+ */
+{{ visibility() }}class UniffiCallEvent internal constructor(
+    {{ visibility() }}val functionName: String,
+    {{ visibility() }}val objectType: String?,
+    {{ visibility() }}val isCallback: Boolean,
+    {{ visibility() }}val duration: kotlin.time.Duration,
+    {{ visibility() }}val exception: Throwable?,
+) {
+    override fun toString(): String {
+        val name = if (objectType != null) "$objectType.$functionName" else functionName
+        val outcome = if (exception != null) "threw $exception" else "returned"
+        return "$name $outcome after $duration"
+    }
+}
+
+/**
+ * Called after every call across the FFI on the thread the call returned on, e.g. to measure the
+ * duration of the calls, or to find the ones made on the main thread. Exceptions thrown by the
+ * interceptor propagate to the caller, unless the call itself threw, in which case they are added
+ * to the suppressed exceptions of the exception of the call.
+ */
+{{ visibility() }}var uniffiCallInterceptor: ((UniffiCallEvent) -> Unit)? = null
+
+internal inline fun <T> uniffiInstrumentCall(
+    functionName: String,
+    objectType: String?,
+    isCallback: Boolean,
+    block: () -> T,
+): T {
+    val interceptor = uniffiCallInterceptor ?: return block()
+    val start = kotlin.time.TimeSource.Monotonic.markNow()
+    val result = try {
+        block()
+    } catch (e: Throwable) {
+        try {
+            interceptor(UniffiCallEvent(functionName, objectType, isCallback, start.elapsedNow(), e))
+        } catch (interceptorException: Throwable) {
+            // Keep the exception of the call, which the caller expects.
+            e.addSuppressed(interceptorException)
+        }
+        throw e
+    }
+    interceptor(UniffiCallEvent(functionName, objectType, isCallback, start.elapsedNow(), null))
+    return result
+}
+{%- endif %}
+{%- if config.track_object_leaks %}
+
+/**
+ * A Rust object that was not destroyed yet, recorded when `track_object_leaks` is enabled.
+ *
+ * @property typeName The name of the Kotlin class of the object.
+ * @property creationStackTrace The stack trace of the creation of the Kotlin object.
+ * @property createdAt When the object was created. `createdAt.elapsedNow()` is the age of the
+ * object.
+ */
+{{ visibility() }}class UniffiLiveObject internal constructor(
+    {{ visibility() }}val typeName: String,
+    {{ visibility() }}val creationStackTrace: String,
+    {{ visibility() }}val createdAt: kotlin.time.TimeSource.Monotonic.ValueTimeMark,
+) {
+    override fun toString(): String {
+        return "$typeName created ${createdAt.elapsedNow()} ago at $creationStackTrace"
+    }
+}
+
+/**
+ * Records the Rust objects created by the bindings until they are destroyed, to find the objects
+ * that are never destroyed or closed.
+ */
+{{ visibility() }}object UniffiObjectTracker {
+    private val lock = kotlinx.atomicfu.locks.reentrantLock()
+    private val objects = LinkedHashMap<Long, UniffiLiveObject>()
+    private var nextId = 0L
+
+    /**
+     * Called with the objects freed by the cleaner after they became unreachable, instead of by
+     * `destroy()` or `close()`. Called on the thread of the cleaner. Prints the object with
+     * `println` by default, which goes to the standard output, or to `System.out` in Logcat on
+     * Android.
+     */
+    {{ visibility() }}var leakHandler: (UniffiLiveObject) -> Unit = { liveObject ->
+        println("A Rust object was not destroyed before it became unreachable: $liveObject")
+    }
+
+    /**
+     * The objects that are alive, oldest first.
+     */
+    {{ visibility() }}fun liveObjects(): List<UniffiLiveObject> {
+        return lock.withLock { objects.values.toList() }
+    }
+
+    /**
+     * Describes the objects that are alive, oldest first.
+     */
+    {{ visibility() }}fun dump(): String {
+        val liveObjects = liveObjects()
+        return buildString {
+            append("${liveObjects.size} live Rust objects")
+            for (liveObject in liveObjects) {
+                append("\n\n")
+                append(liveObject)
+            }
+        }
+    }
+
+    internal fun register(typeName: String): Long {
+        val liveObject = UniffiLiveObject(
+            typeName,
+            Throwable().stackTraceToString(),
+            kotlin.time.TimeSource.Monotonic.markNow(),
+        )
+        return lock.withLock {
+            val id = nextId++
+            objects[id] = liveObject
+            id
+        }
+    }
+
+    // Called when the object is destroyed explicitly, before the Rust object is freed.
+    internal fun unregister(id: Long) {
+        lock.withLock { objects.remove(id) }
+    }
+
+    // Called when the Rust object is freed. The object is still registered when it was freed by
+    // the cleaner.
+    internal fun freed(id: Long) {
+        val liveObject = lock.withLock { objects.remove(id) } ?: return
+        leakHandler(liveObject)
+    }
+
+    private inline fun <T> kotlinx.atomicfu.locks.ReentrantLock.withLock(block: () -> T): T {
+        lock()
+        try {
+            return block()
+        } finally {
+            unlock()
+        }
+    }
+}
+{%- endif %}
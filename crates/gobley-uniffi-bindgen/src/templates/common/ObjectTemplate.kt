@@ -9,9 +9,12 @@
 
 {%- include "Interface.kt" %}
 {% if config.kotlin_multiplatform %}
+{%- if is_error %}
+{%- call kt::error_base_class(name, impl_class_name) %}
+{%- endif %}
 {% call kt::docstring(obj, 0) %}
 {% if (is_error) %}
-{{ visibility() }}expect open class {{ impl_class_name }} : kotlin.Exception, Disposable, {{ interface_name }} {
+{{ visibility() }}expect open class {{ impl_class_name }} : {% call kt::error_superclass(name, impl_class_name) %}, Disposable, {{ interface_name }}{% call kt::error_interfaces(name) %} {
 {% else -%}
 {{ visibility() }}expect open class {{ impl_class_name }}: Disposable, {{ interface_name }}
 {%- for t in obj.trait_impls() -%}
@@ -50,7 +53,7 @@
     override fun close()
 
     {% for meth in obj.methods() -%}
-    {%- call kt::func_decl("override", meth, 4, false) %}
+    {%- call kt::func_decl("override", meth, name, 4, false) %}
     {% endfor %}
 
     {%- for tm in obj.uniffi_traits() %}
@@ -70,7 +73,7 @@
     {%- if !obj.alternate_constructors().is_empty() -%}
     {{ visibility() }}companion object {
         {% for cons in obj.alternate_constructors() -%}
-        {%- call kt::func_decl("", cons, 8, false) %}
+        {%- call kt::func_decl("", cons, name, 8, false) %}
         {% endfor %}
     }
     {% else %}
@@ -102,6 +102,9 @@
 {%- when Type::Object { module_path, name, imp } %}
 {% include "ObjectTemplate.kt" %}
 
+{%- when Type::Timestamp %}
+{{- self.add_import_as(config.timestamp_type_name(), "UniffiInstant") }}
+
 {%- when Type::Record { name, module_path } %}
 {% include "RecordTemplate.kt" %}
 
@@ -12,7 +12,8 @@ internal suspend fun<T, F, E: kotlin.Exception> uniffiRustCallAsync(
     freeFunc: (Long) -> Unit,
     cancelFunc: (Long) -> Unit,
     liftFunc: (F) -> T,
-    errorHandler: UniffiRustCallStatusErrorHandler<E>
+    errorHandler: UniffiRustCallStatusErrorHandler<E>,
+    functionName: String? = null,
 ): T {
     return withContext(Dispatchers.IO) {
         try {
@@ -31,7 +32,7 @@ internal suspend fun<T, F, E: kotlin.Exception> uniffiRustCallAsync(
             } while (pollResult != UNIFFI_RUST_FUTURE_POLL_READY);
 
             return@withContext liftFunc(
-                uniffiRustCallWithError(errorHandler) { status -> completeFunc(rustFuture, status) }
+                uniffiRustCallWithError(errorHandler, functionName) { status -> completeFunc(rustFuture, status) }
             )
         } finally {
             freeFunc(rustFuture)
@@ -1,9 +1,10 @@
 
+// The bytes are copied once in each direction: `write` copies them straight into the memory of the
+// RustBuffer, and `read` copies them out of it into the returned array.
 {{ visibility() }}object FfiConverterByteArray: FfiConverterRustBuffer<ByteArray> {
     override fun read(buf: ByteBuffer): ByteArray {
         val len = buf.getInt()
-        val byteArr = buf.get(len)
-        return byteArr
+        return buf.get(len)
     }
     override fun allocationSize(value: ByteArray): ULong {
         return 4UL + value.size.toULong()
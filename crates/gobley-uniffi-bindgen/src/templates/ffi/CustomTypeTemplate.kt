@@ -1,5 +1,6 @@
 
-{%- match config.custom_types.get(name.as_str())  %}
+{%- let is_expect_class = config.custom_type_is_expect_class(name) %}
+{%- match config.custom_type(name, module_name) %}
 {%- when None %}
 
 {{ visibility() }}typealias {{ ffi_converter_name }} = {{ builtin|ffi_converter_name }}
@@ -8,7 +9,15 @@
 
 {%- let ffi_type_name=builtin|ffi_type|ref|ffi_type_name_by_value(ci) %}
 
-{# When the config specifies a different type name, create a typealias for it #}
+{#- The common bindings declare an expect class when each target uses a different type #}
+{%- if is_expect_class %}
+{%- match config.type_name %}
+{%- when Some(concrete_type_name) %}
+{{ visibility() }}actual typealias {{ type_name }} = {{ concrete_type_name }}
+{%- else %}
+{{ visibility() }}actual typealias {{ type_name }} = {{ builtin|type_name(ci) }}
+{%- endmatch %}
+{%- endif %}
 
 {%- match config.imports %}
 {%- when Some(imports) %}
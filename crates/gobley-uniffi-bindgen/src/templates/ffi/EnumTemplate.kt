@@ -6,6 +6,8 @@
 // and `sealed class` for the general case.
 #}
 
+{%- let has_unknown_variant = config.generate_unknown_enum_variant(e.name()) %}
+
 {%- if e.is_flat() %}
 
 {{ visibility() }}object {{ e|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
@@ -16,12 +18,21 @@
         {{ type_name }}.values()[buf.getInt() - 1]
         {%- endif %}
     } catch (e: IndexOutOfBoundsException) {
+        {%- if has_unknown_variant %}
+        {{ type_name }}.{{ config.unknown_enum_entry_name() }}
+        {%- else %}
         throw RuntimeException("invalid enum value, something is very wrong!!", e)
+        {%- endif %}
     }
 
     override fun allocationSize(value: {{ type_name }}): ULong = 4UL
 
     override fun write(value: {{ type_name }}, buf: ByteBuffer) {
+        {%- if has_unknown_variant %}
+        require(value != {{ type_name }}.{{ config.unknown_enum_entry_name() }}) {
+            "unknown variants of {{ type_name }} can't be passed to Rust"
+        }
+        {%- endif %}
         buf.putInt(value.ordinal + 1)
     }
 }
@@ -29,8 +40,29 @@
 {%- else %}
 
 {{ visibility() }}object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}>{
+    {%- if has_unknown_variant %}
+    // The fields of an unknown variant can't be skipped, so it can only be read when the enum
+    // fills the whole buffer.
+    override fun lift(value: RustBufferByValue): {{ type_name }} {
+        val byteBuf = value.asByteBuffer()!!
+        try {
+            val item = read(byteBuf, isWholeBuffer = true)
+            if (byteBuf.hasRemaining()) {
+                throw RuntimeException("junk remaining in buffer after lifting, something is very wrong!!")
+            }
+            return item
+        } finally {
+            RustBufferHelper.free(value)
+        }
+    }
+
+    override fun read(buf: ByteBuffer): {{ type_name }} = read(buf, isWholeBuffer = false)
+
+    private fun read(buf: ByteBuffer, isWholeBuffer: Boolean): {{ type_name }} {
+    {%- else %}
     override fun read(buf: ByteBuffer): {{ type_name }} {
-        return when(buf.getInt()) {
+    {%- endif %}
+        return when({% if has_unknown_variant %}val rawValue = {% endif %}buf.getInt()) {
             {%- for variant in e.variants() %}
             {{ loop.index }} -> {{ type_name }}.{{ variant|variant_type_name(ci) }}{% if variant.has_fields() %}(
                 {% for field in variant.fields() -%}
@@ -38,7 +70,18 @@
                 {% endfor -%}
             ){%- endif -%}
             {%- endfor %}
+            {%- if has_unknown_variant %}
+            else -> {
+                if (!isWholeBuffer) {
+                    throw RuntimeException("unknown variant $rawValue of {{ type_name }} inside another value, its fields can't be skipped")
+                }
+                // The enum fills the buffer, so the fields of the unknown variant are the rest of it.
+                buf.skipRemaining()
+                {{ type_name }}.Unknown(rawValue)
+            }
+            {%- else %}
             else -> throw RuntimeException("invalid enum value, something is very wrong!!")
+            {%- endif %}
         }
     }
 
@@ -54,6 +97,9 @@
             )
         }
         {%- endfor %}
+        {%- if has_unknown_variant %}
+        is {{ type_name }}.Unknown -> throw IllegalArgumentException("unknown variants of {{ type_name }} can't be passed to Rust")
+        {%- endif %}
     }
 
     override fun write(value: {{ type_name }}, buf: ByteBuffer) {
@@ -67,6 +113,9 @@
                 Unit
             }
             {%- endfor %}
+            {%- if has_unknown_variant %}
+            is {{ type_name }}.Unknown -> throw IllegalArgumentException("unknown variants of {{ type_name }} can't be passed to Rust")
+            {%- endif %}
         }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
     }
 }
@@ -12,11 +12,9 @@
     // Calculate bytes to allocate when creating a `RustBuffer`
     //
     // This must return at least as many bytes as the write() function will
-    // write. It can return more bytes than needed, for example when writing
-    // Strings we can't know the exact bytes needed until we the UTF-8
-    // encoding, so we pessimistically allocate the largest size possible (3
-    // bytes per codepoint).  Allocating extra bytes is not really a big deal
-    // because the `RustBuffer` is short-lived.
+    // write. It can return more bytes than needed, which is not really a big
+    // deal because the `RustBuffer` is short-lived. Strings return the exact
+    // length of their UTF-8 encoding.
     {{ visibility() }}fun allocationSize(value: KotlinType): ULong
 
     // Write a Kotlin type to a `ByteBuffer`
@@ -32,16 +32,17 @@ internal fun UniffiRustCallStatusByValue.isPanic(): Boolean
 // synchronize itself
 
 // Call a rust function that returns a Result<>.  Pass in the Error class companion that corresponds to the Err
-internal inline fun <U, E: kotlin.Exception> uniffiRustCallWithError(errorHandler: UniffiRustCallStatusErrorHandler<E>, crossinline callback: (UniffiRustCallStatus) -> U): U {
+// `functionName` is the name of the FFI function, reported by RustPanicException
+internal inline fun <U, E: kotlin.Exception> uniffiRustCallWithError(errorHandler: UniffiRustCallStatusErrorHandler<E>, functionName: String? = null, crossinline callback: (UniffiRustCallStatus) -> U): U {
     return UniffiRustCallStatusHelper.withReference() { status ->
         val returnValue = callback(status)
-        uniffiCheckCallStatus(errorHandler, status)
+        uniffiCheckCallStatus(errorHandler, status, functionName)
         returnValue
     }
 }
 
 // Check `status` and throw an error if the call wasn't successful
-internal fun<E: kotlin.Exception> uniffiCheckCallStatus(errorHandler: UniffiRustCallStatusErrorHandler<E>, status: UniffiRustCallStatus) {
+internal fun<E: kotlin.Exception> uniffiCheckCallStatus(errorHandler: UniffiRustCallStatusErrorHandler<E>, status: UniffiRustCallStatus, functionName: String? = null) {
     if (status.isSuccess()) {
         return
     } else if (status.isError()) {
@@ -50,16 +51,56 @@ internal fun<E: kotlin.Exception> uniffiCheckCallStatus(errorHandler: UniffiRust
         // when the rust code sees a panic, it tries to construct a rustbuffer
         // with the message.  but if that code panics, then it just sends back
         // an empty buffer.
-        if (status.errorBuf.len > 0) {
-            throw InternalException({{ Type::String.borrow()|lift_fn }}(status.errorBuf))
+        val message = if (status.errorBuf.len > 0) {
+            {{ Type::String.borrow()|lift_fn }}(status.errorBuf)
         } else {
-            throw InternalException("Rust panic")
+            "Rust panic"
         }
+        throw uniffiRustPanicException(message, functionName)
     } else {
         throw InternalException("Unknown rust call status: $status.code")
     }
 }
 
+// Let the panic handler see the exception before it's thrown
+internal fun uniffiRustPanicException(message: String, functionName: String?): RustPanicException {
+    val exception = RustPanicException(message, functionName)
+    uniffiRustPanicHandler?.invoke(exception)
+    return exception
+}
+
+// Throw an exception describing every mismatch when the loaded library doesn't match the bindings
+internal fun uniffiCheckLibraryIntegrity(
+    libraryPath: String?,
+    bindingsContractVersion: Int,
+    libraryContractVersion: Int,
+    checksumMismatches: List<UniffiChecksumMismatch>,
+) {
+    if (bindingsContractVersion == libraryContractVersion && checksumMismatches.isEmpty()) {
+        return
+    }
+    val message = buildString {
+        if (bindingsContractVersion != libraryContractVersion) {
+            append("UniFFI contract version mismatch: the bindings use version $bindingsContractVersion, ")
+            append("but the library uses version $libraryContractVersion")
+        } else {
+            append("UniFFI API checksum mismatch:")
+            for (mismatch in checksumMismatches) {
+                append("\n    ${mismatch.functionName}: expected ${mismatch.expected}, got ${mismatch.actual}")
+            }
+        }
+        append("\nLoaded library: ${libraryPath ?: "unknown"}")
+        append("\nTry cleaning and rebuilding your project")
+    }
+    throw UniffiLibraryMismatchException(
+        message,
+        libraryPath,
+        bindingsContractVersion,
+        libraryContractVersion,
+        checksumMismatches,
+    )
+}
+
 // UniffiRustCallStatusErrorHandler implementation for times when we don't expect a CALL_ERROR
 {{ visibility() }}object UniffiNullRustCallStatusErrorHandler: UniffiRustCallStatusErrorHandler<InternalException> {
     override fun lift(errorBuf: RustBufferByValue): InternalException {
@@ -69,8 +110,8 @@ internal fun<E: kotlin.Exception> uniffiCheckCallStatus(errorHandler: UniffiRust
 }
 
 // Call a rust function that returns a plain value
-internal inline fun <U> uniffiRustCall(crossinline callback: (UniffiRustCallStatus) -> U): U {
-    return uniffiRustCallWithError(UniffiNullRustCallStatusErrorHandler, callback)
+internal inline fun <U> uniffiRustCall(functionName: String? = null, crossinline callback: (UniffiRustCallStatus) -> U): U {
+    return uniffiRustCallWithError(UniffiNullRustCallStatusErrorHandler, functionName, callback)
 }
 
 internal inline fun<T> uniffiTraitInterfaceCall(
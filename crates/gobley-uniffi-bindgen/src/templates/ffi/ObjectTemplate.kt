@@ -21,9 +21,12 @@
 
 {%- macro emit_actual %}{% if config.kotlin_multiplatform %}actual {% endif %}{% endmacro -%}
 
+{%- if is_error %}
+{%- call kt::error_base_class(name, impl_class_name) %}
+{%- endif %}
 {%- call kt::docstring(obj, 0) %}
 {% if (is_error) %}
-{{ visibility() }}{% call emit_actual %}open class {{ impl_class_name }} : kotlin.Exception, Disposable, {{ interface_name }} {
+{{ visibility() }}{% call emit_actual %}open class {{ impl_class_name }} : {% call kt::error_superclass(name, impl_class_name) %}, Disposable, {{ interface_name }}{% call kt::error_interfaces(name) %} {
 {% else -%}
 {{ visibility() }}{% call emit_actual %}open class {{ impl_class_name }}: Disposable, {{ interface_name }}
 {%- for t in obj.trait_impls() -%}
@@ -33,7 +36,12 @@
 
     {{ visibility() }}constructor(pointer: Pointer) {
         this.pointer = pointer
+        {%- if config.track_object_leaks %}
+        this.uniffiTrackingId = UniffiObjectTracker.register(this::class.simpleName ?: "{{ impl_class_name }}")
+        this.cleanable = UniffiLib.CLEANER.register(this, UniffiPointerDestroyer(pointer, uniffiTrackingId))
+        {%- else %}
         this.cleanable = UniffiLib.CLEANER.register(this, UniffiPointerDestroyer(pointer))
+        {%- endif %}
     }
 
     /**
@@ -43,7 +51,12 @@
      */
     {{ visibility() }}{% call emit_actual %}constructor(noPointer: NoPointer) {
         this.pointer = null
+        {%- if config.track_object_leaks %}
+        this.uniffiTrackingId = null
+        this.cleanable = UniffiLib.CLEANER.register(this, UniffiPointerDestroyer(null, null))
+        {%- else %}
         this.cleanable = UniffiLib.CLEANER.register(this, UniffiPointerDestroyer(null))
+        {%- endif %}
     }
 
     {%- match obj.primary_constructor() %}
@@ -53,7 +66,7 @@
     {%-     else %}
     {%- call kt::docstring(cons, 4) %}
     {{ visibility() }}{% call emit_actual %}constructor({% call kt::arg_list(cons, false) -%}) : this(
-        {% call kt::to_ffi_call(cons, 8) %}
+        {% call kt::instrument_call(impl_class_name, name, false) %}{% call kt::to_ffi_call(cons, 8) %}{% call kt::end_instrument_call() %}
     )
     {%-     endif %}
     {%- when None %}
@@ -61,6 +74,10 @@
 
     protected val pointer: Pointer?
     protected val cleanable: UniffiCleaner.Cleanable
+    {%- if config.track_object_leaks %}
+    // The ID of the object in `UniffiObjectTracker`, or `null` for fake objects.
+    private val uniffiTrackingId: Long?
+    {%- endif %}
 
     private val wasDestroyed: kotlinx.atomicfu.AtomicBoolean = kotlinx.atomicfu.atomic(false)
     private val callCounter: kotlinx.atomicfu.AtomicLong = kotlinx.atomicfu.atomic(1L)
@@ -80,6 +97,9 @@
         // Only allow a single call to this method.
         // TODO: maybe we should log a warning if called more than once?
         if (this.wasDestroyed.compareAndSet(false, true)) {
+            {%- if config.track_object_leaks %}
+            uniffiTrackingId?.let { UniffiObjectTracker.unregister(it) }
+            {%- endif %}
             // This decrement always matches the initial count of 1 given at creation time.
             if (this.callCounter.decrementAndGet() == 0L) {
                 cleanable.clean()
@@ -116,13 +136,21 @@
 
     // Use a static inner class instead of a closure so as not to accidentally
     // capture `this` as part of the cleanable's action.
-    private class UniffiPointerDestroyer(private val pointer: Pointer?) : Disposable {
+    private class UniffiPointerDestroyer(
+        private val pointer: Pointer?,
+        {%- if config.track_object_leaks %}
+        private val trackingId: Long?,
+        {%- endif %}
+    ) : Disposable {
         override fun destroy() {
             pointer?.let { ptr ->
                 uniffiRustCall { status ->
                     UniffiLib.{{ obj.ffi_object_free().name() }}(ptr, status)
                 }
             }
+            {%- if config.track_object_leaks %}
+            trackingId?.let { UniffiObjectTracker.freed(it) }
+            {%- endif %}
         }
     }
 
@@ -133,25 +161,33 @@
     }
 
     {% for meth in obj.methods() -%}
-    {%- call kt::func_decl_with_body(actual_override, meth, 4) -%}
+    {%- call kt::func_decl_with_body(actual_override, meth, name, 4) -%}
     {% endfor %}
 
+    {%- if completable_futures %}
+    {%- for meth in obj.methods() %}
+    {%- if meth.is_async() %}
+    {% call kt::completable_future(meth, 4) %}
+    {%- endif %}
+    {%- endfor %}
+    {%- endif %}
+
     {%- for tm in obj.uniffi_traits() %}
     {%-     match tm %}
     {%         when UniffiTrait::Display { fmt } %}
     {% call emit_actual %}override fun toString(): String {
-        return {{ fmt.return_type().unwrap()|lift_fn }}({% call kt::to_ffi_call(fmt, 8) %})
+        return {% call kt::instrument_call("toString", name, false) %}{{ fmt.return_type().unwrap()|lift_fn }}({% call kt::to_ffi_call(fmt, 8) %}){% call kt::end_instrument_call() %}
     }
     {%         when UniffiTrait::Eq { eq, ne } %}
     {# only equals used #}
     {% call emit_actual %}override fun equals(other: Any?): Boolean {
         if (this === other) return true
         if (other !is {{ impl_class_name}}) return false
-        return {{ eq.return_type().unwrap()|lift_fn }}({% call kt::to_ffi_call(eq, 8) %})
+        return {% call kt::instrument_call("equals", name, false) %}{{ eq.return_type().unwrap()|lift_fn }}({% call kt::to_ffi_call(eq, 8) %}){% call kt::end_instrument_call() %}
     }
     {%         when UniffiTrait::Hash { hash } %}
     {% call emit_actual %}override fun hashCode(): Int {
-        return {{ hash.return_type().unwrap()|lift_fn }}({%- call kt::to_ffi_call(hash, 8) %}).toInt()
+        return {% call kt::instrument_call("hashCode", name, false) %}{{ hash.return_type().unwrap()|lift_fn }}({%- call kt::to_ffi_call(hash, 8) %}).toInt(){% call kt::end_instrument_call() %}
     }
     {%-         else %}
     {%-     endmatch %}
@@ -161,7 +197,7 @@
     {% if !obj.alternate_constructors().is_empty() -%}
     {{ visibility() }}{% call emit_actual %}companion object {
         {% for cons in obj.alternate_constructors() -%}
-        {%- call kt::func_decl_with_body(actual, cons, 8) %}
+        {%- call kt::func_decl_with_body(actual, cons, name, 8) %}
         {% endfor %}
     }
     {% else %}
@@ -5,16 +5,66 @@
 }
 
 internal object RustBufferHelper {
-    internal fun allocValue(size: ULong = 0UL): RustBufferByValue = uniffiRustCall { status ->
-        // Note: need to convert the size to a `Long` value to make this work with JVM.
-        UniffiLib.{{ ci.ffi_rustbuffer_alloc().name() }}(size.toLong(), status)
-    }.also {
-        if(it.data == null) {
-            throw RuntimeException("RustBuffer.alloc() returned null data pointer (size=${size})")
+    internal fun allocValue(size: ULong = 0UL): RustBufferByValue {
+        {%- if config.pool_rust_buffers %}
+        uniffiRustBufferPool().take(size)?.let { return it }
+        {%- endif %}
+        return uniffiRustCall { status ->
+            // Note: need to convert the size to a `Long` value to make this work with JVM.
+            UniffiLib.{{ ci.ffi_rustbuffer_alloc().name() }}(size.toLong(), status)
+        }.also {
+            if(it.data == null) {
+                throw RuntimeException("RustBuffer.alloc() returned null data pointer (size=${size})")
+            }
         }
     }
 
-    internal fun free(buf: RustBufferByValue) = uniffiRustCall { status ->
+    internal fun free(buf: RustBufferByValue) {
+        {%- if config.pool_rust_buffers %}
+        if (uniffiRustBufferPool().offer(buf)) return
+        {%- endif %}
+        destroy(buf)
+    }
+
+    // Frees `buf` in Rust, bypassing the pool.
+    internal fun destroy(buf: RustBufferByValue) = uniffiRustCall { status ->
         UniffiLib.{{ ci.ffi_rustbuffer_free().name() }}(buf, status)
     }
 }
+{%- if config.pool_rust_buffers %}
+
+// Small buffers returned by Rust and freed by Kotlin are kept per thread, and passed back to Rust
+// as arguments instead of allocating new ones. Rust takes ownership of argument buffers and frees
+// them using their `capacity`, so a buffer leaves the pool for good once it is handed to Rust.
+internal class UniffiRustBufferPool {
+    internal val buffers = ArrayList<RustBufferByValue>(MAX_BUFFERS)
+
+    // Takes the smallest buffer large enough for `size` bytes, keeping the larger ones for larger
+    // arguments. Rust doesn't allocate empty buffers, so they don't use up a pooled one.
+    internal fun take(size: ULong): RustBufferByValue? {
+        if (size == 0UL) {
+            return null
+        }
+        val index = buffers.indices
+            .filter { buffers[it].capacity.toULong() >= size }
+            .minByOrNull { buffers[it].capacity }
+            ?: return null
+        val buf = buffers.removeAt(index)
+        return RustBufferByValue(capacity = buf.capacity, len = size.toLong(), data = buf.data)
+    }
+
+    internal fun offer(buf: RustBufferByValue): Boolean {
+        val capacity = buf.capacity
+        if (buf.data == null || capacity <= 0L || capacity > MAX_CAPACITY || buffers.size >= MAX_BUFFERS) {
+            return false
+        }
+        buffers.add(buf)
+        return true
+    }
+
+    internal companion object {
+        const val MAX_BUFFERS = 4
+        const val MAX_CAPACITY = 4096L
+    }
+}
+{%- endif %}
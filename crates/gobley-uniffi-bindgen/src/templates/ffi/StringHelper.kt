@@ -1,3 +1,22 @@
+// The length of `value` encoded in UTF-8. Like the encoders, throws on unpaired surrogates.
+internal fun uniffiUtf8Length(value: String): Int {
+    var length = 0
+    var index = 0
+    while (index < value.length) {
+        val char = value[index++]
+        length += when {
+            char.code < 0x80 -> 1
+            char.code < 0x800 -> 2
+            char.isHighSurrogate() && index < value.length && value[index].isLowSurrogate() -> {
+                index++
+                4
+            }
+            char.isSurrogate() -> throw CharacterCodingException()
+            else -> 3
+        }
+    }
+    return length
+}
 
 {{ visibility() }}object FfiConverterString: FfiConverter<String, RustBufferByValue> {
     // Note: we don't inherit from FfiConverterRustBuffer, because we use a
@@ -6,8 +25,7 @@
     override fun lift(value: RustBufferByValue): String {
         try {
             {% call kt::check_rust_buffer_length("value.len") %}
-            val byteArr =  value.asByteBuffer()!!.get(value.len.toInt())
-            return byteArr.decodeToString()
+            return value.asByteBuffer()!!.getString(value.len.toInt())
         } finally {
             RustBufferHelper.free(value)
         }
@@ -15,31 +33,30 @@
 
     override fun read(buf: ByteBuffer): String {
         val len = buf.getInt()
-        val byteArr = buf.get(len)
-        return byteArr.decodeToString()
+        return buf.getString(len)
     }
 
+    // The string is encoded directly into the memory of the RustBuffer.
     override fun lower(value: String): RustBufferByValue {
-        // TODO: prevent allocating a new byte array here
-        val encoded = value.encodeToByteArray(throwOnInvalidSequence = true)
-        return RustBufferHelper.allocValue(encoded.size.toULong()).apply {
-            asByteBuffer()!!.put(encoded)
+        val length = uniffiUtf8Length(value)
+        return RustBufferHelper.allocValue(length.toULong()).apply {
+            if (length > 0) {
+                asByteBuffer()!!.putString(value)
+            }
         }
     }
 
-    // We aren't sure exactly how many bytes our string will be once it's UTF-8
-    // encoded.  Allocate 3 bytes per UTF-16 code unit which will always be
-    // enough.
     override fun allocationSize(value: String): ULong {
         val sizeForLength = 4UL
-        val sizeForString = value.length.toULong() * 3UL
+        val sizeForString = uniffiUtf8Length(value).toULong()
         return sizeForLength + sizeForString
     }
 
+    // `allocationSize` already measured the string, so the length is filled in after encoding it.
     override fun write(value: String, buf: ByteBuffer) {
-        // TODO: prevent allocating a new byte array here
-        val encoded = value.encodeToByteArray(throwOnInvalidSequence = true)
-        buf.putInt(encoded.size)
-        buf.put(encoded)
+        val lengthPosition = buf.position()
+        buf.putInt(0)
+        buf.putString(value)
+        buf.putInt(lengthPosition, buf.position() - lengthPosition - 4)
     }
 }
@@ -1,10 +1,11 @@
+{{- self.add_import_as(config.timestamp_type_name(), "UniffiInstant") }}
 
-{{ visibility() }}object FfiConverterTimestamp: FfiConverterRustBuffer<kotlinx.datetime.Instant> {
-    override fun read(buf: ByteBuffer): kotlinx.datetime.Instant {
+{{ visibility() }}object FfiConverterTimestamp: FfiConverterRustBuffer<UniffiInstant> {
+    override fun read(buf: ByteBuffer): UniffiInstant {
         val seconds = buf.getLong()
         val nanoseconds = buf.getInt()
 
-        val instant = kotlinx.datetime.Instant.fromEpochSeconds(
+        val instant = UniffiInstant.fromEpochSeconds(
             seconds,
             // UniFFI negates nanoseconds when epochSeconds is negative. See #37 for details.
             if (seconds >= 0) nanoseconds else -nanoseconds,
@@ -16,9 +17,9 @@
     }
 
     // 8 bytes for seconds, 4 bytes for nanoseconds
-    override fun allocationSize(value: kotlinx.datetime.Instant): ULong = 12UL
+    override fun allocationSize(value: UniffiInstant): ULong = 12UL
 
-    override fun write(value: kotlinx.datetime.Instant, buf: ByteBuffer) {
+    override fun write(value: UniffiInstant, buf: ByteBuffer) {
         if (value.nanosecondsOfSecond < 0) {
             throw IllegalArgumentException("Invalid timestamp, nano value must be non-negative")
         }
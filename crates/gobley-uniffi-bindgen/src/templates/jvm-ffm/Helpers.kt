@@ -0,0 +1,59 @@
+{% include "ffi/Helpers.kt" %}
+
+// `RustCallStatus` is `{ int8_t code; RustBuffer errorBuf; }`.
+internal val UniffiRustCallStatusLayout: java.lang.foreign.StructLayout = uniffiStructLayout(
+    java.lang.foreign.ValueLayout.JAVA_BYTE.withName("code"),
+    UniffiRustBufferLayout.withName("errorBuf"),
+)
+private val UNIFFI_RUST_CALL_STATUS_ERROR_BUF_OFFSET = UniffiRustCallStatusLayout.byteOffset(
+    java.lang.foreign.MemoryLayout.PathElement.groupElement("errorBuf"),
+)
+
+// A `RustCallStatus` in native memory, passed to Rust functions and callbacks by pointer.
+internal class UniffiRustCallStatus(internal val uniffiSegment: java.lang.foreign.MemorySegment) {
+    var code: Byte
+        get() = uniffiSegment.get(java.lang.foreign.ValueLayout.JAVA_BYTE, 0L)
+        set(value) = uniffiSegment.set(java.lang.foreign.ValueLayout.JAVA_BYTE, 0L, value)
+
+    var errorBuf: RustBufferByValue
+        get() = uniffiReadRustBufferByValue(uniffiSegment, UNIFFI_RUST_CALL_STATUS_ERROR_BUF_OFFSET)
+        set(value) = value.uniffiWriteTo(uniffiSegment, UNIFFI_RUST_CALL_STATUS_ERROR_BUF_OFFSET)
+}
+
+internal class UniffiRustCallStatusByValue(
+    var code: Byte,
+    var errorBuf: RustBufferByValue,
+) {
+    internal constructor(): this(0.toByte(), RustBufferByValue())
+}
+
+internal fun UniffiRustCallStatusByValue.uniffiWriteTo(segment: java.lang.foreign.MemorySegment, offset: Long = 0L) {
+    segment.set(java.lang.foreign.ValueLayout.JAVA_BYTE, offset, code)
+    errorBuf.uniffiWriteTo(segment, offset + UNIFFI_RUST_CALL_STATUS_ERROR_BUF_OFFSET)
+}
+
+internal fun UniffiRustCallStatusByValue.uniffiToSegment(allocator: java.lang.foreign.SegmentAllocator): java.lang.foreign.MemorySegment =
+    allocator.allocate(UniffiRustCallStatusLayout).also { uniffiWriteTo(it) }
+
+internal fun uniffiReadUniffiRustCallStatusByValue(segment: java.lang.foreign.MemorySegment, offset: Long = 0L) =
+    UniffiRustCallStatusByValue(
+        code = segment.get(java.lang.foreign.ValueLayout.JAVA_BYTE, offset),
+        errorBuf = uniffiReadRustBufferByValue(segment, offset + UNIFFI_RUST_CALL_STATUS_ERROR_BUF_OFFSET),
+    )
+
+internal object UniffiRustCallStatusHelper {
+    internal fun allocValue() = UniffiRustCallStatusByValue()
+    internal fun <U> withReference(block: (UniffiRustCallStatus) -> U): U {
+        // The status only lives for the duration of the call, on the calling thread.
+        return java.lang.foreign.Arena.ofConfined().use { arena ->
+            block(UniffiRustCallStatus(arena.allocate(UniffiRustCallStatusLayout)))
+        }
+    }
+}
+
+// Exceptions can't be thrown from upcalls back into Rust, so they're reported to the handler of
+// uncaught exceptions of the thread instead, like JNA does for its callbacks.
+internal fun uniffiReportUpcallException(e: Throwable) {
+    val thread = Thread.currentThread()
+    thread.uncaughtExceptionHandler?.uncaughtException(thread, e)
+}
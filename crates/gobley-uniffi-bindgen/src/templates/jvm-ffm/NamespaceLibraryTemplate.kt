@@ -0,0 +1,477 @@
+{#
+// The `FunctionDescriptor` of an FFI function or callback.
+#}
+{%- macro ffm_descriptor(func, indent) -%}
+java.lang.foreign.FunctionDescriptor.
+{%- match func.return_type() -%}
+{%- when Some(return_type) -%}
+of(
+{{ " "|repeat(indent) }}{{ return_type|ffm_layout }},
+{%- when None -%}
+ofVoid(
+{%- endmatch -%}
+{%- for arg in func.arguments() %}
+{{ " "|repeat(indent) }}{{ arg.type_().borrow()|ffm_layout }},
+{%- endfor -%}
+{%- if func.has_rust_call_status_arg() %}
+{{ " "|repeat(indent) }}java.lang.foreign.ValueLayout.ADDRESS,
+{%- endif %}
+{{ " "|repeat(indent - 4) }})
+{%- endmacro %}
+
+{#
+// Calls the method handle `handle` with the arguments of `func`, storing the carrier of the
+// result in `uniffiResult`. `address` is the function pointer of callbacks.
+#}
+{%- macro ffm_invoke(func, handle, address, indent) %}
+{{ " "|repeat(indent) }}{% if func.return_type().is_some() %}val uniffiResult = {% endif %}{{ handle }}.invoke(
+{%- if !address.is_empty() %}
+{{ " "|repeat(indent + 4) }}{{ address }},
+{%- endif %}
+{%- if let Some(return_type) = func.return_type() %}
+{%- if return_type|ffm_is_by_value_struct %}
+{{ " "|repeat(indent + 4) }}uniffiArena,
+{%- endif %}
+{%- endif %}
+{%- for arg in func.arguments() %}
+{%- let arg_name = arg.name()|var_name %}
+{{ " "|repeat(indent + 4) }}{{ arg.type_().borrow()|ffm_lower(arg_name, ci) }},
+{%- endfor %}
+{%- if func.has_rust_call_status_arg() %}
+{{ " "|repeat(indent + 4) }}uniffiCallStatus.uniffiSegment,
+{%- endif %}
+{{ " "|repeat(indent) }}){% match func.return_type() %}{% when Some(return_type) %} as {{ return_type|ffm_carrier }}{% when None %}{% endmatch %}
+{%- endmacro %}
+
+{#
+// The body of a Kotlin function calling `func` through `handle`. Structs passed by value are
+// allocated in an arena freed when the call returns.
+#}
+{%- macro ffm_downcall(func, handle, address, indent) %}
+{%- let needs_arena = func.arguments()|ffm_needs_arena(func.return_type()) %}
+{%- if needs_arena %}
+{{ " "|repeat(indent) }}{% if func.return_type().is_some() %}return {% endif %}java.lang.foreign.Arena.ofConfined().use { uniffiArena ->
+{%- call ffm_invoke(func, handle, address, indent + 4) %}
+{%- if let Some(return_type) = func.return_type() %}
+{{ " "|repeat(indent + 4) }}{{ return_type|ffm_lift("uniffiResult", ci) }}
+{%- endif %}
+{{ " "|repeat(indent) }}}
+{%- else %}
+{%- call ffm_invoke(func, handle, address, indent) %}
+{%- if let Some(return_type) = func.return_type() %}
+{{ " "|repeat(indent) }}return {{ return_type|ffm_lift("uniffiResult", ci) }}
+{%- endif %}
+{%- endif %}
+{%- endmacro %}
+
+// Define FFI callback types
+
+// The upcall stubs of the Kotlin callbacks passed to Rust. Callbacks are singletons, so their
+// stubs are created once and live as long as the program.
+private val uniffiUpcallStubs = java.util.concurrent.ConcurrentHashMap<Any, java.lang.foreign.MemorySegment>()
+
+{%- for def in ci.ffi_definitions() %}
+{%- match def %}
+{%- when FfiDefinition::CallbackFunction(callback) %}
+{%- let callback_name = callback.name()|ffi_callback_name %}
+internal interface {{ callback_name }} {
+    public fun callback(
+        {%- for arg in callback.arguments() -%}
+        {{ arg.name().borrow()|var_name }}: {{ arg.type_().borrow()|ffi_type_name_by_value(ci) }},
+        {%- endfor -%}
+        {%- if callback.has_rust_call_status_arg() -%}
+        uniffiCallStatus: UniffiRustCallStatus,
+        {%- endif -%}
+    )
+    {%- if let Some(return_type) = callback.return_type() -%}
+    : {{ return_type|ffi_type_name_by_value(ci) }}
+    {%- endif %}
+}
+
+// Calls a function pointer received from Rust.
+internal class {{ callback_name }}Native(internal val uniffiAddress: java.lang.foreign.MemorySegment) : {{ callback_name }} {
+    override fun callback(
+        {%- call kt::arg_list_ffi_decl(callback, 8) %}
+    )
+    {%- if let Some(return_type) = callback.return_type() -%}
+    : {{ return_type|ffi_type_name_by_value(ci) }}
+    {%- endif %} {
+        {%- call ffm_downcall(callback, "DOWNCALL", "uniffiAddress", 8) %}
+    }
+
+    internal companion object {
+        val DESCRIPTOR: java.lang.foreign.FunctionDescriptor = {% call ffm_descriptor(callback, 12) %}
+        private val DOWNCALL: java.lang.invoke.MethodHandle =
+            java.lang.foreign.Linker.nativeLinker().downcallHandle(DESCRIPTOR)
+        val UPCALL: java.lang.invoke.MethodHandle = java.lang.invoke.MethodHandles.lookup().findVirtual(
+            {{ callback_name }}Upcall::class.java,
+            "callback",
+            DESCRIPTOR.toMethodType(),
+        )
+    }
+}
+
+// Receives the calls of Rust to a Kotlin callback, converting the carriers of the arguments.
+internal class {{ callback_name }}Upcall(private val uniffiTarget: {{ callback_name }}) {
+    fun callback(
+        {%- for arg in callback.arguments() %}
+        {{ arg.name()|var_name }}: {{ arg.type_().borrow()|ffm_carrier }},
+        {%- endfor %}
+        {%- if callback.has_rust_call_status_arg() %}
+        uniffiCallStatus: java.lang.foreign.MemorySegment,
+        {%- endif %}
+    )
+    {%- if let Some(return_type) = callback.return_type() -%}
+    : {{ return_type|ffm_carrier }}
+    {%- endif %} {
+        try {
+            {% if callback.return_type().is_some() %}val uniffiResult = {% endif %}uniffiTarget.callback(
+                {%- for arg in callback.arguments() %}
+                {%- let arg_name = arg.name()|var_name %}
+                {{ arg.type_().borrow()|ffm_lift(arg_name, ci) }},
+                {%- endfor %}
+                {%- if callback.has_rust_call_status_arg() %}
+                UniffiRustCallStatus(uniffiCallStatus.reinterpret(UniffiRustCallStatusLayout.byteSize())),
+                {%- endif %}
+            )
+            {%- if let Some(return_type) = callback.return_type() %}
+            {%- if return_type|ffm_is_by_value_struct %}
+            // Rust copies the returned struct before the arena can be collected.
+            val uniffiArena = java.lang.foreign.Arena.ofAuto()
+            {%- endif %}
+            return {{ return_type|ffm_lower("uniffiResult", ci) }}
+            {%- endif %}
+        } catch (e: Throwable) {
+            uniffiReportUpcallException(e)
+            {%- if let Some(return_type) = callback.return_type() %}
+            return {{ return_type|ffm_default_carrier_value }}
+            {%- endif %}
+        }
+    }
+}
+
+internal fun {{ callback_name }}.uniffiUpcallStub(): java.lang.foreign.MemorySegment {
+    if (this is {{ callback_name }}Native) {
+        return uniffiAddress
+    }
+    return uniffiUpcallStubs.computeIfAbsent(this) {
+        java.lang.foreign.Linker.nativeLinker().upcallStub(
+            {{ callback_name }}Native.UPCALL.bindTo({{ callback_name }}Upcall(this)),
+            {{ callback_name }}Native.DESCRIPTOR,
+            java.lang.foreign.Arena.global(),
+        )
+    }
+}
+{%- when FfiDefinition::Struct(ffi_struct) %}
+{%- let struct_name = ffi_struct.name()|ffi_struct_name %}
+
+// A struct in native memory, passed to Rust by pointer. The structs created in Kotlin, like the
+// vtables of callback interfaces, must outlive the calls using them, so they're never freed.
+internal class {{ struct_name }}(internal val uniffiSegment: java.lang.foreign.MemorySegment) {
+    internal constructor(
+        {%- for field in ffi_struct.fields() %}
+        {{ field.name()|var_name }}: {{ field.type_().borrow()|ffi_type_name_for_ffi_struct(ci) }},
+        {%- endfor %}
+    ) : this(java.lang.foreign.Arena.global().allocate(LAYOUT)) {
+        {%- for field in ffi_struct.fields() %}
+        this.{{ field.name()|var_name }} = {{ field.name()|var_name }}
+        {%- endfor %}
+    }
+    {%- for field in ffi_struct.fields() %}
+    {%- let offset_name = "{}Offset"|format(field.name()|var_name_raw) %}
+
+    var {{ field.name()|var_name }}: {{ field.type_().borrow()|ffi_type_name_for_ffi_struct(ci) }}
+        get() = {{ field.type_().borrow()|ffm_read("uniffiSegment", offset_name, ci) }}
+        set(value) {
+            {{ field.type_().borrow()|ffm_write("uniffiSegment", offset_name, "value", ci) }}
+        }
+    {%- endfor %}
+
+    internal companion object {
+        val LAYOUT: java.lang.foreign.StructLayout = uniffiStructLayout(
+            {%- for field in ffi_struct.fields() %}
+            {{ field.type_().borrow()|ffm_layout }}.withName("{{ field.name() }}"),
+            {%- endfor %}
+        )
+        {%- for field in ffi_struct.fields() %}
+        val {{ field.name()|var_name_raw }}Offset: Long =
+            LAYOUT.byteOffset(java.lang.foreign.MemoryLayout.PathElement.groupElement("{{ field.name() }}"))
+        {%- endfor %}
+    }
+}
+
+// The struct passed by value, copied to and from native memory around each call.
+internal class {{ struct_name }}UniffiByValue(
+    {%- for field in ffi_struct.fields() %}
+    val {{ field.name()|var_name }}: {{ field.type_().borrow()|ffi_type_name_for_ffi_struct(ci) }},
+    {%- endfor %}
+) {
+    // By-value structs are written to native memory when they're passed to Rust, see
+    // `uniffiWriteTo`. Kept for the code shared with the JNA bindings.
+    fun write() {}
+}
+
+// The parameters are prefixed, since the fields are named after the arguments of callbacks.
+internal fun {{ struct_name }}UniffiByValue.uniffiWriteTo(uniffiSegment: java.lang.foreign.MemorySegment, uniffiOffset: Long = 0L) {
+    {%- for field in ffi_struct.fields() %}
+    {%- let offset_expr = "uniffiOffset + {}.{}Offset"|format(struct_name, field.name()|var_name_raw) %}
+    {%- let field_name = field.name()|var_name %}
+    {{ field.type_().borrow()|ffm_write("uniffiSegment", offset_expr, field_name, ci) }}
+    {%- endfor %}
+}
+
+internal fun {{ struct_name }}UniffiByValue.uniffiToSegment(allocator: java.lang.foreign.SegmentAllocator): java.lang.foreign.MemorySegment =
+    allocator.allocate({{ struct_name }}.LAYOUT).also { uniffiWriteTo(it) }
+
+internal fun uniffiRead{{ struct_name }}UniffiByValue(segment: java.lang.foreign.MemorySegment, offset: Long = 0L) =
+    {{ struct_name }}UniffiByValue(
+        {%- for field in ffi_struct.fields() %}
+        {%- let offset_expr = "offset + {}.{}Offset"|format(struct_name, field.name()|var_name_raw) %}
+        {{ field.type_().borrow()|ffm_read("segment", offset_expr, ci) }},
+        {%- endfor %}
+    )
+
+internal fun {{ struct_name }}.uniffiSetValue(other: {{ struct_name }}) {
+    {%- for field in ffi_struct.fields() %}
+    {{ field.name()|var_name }} = other.{{ field.name()|var_name }}
+    {%- endfor %}
+}
+internal fun {{ struct_name }}.uniffiSetValue(other: {{ struct_name }}UniffiByValue) {
+    {%- for field in ffi_struct.fields() %}
+    {{ field.name()|var_name }} = other.{{ field.name()|var_name }}
+    {%- endfor %}
+}
+
+{%- when FfiDefinition::Function(_) %}
+{# functions are handled below #}
+{%- endmatch %}
+{%- endfor %}
+
+/**
+ * Chooses the dynamic library loaded by the bindings, e.g. to load it from an application data
+ * directory. Receives the namespace of the component and the default library name, and returns the
+ * name or the absolute path of the library, or `null` to use the default. Must be set before the
+ * bindings are used for the first time.
+ */
+{{ visibility() }}var uniffiLibraryLoader: ((componentName: String, libraryName: String) -> String?)? = null
+
+private var uniffiLibraryName: String? = null
+
+// Checked in order: `uniffiLibraryLoader`, the `uniffi.component.<namespace>.libraryOverride`
+// system property, the `UNIFFI_COMPONENT_<NAMESPACE>_LIBRARY_OVERRIDE` environment variable, and
+// the `cdylib_name`, which is looked up like JNA does, see `uniffiFindLibraryFile`.
+@Synchronized
+private fun findLibraryName(componentName: String): String {
+    uniffiLibraryName?.let { return it }
+    val defaultLibraryName = "{{ config.cdylib_name() }}"
+    val name = uniffiLibraryLoader?.invoke(componentName, defaultLibraryName)
+        ?: System.getProperty("uniffi.component.$componentName.libraryOverride")
+        ?: System.getenv("UNIFFI_COMPONENT_${componentName.uppercase()}_LIBRARY_OVERRIDE")
+        ?: defaultLibraryName
+    uniffiLibraryName = name
+    return name
+}
+
+/**
+ * Extracts the dynamic library `libraryName` bundled in a JAR file to a temporary directory, keeping
+ * the file name, and returns its absolute path. Returns the path of the library itself when it's
+ * not in a JAR file, or `null` when it's not on the class path. Can be used in
+ * [uniffiLibraryLoader].
+ */
+{{ visibility() }}fun uniffiExtractLibraryFromClassPath(libraryName: String): String? {
+    val extractionDestination = java.nio.file.Files.createTempDirectory("gobley-ffm").toFile().apply {
+        deleteOnExit()
+    }
+    val classLoader = UniffiLib::class.java.classLoader!!
+    return findLibraryInClassPath(libraryName, classLoader, extractionDestination)?.absolutePath
+}
+
+// The directory of the libraries of the current platform on the class path, named like the
+// resource prefixes of JNA, e.g. `linux-x86-64` or `darwin-aarch64`.
+private fun uniffiResourcePrefix(): String {
+    val osName = System.getProperty("os.name").lowercase()
+    val os = when {
+        osName.startsWith("windows") -> "win32"
+        osName.startsWith("mac") || osName.startsWith("darwin") -> "darwin"
+        else -> osName.substringBefore(' ')
+    }
+    val arch = when (val osArch = System.getProperty("os.arch").lowercase()) {
+        "x86_64", "amd64" -> "x86-64"
+        "i386", "i486", "i586", "i686" -> "x86"
+        else -> osArch
+    }
+    return "$os-$arch"
+}
+
+internal fun findLibraryInClassPath(
+    library: String,
+    classLoader: ClassLoader,
+    extractionDestination: java.io.File,
+): java.io.File? {
+    val libraryName = System.mapLibraryName(library)
+    val resourcePath = "${uniffiResourcePrefix()}/$libraryName"
+    var url = classLoader.getResource(resourcePath)
+    if (url == null && resourcePath.startsWith("darwin-")) {
+        url = classLoader.getResource("darwin/$libraryName")
+    }
+    if (url == null) {
+        url = classLoader.getResource(libraryName)
+    }
+    if (url == null) {
+        return null
+    }
+    if (url.protocol.equals("file", ignoreCase = true)) {
+        val file = try {
+            java.io.File(url.toURI())
+        } catch (_: java.net.URISyntaxException) {
+            java.io.File(url.path)
+        }
+        return file.takeIf { it.exists() }
+    }
+    val destination = extractionDestination.resolve(resourcePath).apply {
+        parentFile?.mkdirs()
+        deleteOnExit()
+    }
+    url.openStream().use { inputStream ->
+        destination.outputStream().use { outputStream ->
+            inputStream.copyTo(outputStream)
+        }
+    }
+    return destination
+}
+
+// Finds the file of the library `libraryName` like JNA: an absolute path is used as is, then the
+// library is looked up in `java.library.path` and on the class path. Otherwise, returns the file
+// name of the library for the dynamic linker to find it in the system paths.
+private fun uniffiFindLibraryFile(libraryName: String): String {
+    if (java.io.File(libraryName).isAbsolute) {
+        return libraryName
+    }
+    val fileName = System.mapLibraryName(libraryName)
+    for (directory in System.getProperty("java.library.path").orEmpty().split(java.io.File.pathSeparator)) {
+        if (directory.isEmpty()) {
+            continue
+        }
+        val file = java.io.File(directory, fileName)
+        if (file.isFile) {
+            return file.absolutePath
+        }
+    }
+    return uniffiExtractLibraryFromClassPath(libraryName) ?: fileName
+}
+
+// Loads the library at `path`, or by its file name from the system paths, for the lifetime of the
+// program.
+private fun uniffiLibraryLookup(path: String): java.lang.foreign.SymbolLookup = try {
+    if (java.io.File(path).isAbsolute) {
+        java.lang.foreign.SymbolLookup.libraryLookup(java.nio.file.Path.of(path), java.lang.foreign.Arena.global())
+    } else {
+        java.lang.foreign.SymbolLookup.libraryLookup(path, java.lang.foreign.Arena.global())
+    }
+} catch (e: IllegalArgumentException) {
+    throw UnsatisfiedLinkError("Unable to load library '$path': ${e.message}")
+}
+
+{%- let dynamic_library_dependencies = config.dynamic_library_dependencies(module_name) %}
+{%- if !dynamic_library_dependencies.is_empty() %}
+
+// Loads the dynamic libraries the main Rust library depends on before the main library, so that
+// the dynamic linker finds them already loaded, even when they're extracted from a JAR file.
+@Suppress("SameParameterValue")
+private fun loadDynamicLibraryDependencies(vararg dependencies: String) {
+    for (dependency in dependencies) {
+        uniffiLibraryLookup(uniffiFindLibraryFile(dependency))
+    }
+}
+{%- endif %}
+
+// The extern-C FFI functions, called through method handles of the Foreign Function & Memory API.
+// This is an implementation detail which will be called internally by the public API.
+internal object UniffiLib {
+    private val uniffiLibraryPath: String
+    private val uniffiLibrary: java.lang.foreign.SymbolLookup
+
+    init {
+        {%- if !dynamic_library_dependencies.is_empty() %}
+        loadDynamicLibraryDependencies(
+            {%- for dynamic_library in dynamic_library_dependencies %}
+            "{{ dynamic_library }}",
+            {%- endfor %}
+        )
+        {%- endif %}
+        uniffiLibraryPath = uniffiFindLibraryFile(findLibraryName("{{ ci.namespace() }}"))
+        uniffiLibrary = uniffiLibraryLookup(uniffiLibraryPath)
+    }
+
+    {%- if ci.contains_object_types() %}
+    // The Cleaner for the whole library
+    internal val CLEANER: UniffiCleaner by lazy {
+        UniffiCleaner.create()
+    }
+    {%- endif %}
+
+    private fun uniffiDowncallHandle(
+        name: String,
+        descriptor: java.lang.foreign.FunctionDescriptor,
+    ): java.lang.invoke.MethodHandle {
+        val address = uniffiLibrary.find(name).orElseThrow {
+            UnsatisfiedLinkError("Error looking up function '$name' in $uniffiLibraryPath")
+        }
+        return java.lang.foreign.Linker.nativeLinker().downcallHandle(address, descriptor)
+    }
+
+    // The method handle of each function is looked up on first use, and kept in a static final
+    // field of its own object so that the JIT compiler treats it as a constant.
+    {%- for func in ci.iter_ffi_function_definitions() %}
+
+    private object {{ func.name() }}Handle {
+        @JvmField
+        val handle: java.lang.invoke.MethodHandle = uniffiDowncallHandle(
+            "{{ func.name() }}",
+            {% call ffm_descriptor(func, 16) %},
+        )
+    }
+
+    fun {{ func.name() }}(
+        {%- call kt::arg_list_ffi_decl(func, 8) %}
+    ): {% match func.return_type() %}{% when Some(return_type) %}{{ return_type.borrow()|ffi_type_name_by_value(ci) }}{% when None %}Unit{% endmatch %} {
+        {%- call ffm_downcall(func, "{}Handle.handle"|format(func.name()), "", 8) %}
+    }
+    {%- endfor %}
+
+    init {
+        uniffiCheckContractApiVersionAndChecksums()
+        {%- for init_fn in self.initialization_fns(ci) %}
+        {{ init_fn }}
+        {%- endfor %}
+    }
+
+    private fun uniffiCheckContractApiVersionAndChecksums() {
+        // Get the bindings contract version from our ComponentInterface
+        val bindingsContractVersion = {{ ci.uniffi_contract_version() }}
+        // Get the scaffolding contract version by calling the into the dylib
+        val scaffoldingContractVersion = {{ ci.ffi_uniffi_contract_version().name() }}()
+        val checksumMismatches = mutableListOf<UniffiChecksumMismatch>()
+        {%- if !config.omit_checksums %}
+        if (bindingsContractVersion == scaffoldingContractVersion) {
+            {%- for (name, expected_checksum) in ci.iter_checksums() %}
+            {{ name }}().let { checksum ->
+                if (checksum != {{ expected_checksum }}.toShort()) {
+                    checksumMismatches.add(UniffiChecksumMismatch("{{ name }}", {{ expected_checksum }}.toShort(), checksum))
+                }
+            }
+            {%- endfor %}
+        }
+        {%- endif %}
+        uniffiCheckLibraryIntegrity(
+            uniffiLibraryPath,
+            bindingsContractVersion,
+            scaffoldingContractVersion,
+            checksumMismatches,
+        )
+    }
+}
+
+{{ visibility() }}fun uniffiEnsureInitialized() {
+    UniffiLib
+}
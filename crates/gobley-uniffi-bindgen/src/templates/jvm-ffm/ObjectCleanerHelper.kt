@@ -0,0 +1,23 @@
+{% include "ffi/ObjectCleanerHelper.kt" %}
+
+// Without JNA, objects are always cleaned by the cleaner of the JDK.
+private class JavaLangRefCleaner : UniffiCleaner {
+    private val cleaner: java.lang.ref.Cleaner = java.lang.ref.Cleaner.create()
+
+    override fun register(resource: Any, disposable: Disposable): UniffiCleaner.Cleanable =
+        JavaLangRefCleanable(cleaner.register(resource, UniffiCleanerAction(disposable)))
+}
+
+private class JavaLangRefCleanable(
+    val cleanable: java.lang.ref.Cleaner.Cleanable
+) : UniffiCleaner.Cleanable {
+    override fun clean() = cleanable.clean()
+}
+
+private class UniffiCleanerAction(private val disposable: Disposable): Runnable {
+    override fun run() {
+        disposable.destroy()
+    }
+}
+
+private fun UniffiCleaner.Companion.create(): UniffiCleaner = JavaLangRefCleaner()
@@ -0,0 +1,31 @@
+internal typealias Pointer = java.lang.foreign.MemorySegment
+internal val NullPointer: Pointer? = null
+internal fun Pointer.toLong(): Long = address()
+internal fun kotlin.Long.toPointer(): Pointer = java.lang.foreign.MemorySegment.ofAddress(this)
+
+// Method handles return `MemorySegment.NULL` for null pointers, which are `null` in Kotlin like
+// with JNA.
+internal fun uniffiNullable(segment: java.lang.foreign.MemorySegment): Pointer? =
+    segment.takeIf { it.address() != 0L }
+
+// Lays out `fields` like a C compiler, padding each field to its alignment and the struct to the
+// alignment of its largest field.
+internal fun uniffiStructLayout(vararg fields: java.lang.foreign.MemoryLayout): java.lang.foreign.StructLayout {
+    val members = mutableListOf<java.lang.foreign.MemoryLayout>()
+    var size = 0L
+    var alignment = 1L
+    for (field in fields) {
+        val padding = (field.byteAlignment() - size % field.byteAlignment()) % field.byteAlignment()
+        if (padding > 0L) {
+            members.add(java.lang.foreign.MemoryLayout.paddingLayout(padding))
+        }
+        members.add(field)
+        size += padding + field.byteSize()
+        alignment = maxOf(alignment, field.byteAlignment())
+    }
+    val padding = (alignment - size % alignment) % alignment
+    if (padding > 0L) {
+        members.add(java.lang.foreign.MemoryLayout.paddingLayout(padding))
+    }
+    return java.lang.foreign.MemoryLayout.structLayout(*members.toTypedArray())
+}
@@ -0,0 +1,44 @@
+// Out pointers passed to callbacks, e.g. for the return values of callback interface methods.
+
+internal class ByteByReference(internal val uniffiSegment: java.lang.foreign.MemorySegment) {
+    fun getValue(): Byte = uniffiSegment.get(java.lang.foreign.ValueLayout.JAVA_BYTE, 0L)
+
+    fun setValue(value: Byte) = uniffiSegment.set(java.lang.foreign.ValueLayout.JAVA_BYTE, 0L, value)
+}
+
+internal class DoubleByReference(internal val uniffiSegment: java.lang.foreign.MemorySegment) {
+    fun getValue(): Double = uniffiSegment.get(java.lang.foreign.ValueLayout.JAVA_DOUBLE, 0L)
+
+    fun setValue(value: Double) = uniffiSegment.set(java.lang.foreign.ValueLayout.JAVA_DOUBLE, 0L, value)
+}
+
+internal class FloatByReference(internal val uniffiSegment: java.lang.foreign.MemorySegment) {
+    fun getValue(): Float = uniffiSegment.get(java.lang.foreign.ValueLayout.JAVA_FLOAT, 0L)
+
+    fun setValue(value: Float) = uniffiSegment.set(java.lang.foreign.ValueLayout.JAVA_FLOAT, 0L, value)
+}
+
+internal class IntByReference(internal val uniffiSegment: java.lang.foreign.MemorySegment) {
+    fun getValue(): Int = uniffiSegment.get(java.lang.foreign.ValueLayout.JAVA_INT, 0L)
+
+    fun setValue(value: Int) = uniffiSegment.set(java.lang.foreign.ValueLayout.JAVA_INT, 0L, value)
+}
+
+internal class LongByReference(internal val uniffiSegment: java.lang.foreign.MemorySegment) {
+    fun getValue(): Long = uniffiSegment.get(java.lang.foreign.ValueLayout.JAVA_LONG, 0L)
+
+    fun setValue(value: Long) = uniffiSegment.set(java.lang.foreign.ValueLayout.JAVA_LONG, 0L, value)
+}
+
+internal class ShortByReference(internal val uniffiSegment: java.lang.foreign.MemorySegment) {
+    fun getValue(): Short = uniffiSegment.get(java.lang.foreign.ValueLayout.JAVA_SHORT, 0L)
+
+    fun setValue(value: Short) = uniffiSegment.set(java.lang.foreign.ValueLayout.JAVA_SHORT, 0L, value)
+}
+
+internal class PointerByReference(internal val uniffiSegment: java.lang.foreign.MemorySegment) {
+    fun getValue(): Pointer? = uniffiNullable(uniffiSegment.get(java.lang.foreign.ValueLayout.ADDRESS, 0L))
+
+    fun setValue(value: Pointer?) =
+        uniffiSegment.set(java.lang.foreign.ValueLayout.ADDRESS, 0L, value ?: java.lang.foreign.MemorySegment.NULL)
+}
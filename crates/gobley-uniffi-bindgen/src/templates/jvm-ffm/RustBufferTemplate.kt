@@ -0,0 +1,137 @@
+{% include "ffi/RustBufferTemplate.kt" %}
+
+// `RustBuffer` is `{ uint64_t capacity; uint64_t len; uint8_t *data; }`.
+internal val UniffiRustBufferLayout: java.lang.foreign.StructLayout = uniffiStructLayout(
+    java.lang.foreign.ValueLayout.JAVA_LONG.withName("capacity"),
+    java.lang.foreign.ValueLayout.JAVA_LONG.withName("len"),
+    java.lang.foreign.ValueLayout.ADDRESS.withName("data"),
+)
+private val UNIFFI_RUST_BUFFER_LEN_OFFSET = UniffiRustBufferLayout.byteOffset(
+    java.lang.foreign.MemoryLayout.PathElement.groupElement("len"),
+)
+private val UNIFFI_RUST_BUFFER_DATA_OFFSET = UniffiRustBufferLayout.byteOffset(
+    java.lang.foreign.MemoryLayout.PathElement.groupElement("data"),
+)
+
+/**
+ * A `RustBuffer` in native memory, such as the out pointers passed to callbacks.
+ */
+{{ visibility() }}class RustBuffer(internal val uniffiSegment: java.lang.foreign.MemorySegment) {
+    // Note: `capacity` and `len` are actually `ULong` values, but JVM only supports signed values.
+    // When dealing with these fields, make sure to call `toULong()`.
+    {{ visibility() }}var capacity: Long
+        get() = uniffiSegment.get(java.lang.foreign.ValueLayout.JAVA_LONG, 0L)
+        set(value) = uniffiSegment.set(java.lang.foreign.ValueLayout.JAVA_LONG, 0L, value)
+
+    {{ visibility() }}var len: Long
+        get() = uniffiSegment.get(java.lang.foreign.ValueLayout.JAVA_LONG, UNIFFI_RUST_BUFFER_LEN_OFFSET)
+        set(value) = uniffiSegment.set(java.lang.foreign.ValueLayout.JAVA_LONG, UNIFFI_RUST_BUFFER_LEN_OFFSET, value)
+
+    {{ visibility() }}var data: Pointer?
+        get() = uniffiNullable(uniffiSegment.get(java.lang.foreign.ValueLayout.ADDRESS, UNIFFI_RUST_BUFFER_DATA_OFFSET))
+        set(value) = uniffiSegment.set(
+            java.lang.foreign.ValueLayout.ADDRESS,
+            UNIFFI_RUST_BUFFER_DATA_OFFSET,
+            value ?: java.lang.foreign.MemorySegment.NULL,
+        )
+}
+
+/**
+ * A `RustBuffer` passed by value, copied to and from native memory around each call.
+ */
+{{ visibility() }}class RustBufferByValue(
+    // Note: `capacity` and `len` are actually `ULong` values, but JVM only supports signed values.
+    // When dealing with these fields, make sure to call `toULong()`.
+    {{ visibility() }}var capacity: Long,
+    {{ visibility() }}var len: Long,
+    {{ visibility() }}var data: Pointer?,
+) {
+    {{ visibility() }}constructor(): this(0.toLong(), 0.toLong(), null)
+}
+
+internal fun RustBufferByValue.uniffiWriteTo(segment: java.lang.foreign.MemorySegment, offset: Long = 0L) {
+    segment.set(java.lang.foreign.ValueLayout.JAVA_LONG, offset, capacity)
+    segment.set(java.lang.foreign.ValueLayout.JAVA_LONG, offset + UNIFFI_RUST_BUFFER_LEN_OFFSET, len)
+    segment.set(
+        java.lang.foreign.ValueLayout.ADDRESS,
+        offset + UNIFFI_RUST_BUFFER_DATA_OFFSET,
+        data ?: java.lang.foreign.MemorySegment.NULL,
+    )
+}
+
+internal fun RustBufferByValue.uniffiToSegment(allocator: java.lang.foreign.SegmentAllocator): java.lang.foreign.MemorySegment =
+    allocator.allocate(UniffiRustBufferLayout).also { uniffiWriteTo(it) }
+
+internal fun uniffiReadRustBufferByValue(segment: java.lang.foreign.MemorySegment, offset: Long = 0L) = RustBufferByValue(
+    capacity = segment.get(java.lang.foreign.ValueLayout.JAVA_LONG, offset),
+    len = segment.get(java.lang.foreign.ValueLayout.JAVA_LONG, offset + UNIFFI_RUST_BUFFER_LEN_OFFSET),
+    data = uniffiNullable(segment.get(java.lang.foreign.ValueLayout.ADDRESS, offset + UNIFFI_RUST_BUFFER_DATA_OFFSET)),
+)
+
+{%- if config.pool_rust_buffers %}
+
+private val uniffiRustBufferPoolCleaner = java.lang.ref.Cleaner.create()
+
+private val uniffiRustBufferPools = object : ThreadLocal<UniffiRustBufferPool>() {
+    override fun initialValue(): UniffiRustBufferPool {
+        val pool = UniffiRustBufferPool()
+        // Free the pooled buffers once the thread, and therefore the pool, is gone.
+        val buffers = pool.buffers
+        uniffiRustBufferPoolCleaner.register(pool) {
+            buffers.forEach(RustBufferHelper::destroy)
+        }
+        return pool
+    }
+}
+
+internal fun uniffiRustBufferPool(): UniffiRustBufferPool = uniffiRustBufferPools.get()
+{%- endif %}
+
+internal fun RustBuffer.asByteBuffer(): ByteBuffer? {
+    {% call kt::check_rust_buffer_length("this.len") %}
+    return ByteBuffer(data?.reinterpret(this.len)?.asByteBuffer() ?: return null)
+}
+
+internal fun RustBufferByValue.asByteBuffer(): ByteBuffer? {
+    {% call kt::check_rust_buffer_length("this.len") %}
+    return ByteBuffer(data?.reinterpret(this.len)?.asByteBuffer() ?: return null)
+}
+
+// This is a helper for safely passing byte references into the rust code.
+// It's not actually used at the moment, because there aren't many things that you
+// can take a direct pointer to in the JVM, and if we're going to copy something
+// then we might as well copy it into a `RustBuffer`. But it's here for API
+// completeness.
+
+// `ForeignBytes` is `{ int32_t len; uint8_t *data; }`.
+internal val UniffiForeignBytesLayout: java.lang.foreign.StructLayout = uniffiStructLayout(
+    java.lang.foreign.ValueLayout.JAVA_INT.withName("len"),
+    java.lang.foreign.ValueLayout.ADDRESS.withName("data"),
+)
+private val UNIFFI_FOREIGN_BYTES_DATA_OFFSET = UniffiForeignBytesLayout.byteOffset(
+    java.lang.foreign.MemoryLayout.PathElement.groupElement("data"),
+)
+
+internal class ForeignBytesByValue(
+    var len: Int,
+    var data: Pointer?,
+) {
+    constructor(): this(0, null)
+}
+
+internal fun ForeignBytesByValue.uniffiWriteTo(segment: java.lang.foreign.MemorySegment, offset: Long = 0L) {
+    segment.set(java.lang.foreign.ValueLayout.JAVA_INT, offset, len)
+    segment.set(
+        java.lang.foreign.ValueLayout.ADDRESS,
+        offset + UNIFFI_FOREIGN_BYTES_DATA_OFFSET,
+        data ?: java.lang.foreign.MemorySegment.NULL,
+    )
+}
+
+internal fun ForeignBytesByValue.uniffiToSegment(allocator: java.lang.foreign.SegmentAllocator): java.lang.foreign.MemorySegment =
+    allocator.allocate(UniffiForeignBytesLayout).also { uniffiWriteTo(it) }
+
+internal fun uniffiReadForeignBytesByValue(segment: java.lang.foreign.MemorySegment, offset: Long = 0L) = ForeignBytesByValue(
+    len = segment.get(java.lang.foreign.ValueLayout.JAVA_INT, offset),
+    data = uniffiNullable(segment.get(java.lang.foreign.ValueLayout.ADDRESS, offset + UNIFFI_FOREIGN_BYTES_DATA_OFFSET)),
+)
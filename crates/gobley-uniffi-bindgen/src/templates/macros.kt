@@ -25,9 +25,9 @@
 {%- macro to_raw_ffi_call(func, indent) -%}
                         {%- match func.throws_type() -%}
                         {%- when Some(e) -%}
-                        uniffiRustCallWithError({{ e|type_name(ci) }}ErrorHandler)
+                        uniffiRustCallWithError({{ e|type_name(ci) }}ErrorHandler, "{{ func.ffi_func().name() }}")
                         {%- else -%}
-                        uniffiRustCall
+                        uniffiRustCall("{{ func.ffi_func().name() }}")
                         {%- endmatch %} { uniffiRustCallStatus ->
 {{ " "|repeat(indent) }}    UniffiLib.{{ func.ffi_func().name() }}(
                                 {%- if func.takes_self() %}
@@ -47,15 +47,20 @@
                         {%- endif %}
 {%- endmacro -%}
 
-{%- macro func_decl(func_decl, callable, indent, is_decl_override) %}
+{#-
+ # The declaration of a function calling Rust. `object_name` is the object the function belongs
+ # to, or empty for top-level functions.
+ #}
+{%- macro func_decl(func_decl, callable, object_name, indent, is_decl_override) %}
+{%- let is_suspend = callable.is_async() || config.is_suspend_sync_call(ci, object_name, callable.name(), callable.is_async()) %}
                         {%- call docstring(callable, indent) -%}
                         {%- match callable.throws_type() -%}
                         {%-     when Some(throwable) %}
-{{ " "|repeat(indent) }}@Throws({{ throwable|type_name(ci) }}::class {%- if callable.is_async() -%}, kotlin.coroutines.cancellation.CancellationException::class{%- endif -%})
+{{ " "|repeat(indent) }}@Throws({{ throwable|type_name(ci) }}::class {%- if is_suspend -%}, kotlin.coroutines.cancellation.CancellationException::class{%- endif -%})
                         {%-     else -%}
                         {%- endmatch %}
 {{ " "|repeat(indent) }}{{ visibility() }}{% if func_decl.len() != 0 -%}{{ func_decl }} {% endif -%}
-                        {%- if callable.is_async() -%}suspend {% endif -%}
+                        {%- if is_suspend -%}suspend {% endif -%}
                         fun {{ callable.name()|fn_name }}(
                             {%- call arg_list(callable, is_decl_override || !callable.takes_self()) -%}
                         )
@@ -65,15 +70,20 @@
                         {%- endmatch -%}
 {% endmacro %}
 
-{%- macro func_decl_with_body(func_decl, callable, indent) %}
+{#-
+ # The body of a function calling Rust. `object_name` is the object the function belongs to, or
+ # empty for top-level functions.
+ #}
+{%- macro func_decl_with_body(func_decl, callable, object_name, indent) %}
+{%- let is_suspend_sync = config.is_suspend_sync_call(ci, object_name, callable.name(), callable.is_async()) %}
                         {%- call docstring(callable, indent) -%}
                         {%- match callable.throws_type() -%}
                         {%-     when Some(throwable) %}
-{{ " "|repeat(indent) }}@Throws({{ throwable|type_name(ci) }}::class {%- if callable.is_async() -%}, kotlin.coroutines.cancellation.CancellationException::class{%- endif -%})
+{{ " "|repeat(indent) }}@Throws({{ throwable|type_name(ci) }}::class {%- if callable.is_async() || is_suspend_sync -%}, kotlin.coroutines.cancellation.CancellationException::class{%- endif -%})
                         {%-     else -%}
                         {%- endmatch %}
 {{ " "|repeat(indent) }}{{ visibility() }}{% if func_decl.len() != 0 -%}{{ func_decl }} {% endif -%}
-                        {%- if callable.is_async() -%}suspend {% endif -%}
+                        {%- if callable.is_async() || is_suspend_sync -%}suspend {% endif -%}
                         fun {{ callable.name()|fn_name }}(
                             {%- call arg_list(callable, false) -%}
                         )
@@ -82,22 +92,49 @@
                         {%-     else -%}
                         {%- endmatch %} {
                             {%- if callable.is_async() %}
-{{ " "|repeat(indent) }}    return {% call call_async(callable, indent + 4) -%}
+{{ " "|repeat(indent) }}    return {% call instrument_call(callable.name()|fn_name, object_name, false) %}{% call call_async(callable, indent + 4) -%}{% call end_instrument_call() %}
+                            {%- else if is_suspend_sync %}
+{{ " "|repeat(indent) }}    return kotlinx.coroutines.withContext({{ config.suspend_dispatcher() }}) {
+                            {%- call sync_call(callable, object_name, indent + 4, "") %}
+{{ " "|repeat(indent) }}    {{ '}' }}
                             {%- else -%}
+                            {%- call sync_call(callable, object_name, indent, "return ") %}
+                            {%- endif %}
+{{ " "|repeat(indent) }}{{ '}' }}
+{% endmacro %}
+
+{#-
+ # The statement of a body calling the sync function `callable`, returning the result with
+ # `return_keyword` when it has one.
+ #}
+{%- macro sync_call(callable, object_name, indent, return_keyword) -%}
                             {%- match callable.return_type() -%}
                             {%-     when Some(return_type) %}
-{{ " "|repeat(indent) }}    return {{ return_type|lift_fn }}({%- call to_ffi_call(callable, indent + 4) -%})
+{{ " "|repeat(indent) }}    {{ return_keyword }}{% call instrument_call(callable.name()|fn_name, object_name, false) %}{{ return_type|lift_fn }}({%- call to_ffi_call(callable, indent + 4) -%}){% call end_instrument_call() %}
                             {%-     else %}
-{{ " "|repeat(indent) }}    {% call to_ffi_call(callable, indent + 4) -%}
+{{ " "|repeat(indent) }}    {% call instrument_call(callable.name()|fn_name, object_name, false) %}{% call to_ffi_call(callable, indent + 4) -%}{% call end_instrument_call() %}
                             {%- endmatch %}
-                            {%- endif %}
-{{ " "|repeat(indent) }}{{ '}' }}
-{% endmacro %}
+{%- endmacro %}
 
-{%- macro func_decl_with_stub(func_decl, callable, indent) %}
+{#-
+ # Opens the report of a call of `function_name` to `uniffiCallInterceptor`, closed by
+ # `end_instrument_call`. `object_name` is the object or callback interface the function belongs
+ # to, or empty for top-level functions.
+ #}
+{%- macro instrument_call(function_name, object_name, is_callback) -%}
+{%- if config.instrument_calls -%}
+uniffiInstrumentCall("{{ function_name|unquote }}", {% if object_name.is_empty() %}null{% else %}"{{ object_name|class_name(ci) }}"{% endif %}, {{ is_callback }}) { {% endif -%}
+{%- endmacro %}
+
+{%- macro end_instrument_call() -%}
+{%- if config.instrument_calls %} }{% endif -%}
+{%- endmacro %}
+
+{%- macro func_decl_with_stub(func_decl, callable, object_name, indent) %}
+{%- let is_suspend = callable.is_async() || config.is_suspend_sync_call(ci, object_name, callable.name(), callable.is_async()) %}
                         {%- call docstring(callable, indent) %}
 {{ " "|repeat(indent) }}{{ visibility() }}{% if func_decl.len() != 0 -%}{{ func_decl }} {% endif -%}
-                        {%- if callable.is_async() -%}suspend {% endif -%}
+                        {%- if is_suspend -%}suspend {% endif -%}
                         fun {{ callable.name()|fn_name }}(
                             {%- call arg_list(callable, false) -%}
                         )
@@ -109,6 +146,32 @@
 {{ " "|repeat(indent) }}{{ '}' }}
 {% endmacro %}
 
+{#-
+ # The `<function>Async` companion of the async function `callable`, returning a
+ # `CompletableFuture` for the callers on the JVM that can't call `suspend` functions.
+ #}
+{%- macro completable_future(callable, indent) %}
+{{ " "|repeat(indent) }}/**
+{{ " "|repeat(indent) }} * Calls [{{ callable.name()|fn_name|unquote }}], cancelling the Rust future when the returned future is cancelled.
+{{ " "|repeat(indent) }} */
+{{ " "|repeat(indent) }}{{ visibility() }}fun {{ callable.name()|completable_future_fn_name }}(
+                            {%- call arg_list(callable, true) -%}
+                        ): java.util.concurrent.CompletableFuture<
+                        {%- match callable.return_type() -%}
+                        {%-     when Some(return_type) -%}{{ return_type|type_name(ci) }}
+                        {%-     when None -%}Unit
+                        {%- endmatch -%}
+                        > {
+{{ " "|repeat(indent) }}    return uniffiCompletableFuture {
+{{ " "|repeat(indent) }}        {{ callable.name()|fn_name }}(
+                                    {%- for arg in callable.arguments() -%}
+                                    {{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}
+                                    {%- endfor -%}
+                                )
+{{ " "|repeat(indent) }}    }
+{{ " "|repeat(indent) }}{{ '}' }}
+{% endmacro %}
+
 {%- macro call_async(callable, indent) -%}
                         uniffiRustCallAsync(
                             {%- if callable.takes_self() %}
@@ -145,6 +208,7 @@
                             {%- when None %}
 {{ " "|repeat(indent) }}    UniffiNullRustCallStatusErrorHandler,
                             {%- endmatch %}
+{{ " "|repeat(indent) }}    "{{ callable.ffi_func().name() }}",
 {{ " "|repeat(indent) }})
 {%- endmacro %}
 
@@ -298,6 +362,17 @@ v{{- field_num -}}
 {{ " "|repeat(indent) }}{{ '}' }}
 {%- endmacro -%}
 
+{#-
+ # The `@file:OptIn` annotation of the bindings of `module_name`. `OptIn` isn't repeatable, so
+ # every marker is listed in a single annotation.
+ #}
+{%- macro file_opt_in(module_name) %}
+{%- let opt_ins = config.opt_in_annotations(ci, module_name) %}
+{%- if !opt_ins.is_empty() %}
+@file:OptIn({% for opt_in in opt_ins %}{{ opt_in }}::class{% if !loop.last %}, {% endif %}{% endfor %})
+{%- endif %}
+{%- endmacro %}
+
 {%- macro docstring_value(maybe_docstring, indent_spaces) %}
 {%- match maybe_docstring %}
 {%- when Some(docstring) %}
@@ -309,3 +384,38 @@ v{{- field_num -}}
 {%- macro docstring(defn, indent_spaces) %}
 {%- call docstring_value(defn.docstring(), indent_spaces) %}
 {%- endmacro %}
+
+{#-
+ # The supertypes of the error `name`, whose Kotlin class is named `class_name`.
+ #}
+{%- macro error_superclass(name, class_name) -%}
+{%- if config.error_has_base_class(name) -%}
+{{ class_name }}Base
+{%- else -%}
+{{ config.error_superclass(name, module_name) }}
+{%- endif -%}
+{%- endmacro %}
+
+{%- macro error_interfaces(name) -%}
+{%- for interface in config.error_interfaces(name) %}, {{ interface }}{% endfor -%}
+{%- endmacro %}
+
+{#-
+ # The class extended by the error `name` when its superclass differs between the targets. The
+ # common bindings declare it, and the bindings of each target define it.
+ #}
+{%- macro error_base_class(name, class_name) %}
+{%- if config.error_has_base_class(name) %}
+{%- if module_name == "common" %}
+{{ visibility() }}expect open class {{ class_name }}Base : {{ config.error_superclass(name, module_name) }} {
+    constructor()
+    constructor(message: String)
+}
+{%- else %}
+{{ visibility() }}actual open class {{ class_name }}Base : {{ config.error_superclass(name, module_name) }} {
+    actual constructor() : super()
+    actual constructor(message: String) : super(message)
+}
+{%- endif %}
+{% endif %}
+{%- endmacro %}
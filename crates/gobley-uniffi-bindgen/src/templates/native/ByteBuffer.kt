@@ -1,3 +1,12 @@
+// Strings of up to this many bytes are lifted through an array reused by each thread.
+private const val UNIFFI_STRING_SCRATCH_SIZE = 16 * 1024
+
+@kotlin.native.concurrent.ThreadLocal
+private var uniffiThreadStringScratch: ByteArray? = null
+
+private fun uniffiStringScratch(): ByteArray = uniffiThreadStringScratch
+    ?: ByteArray(UNIFFI_STRING_SCRATCH_SIZE).also { uniffiThreadStringScratch = it }
+
 
 {{ visibility() }}class ByteBuffer(
     internal val pointer: CPointer<kotlinx.cinterop.ByteVar>,
@@ -8,6 +17,10 @@
 
     {{ visibility() }}fun hasRemaining(): Boolean = capacity != position
 
+    {{ visibility() }}fun skipRemaining() {
+        position = capacity
+    }
+
     private fun checkRemaining(bytes: Int) {
         val remaining = capacity - position
         require(bytes <= remaining) { 
@@ -32,6 +45,25 @@
         return result
     }
 
+    // Decodes the UTF-8 bytes straight into the String. The bytes are first copied into an array
+    // reused by the thread, so short strings don't allocate an intermediate array.
+    {{ visibility() }}fun getString(length: Int): String {
+        checkRemaining(length)
+        if (length == 0) {
+            return ""
+        }
+        val bytes = if (length <= UNIFFI_STRING_SCRATCH_SIZE) {
+            uniffiStringScratch()
+        } else {
+            ByteArray(length)
+        }
+        bytes.usePinned { pinned ->
+            memcpy(pinned.addressOf(0), pointer + position, length.convert())
+        }
+        position += length
+        return bytes.decodeToString(0, length)
+    }
+
     {{ visibility() }}fun getShort(): Short {
         checkRemaining(2)
         return (((pointer[position++].toInt() and 0xff) shl 8)
@@ -77,6 +109,42 @@
         }
     }
 
+    // Encodes `value` directly into the buffer, which must have room for `uniffiUtf8Length(value)`
+    // bytes.
+    {{ visibility() }}fun putString(value: String) {
+        var index = 0
+        while (index < value.length) {
+            val char = value[index++]
+            val code = char.code
+            when {
+                code < 0x80 -> {
+                    checkRemaining(1)
+                    pointer[position++] = code.toByte()
+                }
+                code < 0x800 -> {
+                    checkRemaining(2)
+                    pointer[position++] = (0xc0 or (code shr 6)).toByte()
+                    pointer[position++] = (0x80 or (code and 0x3f)).toByte()
+                }
+                char.isHighSurrogate() && index < value.length && value[index].isLowSurrogate() -> {
+                    val codePoint = 0x10000 + ((code - 0xd800) shl 10) + (value[index++].code - 0xdc00)
+                    checkRemaining(4)
+                    pointer[position++] = (0xf0 or (codePoint shr 18)).toByte()
+                    pointer[position++] = (0x80 or ((codePoint shr 12) and 0x3f)).toByte()
+                    pointer[position++] = (0x80 or ((codePoint shr 6) and 0x3f)).toByte()
+                    pointer[position++] = (0x80 or (codePoint and 0x3f)).toByte()
+                }
+                char.isSurrogate() -> throw CharacterCodingException()
+                else -> {
+                    checkRemaining(3)
+                    pointer[position++] = (0xe0 or (code shr 12)).toByte()
+                    pointer[position++] = (0x80 or ((code shr 6) and 0x3f)).toByte()
+                    pointer[position++] = (0x80 or (code and 0x3f)).toByte()
+                }
+            }
+        }
+    }
+
     {{ visibility() }}fun putShort(value: Short) {
         checkRemaining(2)
         pointer[position++] = (value.toInt() ushr 8 and 0xff).toByte()
@@ -91,6 +159,17 @@
         pointer[position++] = (value and 0xff).toByte()
     }
 
+    // Writes `value` at `index` without moving the position.
+    {{ visibility() }}fun putInt(index: Int, value: Int) {
+        require(index >= 0 && index + 4 <= capacity) {
+            "index out of bounds: index: $index, capacity: $capacity"
+        }
+        pointer[index] = (value ushr 24 and 0xff).toByte()
+        pointer[index + 1] = (value ushr 16 and 0xff).toByte()
+        pointer[index + 2] = (value ushr 8 and 0xff).toByte()
+        pointer[index + 3] = (value and 0xff).toByte()
+    }
+
     {{ visibility() }}fun putLong(value: Long) {
         checkRemaining(8)
         pointer[position++] = (value ushr 56 and 0xffL).toByte()
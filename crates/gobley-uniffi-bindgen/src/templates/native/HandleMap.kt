@@ -1,37 +1,156 @@
-
+// Maps Kotlin objects passed to Rust, like callback interface implementations and futures, to
+// handles. The handles are split over stripes, each a slab guarded by its own lock, so concurrent
+// callers rarely wait for each other. A slot gets a new generation when its object is removed,
+// which tells stale handles apart from the handles of the objects reusing the slot.
+//
+// | generation | slot (20 bits) | stripe (4 bits) |
+//
+// Trait interface handles are passed to Rust as pointers, so on 32-bit platforms handles fit in 32
+// bits, leaving 8 bits to the generation, and 63 bits otherwise. Freed slots are reused in FIFO
+// order, and a slot whose generations are exhausted is retired, so a stale handle never resolves to
+// another object.
+//
+// Generations start at 1 to prevent "Null Pointers" in native's `interpretCPointer`.
 internal class UniffiHandleMap<T: Any> {
-    private val mapLock = kotlinx.atomicfu.locks.ReentrantLock()
-    private val map = HashMap<Long, T>()
-
-    // We'll start at 1L to prevent "Null Pointers" in native's `interpretCPointer`
-    private val counter: kotlinx.atomicfu.AtomicLong = kotlinx.atomicfu.atomic(1L)
+    private val stripes = Array(STRIPE_COUNT) { UniffiHandleMapStripe<T>() }
+    private val nextStripe: kotlinx.atomicfu.AtomicInt = kotlinx.atomicfu.atomic(0)
 
+    // Each stripe is counted under its own lock, so the total isn't a snapshot of the whole map
+    // while other threads insert or remove objects.
     internal val size: Int
-        get() = map.size
+        get() = stripes.sumOf { it.size }
 
     // Insert a new object into the handle map and get a handle for it
     internal fun insert(obj: T): Long {
-        val handle = counter.getAndAdd(1)
-        syncAccess { map.put(handle, obj) }
-        return handle
+        val stripe = nextStripe.getAndIncrement() and STRIPE_MASK
+        return stripes[stripe].insert(obj) or stripe.toLong()
     }
 
     // Get an object from the handle map
     internal fun get(handle: Long): T {
-        return syncAccess { map.get(handle) } ?: throw InternalException("UniffiHandleMap.get: Invalid handle")
+        return stripeOf(handle, "get").get(handle, slotOf(handle), generationOf(handle), "get")
     }
 
     // Remove an entry from the handlemap and get the Kotlin object back
     internal fun remove(handle: Long): T {
-        return syncAccess { map.remove(handle) } ?: throw InternalException("UniffiHandleMap.remove: Invalid handle")
+        return stripeOf(handle, "remove").remove(handle, slotOf(handle), generationOf(handle), "remove")
+    }
+
+    private fun stripeOf(handle: Long, operation: String): UniffiHandleMapStripe<T> {
+        if (handle ushr HANDLE_BITS != 0L || generationOf(handle) == 0L) {
+            throw InternalException("UniffiHandleMap.$operation: Invalid handle $handle")
+        }
+        return stripes[(handle and STRIPE_MASK.toLong()).toInt()]
+    }
+
+    private fun slotOf(handle: Long): Int = ((handle ushr SLOT_SHIFT) and SLOT_MASK.toLong()).toInt()
+
+    private fun generationOf(handle: Long): Long = handle ushr GENERATION_SHIFT
+
+    internal companion object {
+        val HANDLE_BITS = if (kotlinx.cinterop.sizeOf<kotlinx.cinterop.COpaquePointerVar>() < 8) 32 else 63
+        const val STRIPE_BITS = 4
+        const val SLOT_BITS = 20
+        const val SLOT_SHIFT = STRIPE_BITS
+        const val GENERATION_SHIFT = STRIPE_BITS + SLOT_BITS
+        const val STRIPE_COUNT = 1 shl STRIPE_BITS
+        const val STRIPE_MASK = STRIPE_COUNT - 1
+        const val SLOT_MASK = (1 shl SLOT_BITS) - 1
+        val MAX_GENERATION = (1L shl (HANDLE_BITS - GENERATION_SHIFT)) - 1
+    }
+}
+
+private class UniffiHandleMapStripe<T: Any> {
+    private val lock = kotlinx.atomicfu.locks.ReentrantLock()
+    private val objects = ArrayList<T?>()
+    // The generation of each slot, or 0 once the slot is retired.
+    private var generations = LongArray(0)
+    // A ring buffer of the freed slots, `freeSlotCount` of them starting at `freeSlotHead`.
+    private var freeSlots = IntArray(0)
+    private var freeSlotHead = 0
+    private var freeSlotCount = 0
+
+    private var count = 0
+
+    val size: Int
+        get() = synchronized { count }
+
+    // Returns the handle of `obj` without the stripe bits.
+    fun insert(obj: T): Long = synchronized {
+        val slot = if (freeSlotCount > 0) {
+            val slot = freeSlots[freeSlotHead]
+            freeSlotHead = (freeSlotHead + 1) % freeSlots.size
+            freeSlotCount -= 1
+            slot
+        } else {
+            val slot = objects.size
+            if (slot > UniffiHandleMap.SLOT_MASK) {
+                throw InternalException("UniffiHandleMap.insert: Too many handles")
+            }
+            objects.add(null)
+            if (slot == generations.size) {
+                generations = generations.copyOf(maxOf(16, slot * 2))
+            }
+            generations[slot] = 1L
+            slot
+        }
+        objects[slot] = obj
+        count += 1
+        (generations[slot] shl UniffiHandleMap.GENERATION_SHIFT) or
+            (slot.toLong() shl UniffiHandleMap.SLOT_SHIFT)
+    }
+
+    fun get(handle: Long, slot: Int, generation: Long, operation: String): T = synchronized {
+        find(handle, slot, generation, operation)
+    }
+
+    fun remove(handle: Long, slot: Int, generation: Long, operation: String): T = synchronized {
+        val obj = find(handle, slot, generation, operation)
+        objects[slot] = null
+        count -= 1
+        if (generation == UniffiHandleMap.MAX_GENERATION) {
+            // Reusing the slot would wrap its generation around, and let the stale handles of the
+            // slot match the new objects.
+            generations[slot] = 0L
+        } else {
+            generations[slot] = generation + 1
+            pushFreeSlot(slot)
+        }
+        obj
+    }
+
+    private fun pushFreeSlot(slot: Int) {
+        if (freeSlotCount == freeSlots.size) {
+            val grown = IntArray(maxOf(16, freeSlots.size * 2))
+            for (i in 0 until freeSlotCount) {
+                grown[i] = freeSlots[(freeSlotHead + i) % freeSlots.size]
+            }
+            freeSlots = grown
+            freeSlotHead = 0
+        }
+        freeSlots[(freeSlotHead + freeSlotCount) % freeSlots.size] = slot
+        freeSlotCount += 1
+    }
+
+    private fun find(handle: Long, slot: Int, generation: Long, operation: String): T {
+        if (slot >= objects.size) {
+            throw InternalException("UniffiHandleMap.$operation: Invalid handle $handle")
+        }
+        if (generations[slot] != generation) {
+            throw UniffiStaleHandleException(
+                "UniffiHandleMap.$operation: Stale handle $handle, its object was already removed",
+                handle,
+            )
+        }
+        return objects[slot]!!
     }
 
-    internal fun <T> syncAccess(block: () -> T): T {
-        mapLock.lock()
+    private inline fun <R> synchronized(block: () -> R): R {
+        lock.lock()
         try {
             return block()
         } finally {
-            mapLock.unlock()
+            lock.unlock()
         }
     }
 }
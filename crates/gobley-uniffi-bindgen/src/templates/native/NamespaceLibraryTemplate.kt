@@ -64,6 +64,7 @@ internal val {{ ffi_struct.name()|ffi_struct_name }}UniffiByValue.{{ field.name(
 
 internal object UniffiLib {
     init {
+        uniffiCheckContractApiVersionAndChecksums()
         {%- for init_fn in self.initialization_fns(ci) %}
             {{ init_fn }}
         {%- endfor %}
@@ -76,6 +77,32 @@ internal object UniffiLib {
     }
     {%- endif %}
 
+    private fun uniffiCheckContractApiVersionAndChecksums() {
+        // Get the bindings contract version from our ComponentInterface
+        val bindingsContractVersion = {{ ci.uniffi_contract_version() }}
+        // Get the scaffolding contract version by calling the into the static library
+        val scaffoldingContractVersion = {{ ci.ffi_uniffi_contract_version().name() }}().toInt()
+        val checksumMismatches = mutableListOf<UniffiChecksumMismatch>()
+        {%- if !config.omit_checksums %}
+        if (bindingsContractVersion == scaffoldingContractVersion) {
+            {%- for (name, expected_checksum) in ci.iter_checksums() %}
+            {{ name }}().toShort().let { checksum ->
+                if (checksum != {{ expected_checksum }}.toShort()) {
+                    checksumMismatches.add(UniffiChecksumMismatch("{{ name }}", {{ expected_checksum }}.toShort(), checksum))
+                }
+            }
+            {%- endfor %}
+        }
+        {%- endif %}
+        // The Rust library is linked statically into the binary.
+        uniffiCheckLibraryIntegrity(
+            null,
+            bindingsContractVersion,
+            scaffoldingContractVersion,
+            checksumMismatches,
+        )
+    }
+
     {% for func in ci.iter_ffi_function_definitions() -%}
     fun {{ func.name() }}(
         {%- call kt::arg_list_ffi_decl(func, 8) %}
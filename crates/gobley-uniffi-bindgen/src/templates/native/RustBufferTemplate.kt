@@ -45,6 +45,16 @@
     )
 }
 
+{%- if config.pool_rust_buffers %}
+
+// Pooled buffers are not freed when their thread exits, leaking at most `MAX_BUFFERS` buffers of
+// `MAX_CAPACITY` bytes per thread.
+@kotlin.native.concurrent.ThreadLocal
+private val uniffiThreadRustBufferPool = UniffiRustBufferPool()
+
+internal fun uniffiRustBufferPool(): UniffiRustBufferPool = uniffiThreadRustBufferPool
+{%- endif %}
+
 internal typealias ForeignBytes = CPointer<{{ ci.namespace() }}.cinterop.ForeignBytes>
 internal var ForeignBytes.len: Int
     get() = pointed.len
@@ -1,5 +1,7 @@
 
 {%- import "macros.kt" as kt %}
+{#- `CompletableFuture` is only available on the JVM and Android #}
+{%- let completable_futures = false %}
 
 {%- if ci.has_callback_definitions() %}
 {%- include "ffi/CallbackInterfaceRuntime.kt" %}
@@ -132,3 +134,8 @@
 {{ self.add_import("kotlinx.coroutines.IO") }}
 {{ self.add_import("kotlinx.coroutines.Dispatchers") }}
 {%- endif %}
+
+{%- if !config.suspend_sync_calls.is_empty() %}
+{# `Dispatchers.IO` is an extension on Kotlin/Native, so `suspend_dispatcher` can use it #}
+{{ self.add_import("kotlinx.coroutines.IO") }}
+{%- endif %}
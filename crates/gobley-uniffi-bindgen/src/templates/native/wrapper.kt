@@ -1,7 +1,7 @@
 {%- call kt::docstring_value(ci.namespace_docstring(), 0) %}
 
 @file:Suppress("RemoveRedundantBackticks")
-@file:OptIn(ExperimentalForeignApi::class)
+{%- call kt::file_opt_in(module_name) %}
 
 package {{ config.package_name() }}
 
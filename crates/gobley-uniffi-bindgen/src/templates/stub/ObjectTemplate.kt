@@ -7,9 +7,12 @@
 {%- let is_error = ci.is_name_used_as_error(name) %}
 {%- let ffi_converter_name = obj|ffi_converter_name %}
 
+{%- if is_error %}
+{%- call kt::error_base_class(name, impl_class_name) %}
+{%- endif %}
 {%- call kt::docstring(obj, 0) %}
 {% if (is_error) %}
-{{ visibility() }}actual open class {{ impl_class_name }} : kotlin.Exception, Disposable, {{ interface_name }} {
+{{ visibility() }}actual open class {{ impl_class_name }} : {% call kt::error_superclass(name, impl_class_name) %}, Disposable, {{ interface_name }}{% call kt::error_interfaces(name) %} {
 {% else -%}
 {{ visibility() }}actual open class {{ impl_class_name }}: Disposable, {{ interface_name }}
 {%- for t in obj.trait_impls() -%}
@@ -47,7 +50,7 @@
     }
 
     {% for meth in obj.methods() -%}
-    {%- call kt::func_decl_with_stub("actual override", meth, 4) -%}
+    {%- call kt::func_decl_with_stub("actual override", meth, name, 4) -%}
     {% endfor %}
 
     {%- for tm in obj.uniffi_traits() %}
@@ -73,7 +76,7 @@
     {% if !obj.alternate_constructors().is_empty() -%}
     {{ visibility() }}actual companion object {
         {% for cons in obj.alternate_constructors() -%}
-        {%- call kt::func_decl_with_stub("actual", cons, 8) %}
+        {%- call kt::func_decl_with_stub("actual", cons, name, 8) %}
         {% endfor %}
     }
     {% else %}
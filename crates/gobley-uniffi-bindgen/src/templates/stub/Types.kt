@@ -22,6 +22,35 @@
 {%- when Type::Object { module_path, name, imp } %}
 {% include "ObjectTemplate.kt" %}
 
+{%- when Type::Enum { name, module_path } %}
+{%- if ci.is_name_used_as_error(name) %}
+{%- call kt::error_base_class(name, type_name) %}
+{%- endif %}
+
+{%- when Type::Timestamp %}
+{{- self.add_import_as(config.timestamp_type_name(), "UniffiInstant") }}
+
+{%- when Type::Custom { module_path, name, builtin } %}
+{%- if config.custom_type_is_expect_class(name) %}
+{%- match config.custom_type(name, module_name) %}
+{%- when Some(custom_type_config) %}
+{%- match custom_type_config.type_name %}
+{%- when Some(concrete_type_name) %}
+{%- match custom_type_config.imports %}
+{%- when Some(imports) %}
+{%- for import_name in imports %}
+{{ self.add_import(import_name) }}
+{%- endfor %}
+{%- else %}
+{%- endmatch %}
+{{ visibility() }}actual typealias {{ type_name }} = {{ concrete_type_name }}
+{%- else %}
+{{ visibility() }}actual typealias {{ type_name }} = {{ builtin|type_name(ci) }}
+{%- endmatch %}
+{%- else %}
+{%- endmatch %}
+{%- endif %}
+
 {%- else %}
 {%- endmatch %}
 {%- endfor %}
@@ -1,9 +1,14 @@
 {%- call kt::docstring_value(ci.namespace_docstring(), 0) %}
 
 @file:Suppress("RemoveRedundantBackticks")
+{%- call kt::file_opt_in(module_name) %}
 
 package {{ config.package_name() }}
 
+{%- for req in self.imports() %}
+{{ req.render() }}
+{%- endfor %}
+
 // Public interface members begin here.
 {{ type_helper_code }}
 
@@ -0,0 +1,304 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use anyhow::{bail, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use fs_err as fs;
+use toml::value::{Table, Value};
+
+use crate::gen_kotlin_multiplatform::validation::find_unknown_keys;
+
+/// The name of the file containing the workspace-level defaults, placed next to the workspace
+/// `Cargo.toml`.
+pub const WORKSPACE_CONFIG_FILE_NAME: &str = "gobley.toml";
+
+/// Configuration keys that must be different for each crate and can't be used as defaults.
+const CRATE_SPECIFIC_KEYS: &[&str] = &["package_name", "cdylib_name"];
+
+/// Default bindings configuration shared by every crate in a Cargo workspace. Read from
+/// `gobley.toml` or the `[workspace.metadata.gobley]` table of the workspace `Cargo.toml`.
+/// The `uniffi.toml` of each crate overrides the values here.
+#[derive(Debug, Clone)]
+pub struct WorkspaceConfig {
+    /// The file the configuration was read from.
+    pub path: Utf8PathBuf,
+    pub table: Table,
+}
+
+impl WorkspaceConfig {
+    /// Reads the workspace configuration from a `gobley.toml` file, or from the
+    /// `[workspace.metadata.gobley]` table when `path` points to a `Cargo.toml`.
+    pub fn load(path: &Utf8Path) -> Result<Self> {
+        let table = if path.file_name() == Some("Cargo.toml") {
+            read_toml_file(path)?
+                .get("workspace")
+                .and_then(|workspace| workspace.get("metadata"))
+                .and_then(|metadata| metadata.get("gobley"))
+                .cloned()
+                .unwrap_or_else(|| Value::Table(Table::new()))
+        } else {
+            Value::Table(read_toml_file(path)?)
+        };
+        let Value::Table(table) = table else {
+            bail!("{path}: workspace.metadata.gobley must be a table");
+        };
+
+        // Unknown keys are only reported as warnings until the next minor release.
+        for warning in unknown_key_warnings(path, &table) {
+            eprintln!("{warning}");
+        }
+        let mut errors = Vec::new();
+        for key in CRATE_SPECIFIC_KEYS {
+            if table.contains_key(*key) {
+                errors.push(format!(
+                    "`{key}` is specific to each crate and can't be set for the workspace"
+                ));
+            }
+        }
+        if !errors.is_empty() {
+            bail!(
+                "invalid workspace configuration in {path}:\n{}",
+                errors
+                    .iter()
+                    .map(|error| format!("  - {error}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+
+        Ok(Self {
+            path: path.to_owned(),
+            table,
+        })
+    }
+
+    /// Looks for the workspace configuration in `dir` and its ancestors. The search stops at the
+    /// directory of the workspace `Cargo.toml`, or at the package root when the package is not
+    /// part of a workspace. `gobley.toml` takes priority over `[workspace.metadata.gobley]`.
+    /// Returns `None` when `dir` is not inside a Cargo package. `dir` should be an absolute path.
+    pub fn find(dir: &Utf8Path) -> Result<Option<Self>> {
+        let mut package_root = None;
+        let mut workspace_root = None;
+        for dir in dir.ancestors() {
+            let manifest_path = dir.join("Cargo.toml");
+            if !manifest_path.is_file() {
+                continue;
+            }
+            package_root.get_or_insert(dir);
+            if read_toml_file(&manifest_path)?.contains_key("workspace") {
+                workspace_root = Some(dir);
+                break;
+            }
+        }
+        let Some(root) = workspace_root.or(package_root) else {
+            return Ok(None);
+        };
+
+        for dir in dir.ancestors() {
+            let config_path = dir.join(WORKSPACE_CONFIG_FILE_NAME);
+            if config_path.is_file() {
+                return Self::load(&config_path).map(Some);
+            }
+            if dir == root {
+                break;
+            }
+        }
+
+        match workspace_root {
+            Some(workspace_root) => {
+                let config = Self::load(&workspace_root.join("Cargo.toml"))?;
+                Ok((!config.table.is_empty()).then_some(config))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Merges the configuration of a crate on top of the workspace defaults. Tables such as
+    /// `custom_types` are merged key by key, while other values including arrays are replaced.
+    pub fn merge(&self, crate_config: &Value) -> Value {
+        let mut table = self.table.clone();
+        if let Value::Table(crate_config) = crate_config {
+            merge_tables(&mut table, crate_config);
+        }
+        Value::Table(table)
+    }
+}
+
+fn merge_tables(base: &mut Table, overrides: &Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(key), value) {
+            (Some(Value::Table(base)), Value::Table(value)) => merge_tables(base, value),
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn unknown_key_warnings(path: &Utf8Path, table: &Table) -> Vec<String> {
+    find_unknown_keys(&Value::Table(table.clone()))
+        .into_iter()
+        .map(|warning| format!("Warning: workspace configuration in {path}: {warning}"))
+        .collect()
+}
+
+fn read_toml_file(path: &Utf8Path) -> Result<Table> {
+    let contents = fs::read_to_string(path)?;
+    toml::from_str(&contents).with_context(|| format!("failed to parse {path}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_dir(name: &str) -> Utf8PathBuf {
+        let root = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!(
+                "gobley-uniffi-bindgen-{name}-{}",
+                std::process::id()
+            ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn workspace_config(table: &str) -> WorkspaceConfig {
+        WorkspaceConfig {
+            path: Utf8PathBuf::from(WORKSPACE_CONFIG_FILE_NAME),
+            table: toml::from_str(table).unwrap(),
+        }
+    }
+
+    #[test]
+    fn merge_overrides_values_and_merges_tables() {
+        let config = workspace_config(
+            r#"
+            kotlin_targets = ["jvm", "android", "native"]
+            generate_immutable_records = true
+            [custom_types.Url]
+            type_name = "String"
+            [custom_types.Uuid]
+            type_name = "String"
+            "#,
+        );
+        let crate_config: Value = toml::from_str(
+            r#"
+            package_name = "component"
+            kotlin_targets = ["jvm"]
+            [custom_types.Uuid]
+            imports = ["kotlin.uuid.Uuid"]
+            "#,
+        )
+        .unwrap();
+        let expected: Value = toml::from_str(
+            r#"
+            package_name = "component"
+            kotlin_targets = ["jvm"]
+            generate_immutable_records = true
+            [custom_types.Url]
+            type_name = "String"
+            [custom_types.Uuid]
+            type_name = "String"
+            imports = ["kotlin.uuid.Uuid"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.merge(&crate_config), expected);
+    }
+
+    #[test]
+    fn unknown_keys_are_warnings() {
+        let config = workspace_config("kotlin_target = [\"jvm\"]\n");
+        assert_eq!(
+            unknown_key_warnings(&config.path, &config.table),
+            vec![
+                "Warning: workspace configuration in gobley.toml: unknown key `kotlin_target`, did you mean `kotlin_targets`?"
+            ],
+        );
+    }
+
+    #[test]
+    fn crate_specific_keys_are_errors() {
+        let root = create_dir("crate-specific-keys");
+        let config_path = root.join(WORKSPACE_CONFIG_FILE_NAME);
+        fs::write(&config_path, "package_name = \"component\"\n").unwrap();
+        assert!(WorkspaceConfig::load(&config_path).is_err());
+    }
+
+    #[test]
+    fn find_reads_workspace_metadata() {
+        let root = create_dir("find-workspace-metadata");
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"component\"]\n\n[workspace.metadata.gobley]\nkotlin_multiplatform = true\n",
+        )
+        .unwrap();
+        let crate_path = root.join("component");
+        fs::create_dir_all(crate_path.join("src")).unwrap();
+        fs::write(
+            crate_path.join("Cargo.toml"),
+            "[package]\nname = \"component\"\n",
+        )
+        .unwrap();
+
+        let config = WorkspaceConfig::find(&crate_path.join("src"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.path, root.join("Cargo.toml"));
+        assert_eq!(
+            config.table.get("kotlin_multiplatform"),
+            Some(&Value::Boolean(true))
+        );
+
+        // gobley.toml takes priority over the workspace metadata.
+        fs::write(
+            root.join(WORKSPACE_CONFIG_FILE_NAME),
+            "kotlin_multiplatform = false\n",
+        )
+        .unwrap();
+        let config = WorkspaceConfig::find(&crate_path).unwrap().unwrap();
+        assert_eq!(config.path, root.join(WORKSPACE_CONFIG_FILE_NAME));
+    }
+
+    #[test]
+    fn find_stops_at_package_root_outside_of_workspace() {
+        let root = create_dir("find-package-root");
+        // Not read, since the package below is not part of a workspace.
+        fs::write(
+            root.join(WORKSPACE_CONFIG_FILE_NAME),
+            "kotlin_multiplatform = true\n",
+        )
+        .unwrap();
+        let crate_path = root.join("component");
+        fs::create_dir_all(&crate_path).unwrap();
+        fs::write(
+            crate_path.join("Cargo.toml"),
+            "[package]\nname = \"component\"\n",
+        )
+        .unwrap();
+        assert!(WorkspaceConfig::find(&crate_path).unwrap().is_none());
+
+        fs::write(
+            crate_path.join(WORKSPACE_CONFIG_FILE_NAME),
+            "kotlin_multiplatform = true\n",
+        )
+        .unwrap();
+        let config = WorkspaceConfig::find(&crate_path).unwrap().unwrap();
+        assert_eq!(config.path, crate_path.join(WORKSPACE_CONFIG_FILE_NAME));
+    }
+
+    #[test]
+    fn find_outside_of_package_returns_none() {
+        let root = create_dir("find-outside-package");
+        fs::write(
+            root.join(WORKSPACE_CONFIG_FILE_NAME),
+            "kotlin_multiplatform = true\n",
+        )
+        .unwrap();
+        assert!(WorkspaceConfig::find(&root).unwrap().is_none());
+    }
+}
@@ -38,26 +38,44 @@ if (ext.propertyIsTrue("gobley.projects.gradleTests")) {
 }
 
 if (ext.propertyIsTrue("gobley.projects.uniffiTests")) {
+    include(":tests:uniffi:android-jni")
+    include(":tests:uniffi:call-instrumentation")
     include(":tests:uniffi:callbacks")
     include(":tests:uniffi:chronological")
+    include(":tests:uniffi:cinterop-def")
+    include(":tests:uniffi:completable-futures")
     include(":tests:uniffi:coverall")
     include(":tests:uniffi:coverall-android")
     include(":tests:uniffi:coverall-jvm")
     include(":tests:uniffi:coverall-pure-kotlin-dep")
+    include(":tests:uniffi:custom-type-presets")
+    include(":tests:uniffi:custom-types-per-target")
     include(":tests:uniffi:docstring")
     include(":tests:uniffi:docstring-proc-macro")
     include(":tests:uniffi:dynamic-library-dependencies")
     include(":tests:uniffi:enum-types")
+    include(":tests:uniffi:error-supertypes")
     include(":tests:uniffi:error-types")
+    include(":tests:uniffi:handle-map")
+    include(":tests:uniffi:jvm-common-source-set")
+    include(":tests:uniffi:jvm-ffm:callbacks")
+    include(":tests:uniffi:jvm-ffm:coverall")
     include(":tests:uniffi:keywords")
     include(":tests:uniffi:large-enum")
     include(":tests:uniffi:large-error")
+    include(":tests:uniffi:library-loader")
+    include(":tests:uniffi:object-leak-tracking")
     include(":tests:uniffi:proc-macro")
+    include(":tests:uniffi:rust-buffer-pool")
+    include(":tests:uniffi:rust-panics")
     include(":tests:uniffi:simple-fns")
     include(":tests:uniffi:simple-iface")
     include(":tests:uniffi:struct-default-values")
+    include(":tests:uniffi:suspend-sync-calls")
+    include(":tests:uniffi:timestamp-type")
     include(":tests:uniffi:trait-methods")
     include(":tests:uniffi:type-limits")
+    include(":tests:uniffi:unknown-enum-variants")
 }
 // Run :tests:uniffi:ext-types separately
 if (ext.propertyIsTrue("gobley.projects.uniffiTests.extTypes")) {
@@ -73,6 +91,7 @@ if (ext.propertyIsTrue("gobley.projects.uniffiTests.extTypes")) {
 // Run :tests:uniffi:futures separately
 if (ext.propertyIsTrue("gobley.projects.uniffiTests.futures")) {
     include(":tests:uniffi:futures")
+    include(":tests:uniffi:jvm-ffm:futures")
 }
 
 if (ext.propertyIsTrue("gobley.projects.examples.app")) {
@@ -0,0 +1,73 @@
+import org.jetbrains.kotlin.gradle.dsl.JvmTarget
+
+plugins {
+    kotlin("multiplatform")
+    id("dev.gobley.cargo")
+    id("dev.gobley.uniffi")
+    alias(libs.plugins.kotlin.atomicfu)
+    alias(libs.plugins.android.library)
+}
+
+uniffi {
+    bindgenFromPath(rootProject.layout.projectDirectory.dir("crates/gobley-uniffi-bindgen"))
+    // The JNI shim is compiled into the Android libraries, so the bindings are generated from the
+    // library built for the JVM.
+    generateFromLibrary {
+        namespace = "android_jni"
+    }
+}
+
+kotlin {
+    explicitApi()
+    jvmToolchain(17)
+    jvm()
+    androidTarget {
+        compilerOptions {
+            jvmTarget = JvmTarget.JVM_17
+        }
+    }
+
+    sourceSets {
+        commonTest {
+            dependencies {
+                implementation(kotlin("test"))
+                implementation(libs.kotlinx.coroutines.test)
+                implementation(libs.kotest.assertions.core)
+            }
+        }
+        androidInstrumentedTest {
+            dependencies {
+                implementation(libs.junit)
+                implementation(libs.androidx.test.core)
+                implementation(libs.androidx.test.runner)
+                implementation(libs.kotest.assertions.core)
+            }
+        }
+    }
+}
+
+android {
+    namespace = "dev.gobley.uniffi.tests.uniffi.androidjni"
+    compileSdk = libs.versions.android.compileSdk.get().toInt()
+
+    defaultConfig {
+        minSdk = 29
+        testInstrumentationRunner = "androidx.test.runner.AndroidJUnitRunner"
+        ndk.abiFilters.add("arm64-v8a")
+    }
+
+    packaging {
+        resources {
+            excludes += "/META-INF/{AL2.0,LGPL2.1}"
+        }
+    }
+
+    compileOptions {
+        sourceCompatibility = JavaVersion.VERSION_17
+        targetCompatibility = JavaVersion.VERSION_17
+    }
+}
+
+tasks.withType<Test> {
+    useJUnitPlatform()
+}
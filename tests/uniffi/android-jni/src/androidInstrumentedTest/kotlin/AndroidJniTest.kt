@@ -0,0 +1,46 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import android_jni.*
+import io.kotest.matchers.*
+import org.junit.Test
+
+// Calls the library through the JNI shim compiled into it by the build script.
+class AndroidJniTest {
+    @Test
+    fun functions() {
+        add(2, 3) shouldBe 5
+        reverse(byteArrayOf(1, 2, 3)) shouldBe byteArrayOf(3, 2, 1)
+    }
+
+    @Test
+    fun callbacksFromRustThreads() {
+        val listener = object : Listener {
+            override fun onValue(value: Long): Long = value * 2
+        }
+        Notifier(listener).use { notifier ->
+            repeat(100) {
+                notifier.notifyFromThreads(8) shouldBe 56
+            }
+        }
+    }
+
+    @Test
+    fun callbacksOfSeveralListeners() {
+        // Each listener is upcalled through the same forwarding function of the vtable.
+        val notifiers = List(4) { index ->
+            Notifier(object : Listener {
+                override fun onValue(value: Long): Long = value + index
+            })
+        }
+        repeat(100) {
+            notifiers.forEachIndexed { index, notifier ->
+                notifier.notifyFromThreads(8) shouldBe 28L + 8 * index
+            }
+        }
+        notifiers.forEach { it.close() }
+    }
+}
@@ -0,0 +1,51 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+use std::thread;
+
+#[uniffi::export]
+fn add(lhs: i64, rhs: i64) -> i64 {
+    lhs + rhs
+}
+
+#[uniffi::export]
+fn reverse(bytes: Vec<u8>) -> Vec<u8> {
+    bytes.into_iter().rev().collect()
+}
+
+#[uniffi::export(with_foreign)]
+pub trait Listener: Send + Sync {
+    fn on_value(&self, value: i64) -> i64;
+}
+
+#[derive(uniffi::Object)]
+pub struct Notifier {
+    listener: Arc<dyn Listener>,
+}
+
+#[uniffi::export]
+impl Notifier {
+    #[uniffi::constructor]
+    fn new(listener: Arc<dyn Listener>) -> Self {
+        Self { listener }
+    }
+
+    /// Calls the listener from `threads` threads created by Rust at the same time, returning the
+    /// sum of the results.
+    fn notify_from_threads(&self, threads: i64) -> i64 {
+        let handles: Vec<_> = (0..threads)
+            .map(|value| {
+                let listener = self.listener.clone();
+                thread::spawn(move || listener.on_value(value))
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).sum()
+    }
+}
+
+#[cfg(target_os = "android")]
+include!(concat!(env!("OUT_DIR"), "/uniffi_jni_android_jni.rs"));
+
+uniffi::setup_scaffolding!("android_jni");
@@ -0,0 +1,29 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import android_jni.*
+import io.kotest.matchers.*
+import kotlin.test.*
+
+// The JVM bindings still use JNA, and are generated from the library built for the JVM, which
+// doesn't contain the JNI shim.
+class AndroidJniCommonTest {
+    @Test
+    fun functions() {
+        add(2, 3) shouldBe 5
+        reverse(byteArrayOf(1, 2, 3)) shouldBe byteArrayOf(3, 2, 1)
+    }
+
+    @Test
+    fun callbacksFromRustThreads() {
+        val listener = object : Listener {
+            override fun onValue(value: Long): Long = value * 2
+        }
+        Notifier(listener).use { notifier ->
+            notifier.notifyFromThreads(8) shouldBe 56
+        }
+    }
+}
@@ -0,0 +1,65 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum CounterError {
+    #[error("overflow")]
+    Overflow,
+}
+
+#[uniffi::export]
+fn add(a: u32, b: u32) -> Result<u32, CounterError> {
+    a.checked_add(b).ok_or(CounterError::Overflow)
+}
+
+#[uniffi::export(callback_interface)]
+pub trait Listener: Send + Sync {
+    fn on_value(&self, value: u32);
+}
+
+#[derive(uniffi::Object)]
+pub struct Counter {
+    value: std::sync::atomic::AtomicU32,
+}
+
+#[uniffi::export]
+impl Counter {
+    #[uniffi::constructor]
+    fn new() -> Self {
+        Self {
+            value: std::sync::atomic::AtomicU32::new(0),
+        }
+    }
+
+    fn increment(&self, listener: Box<dyn Listener>) {
+        let value = self
+            .value
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+            + 1;
+        listener.on_value(value);
+    }
+}
+
+// Implemented by `ShapeImpl` in Kotlin, which must report the calls as `Shape`.
+#[uniffi::export(with_foreign)]
+pub trait Shape: Send + Sync {
+    fn area(&self) -> u32;
+}
+
+struct Square {
+    side: u32,
+}
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.side * self.side
+    }
+}
+
+#[uniffi::export]
+fn make_square(side: u32) -> std::sync::Arc<dyn Shape> {
+    std::sync::Arc::new(Square { side })
+}
+
+uniffi::include_scaffolding!("call-instrumentation");
@@ -0,0 +1,79 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import call_instrumentation.*
+import io.kotest.assertions.throwables.*
+import io.kotest.matchers.*
+import io.kotest.matchers.types.*
+import kotlin.test.*
+
+class CallInstrumentationTest {
+    private val events = mutableListOf<UniffiCallEvent>()
+
+    @BeforeTest
+    fun setUp() {
+        uniffiCallInterceptor = { events.add(it) }
+    }
+
+    @AfterTest
+    fun tearDown() {
+        uniffiCallInterceptor = null
+    }
+
+    @Test
+    fun testTopLevelFunctions() {
+        add(1u, 2u) shouldBe 3u
+        shouldThrow<CounterException.Overflow> { add(UInt.MAX_VALUE, 1u) }
+
+        events.map { it.functionName } shouldBe listOf("add", "add")
+        events[0].objectType shouldBe null
+        events[0].exception shouldBe null
+        events[1].exception.shouldBeInstanceOf<CounterException.Overflow>()
+    }
+
+    @Test
+    fun testInterceptorExceptions() {
+        val interceptorException = IllegalStateException("interceptor")
+        uniffiCallInterceptor = { throw interceptorException }
+
+        // Without an exception from the call, the caller gets the exception of the interceptor.
+        shouldThrow<IllegalStateException> { add(1u, 2u) } shouldBeSameInstanceAs interceptorException
+
+        // Otherwise the exception of the call is kept.
+        val exception = shouldThrow<CounterException.Overflow> { add(UInt.MAX_VALUE, 1u) }
+        exception.suppressedExceptions shouldBe listOf(interceptorException)
+    }
+
+    @Test
+    fun testObjectsAndCallbacks() {
+        val values = mutableListOf<UInt>()
+        Counter().use { counter ->
+            counter.increment(object : Listener {
+                override fun onValue(value: UInt) {
+                    values.add(value)
+                }
+            })
+        }
+
+        values shouldBe listOf(1u)
+        events.map { "${it.objectType}.${it.functionName} ${it.isCallback}" } shouldBe listOf(
+            "Counter.Counter false",
+            "Listener.onValue true",
+            "Counter.increment false",
+        )
+    }
+
+    @Test
+    fun testTraitObjects() {
+        makeSquare(3u).area() shouldBe 9u
+
+        // The calls are reported with the name of the trait rather than the class implementing it.
+        events.map { "${it.objectType}.${it.functionName}" } shouldBe listOf(
+            "null.makeSquare",
+            "Shape.area",
+        )
+    }
+}
@@ -0,0 +1,10 @@
+plugins {
+    id("uniffi-tests-from-library")
+}
+
+// The JVM tests check the .def files written for Kotlin/Native.
+tasks.withType<Test>().configureEach {
+    val cinteropDirectory = layout.buildDirectory.dir("generated/uniffi/nativeInterop/cinterop")
+    dependsOn("generateDummyDefFile")
+    systemProperty("gobley.fixture.cinteropDirectory", cinteropDirectory.get().asFile.path)
+}
@@ -0,0 +1,10 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[uniffi::export]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+uniffi::include_scaffolding!("cinterop-def");
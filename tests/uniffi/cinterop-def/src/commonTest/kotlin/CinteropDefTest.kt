@@ -0,0 +1,17 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import cinterop_def.*
+import io.kotest.matchers.*
+import kotlin.test.*
+
+class CinteropDefTest {
+    @Test
+    fun testBindingsWork() {
+        // On Kotlin/Native, the test executable is linked with the linker options of the .def file.
+        add(1, 2) shouldBe 3
+    }
+}
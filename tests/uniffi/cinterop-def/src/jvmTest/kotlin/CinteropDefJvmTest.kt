@@ -0,0 +1,33 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import io.kotest.matchers.*
+import java.io.File
+import kotlin.test.*
+
+class CinteropDefJvmTest {
+    private val cinteropDirectory = File(System.getProperty("gobley.fixture.cinteropDirectory"))
+
+    @Test
+    fun testGeneratedDefFile() {
+        val defFile = cinteropDirectory.resolve("headers/cinterop_def/cinterop_def.def")
+        defFile.readLines() shouldBe listOf(
+            "headers = cinterop_def.h",
+            "package = cinterop_def.cinterop",
+            "staticLibraries = libgobley_fixture_cinterop_def.a",
+            "libraryPaths = cinterop-def-fixture/lib",
+            "compilerOpts = -DCINTEROP_DEF_FIXTURE",
+            "linkerOpts = -Lcinterop-def-fixture/lib",
+        )
+    }
+
+    @Test
+    fun testDefFileUsedByGradle() {
+        // The Gradle plugin links the Rust library itself, so only the linker options are kept.
+        val defFile = cinteropDirectory.resolve("dummy.def")
+        defFile.readLines() shouldBe listOf("linkerOpts = -Lcinterop-def-fixture/lib")
+    }
+}
@@ -0,0 +1,70 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PENDING_FUTURE_STARTED: AtomicBool = AtomicBool::new(false);
+static PENDING_FUTURE_DROPPED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum DivisionError {
+    #[error("division by zero")]
+    DivisionByZero,
+}
+
+#[uniffi::export]
+async fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[uniffi::export]
+async fn divide(a: i32, b: i32) -> Result<i32, DivisionError> {
+    a.checked_div(b).ok_or(DivisionError::DivisionByZero)
+}
+
+// Sets `PENDING_FUTURE_DROPPED` when the future of `wait_forever` is dropped.
+struct DropGuard;
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        PENDING_FUTURE_DROPPED.store(true, Ordering::SeqCst);
+    }
+}
+
+#[uniffi::export]
+async fn wait_forever() {
+    let _guard = DropGuard;
+    PENDING_FUTURE_STARTED.store(true, Ordering::SeqCst);
+    std::future::pending::<()>().await
+}
+
+// Whether the future of `wait_forever` has been polled, so cancelling it drops `DropGuard`.
+#[uniffi::export]
+fn pending_future_started() -> bool {
+    PENDING_FUTURE_STARTED.load(Ordering::SeqCst)
+}
+
+#[uniffi::export]
+fn pending_future_dropped() -> bool {
+    PENDING_FUTURE_DROPPED.load(Ordering::SeqCst)
+}
+
+#[derive(uniffi::Object)]
+pub struct Multiplier {
+    factor: i32,
+}
+
+#[uniffi::export]
+impl Multiplier {
+    #[uniffi::constructor]
+    fn new(factor: i32) -> Self {
+        Self { factor }
+    }
+
+    async fn multiply(&self, value: i32) -> i32 {
+        value * self.factor
+    }
+}
+
+uniffi::include_scaffolding!("completable-futures");
@@ -0,0 +1,56 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import completable_futures.*
+import io.kotest.assertions.throwables.shouldThrow
+import io.kotest.matchers.shouldBe
+import io.kotest.matchers.types.shouldBeInstanceOf
+import java.util.concurrent.CancellationException
+import java.util.concurrent.ExecutionException
+import java.util.concurrent.TimeUnit
+import kotlin.test.Test
+
+class CompletableFuturesTest {
+    @Test
+    fun functionsCompleteTheFuture() {
+        addAsync(1, 2).get(10, TimeUnit.SECONDS) shouldBe 3
+    }
+
+    @Test
+    fun methodsCompleteTheFuture() {
+        Multiplier(3).use { multiplier ->
+            multiplier.multiplyAsync(4).get(10, TimeUnit.SECONDS) shouldBe 12
+        }
+    }
+
+    @Test
+    fun errorsCompleteTheFutureExceptionally() {
+        divideAsync(6, 3).get(10, TimeUnit.SECONDS) shouldBe 2
+        val exception = shouldThrow<ExecutionException> {
+            divideAsync(1, 0).get(10, TimeUnit.SECONDS)
+        }
+        exception.cause.shouldBeInstanceOf<DivisionException.DivisionByZero>()
+    }
+
+    @Test
+    fun cancellingTheFutureCancelsTheRustFuture() {
+        val future = waitForeverAsync()
+        // Cancelling before the Rust future is polled wouldn't create the guard to drop.
+        waitUntil { pendingFutureStarted() }
+        pendingFutureStarted() shouldBe true
+        future.cancel(true) shouldBe true
+        shouldThrow<CancellationException> {
+            future.get(10, TimeUnit.SECONDS)
+        }
+        waitUntil { pendingFutureDropped() }
+        pendingFutureDropped() shouldBe true
+    }
+
+    private fun waitUntil(condition: () -> Boolean) {
+        val deadline = System.nanoTime() + TimeUnit.SECONDS.toNanos(10)
+        while (!condition() && System.nanoTime() < deadline) {
+            Thread.sleep(10)
+        }
+    }
+}
@@ -0,0 +1,14 @@
+plugins {
+    id("uniffi-tests-from-library")
+}
+
+kotlin {
+    sourceSets {
+        commonMain {
+            dependencies {
+                implementation(libs.ktor.http)
+                implementation(libs.kotlinx.datetime)
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The presets only need the values to be passed as strings, so plain wrappers stand in for the
+// types of `uuid`, `url`, `chrono` and `time` here.
+pub struct Uuid(String);
+pub struct Url(String);
+pub struct LocalDate(String);
+pub struct DateTime(String);
+
+uniffi::custom_newtype!(Uuid, String);
+uniffi::custom_newtype!(Url, String);
+uniffi::custom_newtype!(LocalDate, String);
+uniffi::custom_newtype!(DateTime, String);
+
+#[uniffi::export]
+fn nil_uuid() -> Uuid {
+    Uuid(String::from("00000000-0000-0000-0000-000000000000"))
+}
+
+#[uniffi::export]
+fn uuid_to_string(uuid: Uuid) -> String {
+    uuid.0
+}
+
+#[uniffi::export]
+fn make_url(host: String) -> Url {
+    Url(format!("https://{host}/"))
+}
+
+#[uniffi::export]
+fn url_to_string(url: Url) -> String {
+    url.0
+}
+
+#[uniffi::export]
+fn new_year(year: i32) -> LocalDate {
+    LocalDate(format!("{year:04}-01-01"))
+}
+
+#[uniffi::export]
+fn local_date_to_string(date: LocalDate) -> String {
+    date.0
+}
+
+#[uniffi::export]
+fn unix_epoch() -> DateTime {
+    DateTime(String::from("1970-01-01T00:00:00Z"))
+}
+
+#[uniffi::export]
+fn date_time_to_string(date_time: DateTime) -> String {
+    date_time.0
+}
+
+uniffi::include_scaffolding!("custom-type-presets");
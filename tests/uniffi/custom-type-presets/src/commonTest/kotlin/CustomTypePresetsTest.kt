@@ -0,0 +1,42 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import custom_type_presets.*
+import io.kotest.matchers.*
+import kotlin.test.*
+import kotlin.uuid.ExperimentalUuidApi
+
+class CustomTypePresetsTest {
+    @OptIn(ExperimentalUuidApi::class)
+    @Test
+    fun testUuid() {
+        val uuid: kotlin.uuid.Uuid = nilUuid()
+        uuid shouldBe kotlin.uuid.Uuid.NIL
+        val random = kotlin.uuid.Uuid.random()
+        uuidToString(random) shouldBe random.toString()
+    }
+
+    @Test
+    fun testUrl() {
+        val url: io.ktor.http.Url = makeUrl("example.com")
+        url.host shouldBe "example.com"
+        urlToString(io.ktor.http.Url("https://gobley.dev/docs")) shouldBe "https://gobley.dev/docs"
+    }
+
+    @Test
+    fun testLocalDate() {
+        val date: kotlinx.datetime.LocalDate = newYear(2025)
+        date shouldBe kotlinx.datetime.LocalDate(2025, 1, 1)
+        localDateToString(kotlinx.datetime.LocalDate(2024, 2, 29)) shouldBe "2024-02-29"
+    }
+
+    @Test
+    fun testExplicitValuesOverridePreset() {
+        val timestamp: kotlinx.datetime.Instant = unixEpoch()
+        timestamp shouldBe kotlinx.datetime.Instant.fromEpochSeconds(0)
+        dateTimeToString(timestamp) shouldBe "1970-01-01T00:00:00+00:00"
+    }
+}
@@ -0,0 +1,35 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+pub struct Url(String);
+
+uniffi::custom_type!(Url, String, {
+    lower: |url| url.0,
+    try_lift: |val| Ok(Url(val)),
+});
+
+#[uniffi::export]
+fn make_url(host: String) -> Url {
+    Url(format!("https://{host}/"))
+}
+
+#[uniffi::export]
+fn url_host(url: Url) -> String {
+    let without_scheme = url
+        .0
+        .split_once("://")
+        .map_or(url.0.as_str(), |(_, rest)| rest);
+    without_scheme
+        .split(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[uniffi::export]
+fn url_list(hosts: Vec<String>) -> Vec<Url> {
+    hosts.into_iter().map(make_url).collect()
+}
+
+uniffi::include_scaffolding!("custom-types-per-target");
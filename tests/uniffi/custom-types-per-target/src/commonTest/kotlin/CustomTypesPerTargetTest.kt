@@ -0,0 +1,18 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import custom_types_per_target.*
+import io.kotest.matchers.*
+import kotlin.test.*
+
+class CustomTypesPerTargetTest {
+    @Test
+    fun testRoundTrip() {
+        // `Url` is an expect class in commonMain, so only the functions can create one here.
+        urlHost(makeUrl("example.com")) shouldBe "example.com"
+        urlList(listOf("a.example", "b.example")).map(::urlHost) shouldBe listOf("a.example", "b.example")
+    }
+}
@@ -0,0 +1,19 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import custom_types_per_target.*
+import io.kotest.matchers.*
+import java.net.URI
+import kotlin.test.*
+
+class CustomTypesPerTargetJvmTest {
+    @Test
+    fun testJvmOverride() {
+        val url: URI = makeUrl("example.com")
+        url shouldBe URI("https://example.com/")
+        urlHost(URI("https://gobley.dev/docs")) shouldBe "gobley.dev"
+    }
+}
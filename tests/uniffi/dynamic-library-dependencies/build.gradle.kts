@@ -1,4 +1,8 @@
+import gobley.gradle.GobleyHost
 import gobley.gradle.cargo.dsl.jvm
+import gobley.gradle.rust.targets.RustTarget
+import org.jetbrains.kotlin.gradle.plugin.mpp.KotlinNativeTarget
+import org.jetbrains.kotlin.gradle.targets.native.tasks.KotlinNativeTest
 
 plugins {
     id("uniffi-tests-from-library")
@@ -9,3 +13,21 @@ cargo {
         dynamicLibraries.add("gobley_fixture_dynamic_library_dependencies_the_dependency")
     }
 }
+
+// The Kotlin/Native test executables load the dependency from the Cargo output directory, where
+// build.rs copies it to.
+kotlin.targets.withType<KotlinNativeTarget>().configureEach {
+    val outputDirectory = cargo.cargoPackage.map {
+        it.outputDirectory(target = RustTarget(konanTarget)).asFile
+    }
+    tasks.withType<KotlinNativeTest>().matching { it.name == "${name}Test" }.configureEach {
+        val platform = GobleyHost.Platform.current
+        val variableName = when (platform) {
+            GobleyHost.Platform.Windows -> "PATH"
+            GobleyHost.Platform.MacOS -> "DYLD_LIBRARY_PATH"
+            GobleyHost.Platform.Linux -> "LD_LIBRARY_PATH"
+        }
+        val paths = listOfNotNull(outputDirectory.get().path, System.getenv(variableName))
+        environment(variableName, paths.joinToString(platform.pathSeparator))
+    }
+}
@@ -72,6 +72,34 @@ fn main() {
         .unwrap();
     }
 
+    if env::var("CARGO_CFG_TARGET_OS").unwrap() == "windows"
+        && env::var("CARGO_CFG_TARGET_ENV").unwrap() == "gnu"
+    {
+        // Kotlin/Native links against the import library on MinGW
+        let library_filename = format!(
+            "lib{}.dll.a",
+            library_filename.strip_suffix(".dll").unwrap()
+        );
+        fs::copy(
+            build_output_directory.join(&library_filename),
+            PathBuf::from(&out_dir).join(&library_filename),
+        )
+        .unwrap();
+    }
+
+    // Kotlin/Native test executables can't find the build script output directory, so copy the
+    // dependency to the output directory of the profile as well, i.e., target/<triple>/<profile>.
+    let profile_output_directory = PathBuf::from(&out_dir)
+        .ancestors()
+        .nth(3)
+        .unwrap()
+        .to_owned();
+    fs::copy(
+        build_output_directory.join(&library_filename),
+        profile_output_directory.join(&library_filename),
+    )
+    .unwrap();
+
     // Link the dependency
     println!("cargo::rustc-link-search={out_dir}");
 }
@@ -0,0 +1,18 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+package error_supertypes.support
+
+// Supertypes of the errors, named in uniffi.toml.
+
+interface Reportable {
+    val shouldReport: Boolean get() = true
+}
+
+open class AppException : Exception {
+    constructor() : super()
+    constructor(message: String) : super(message)
+}
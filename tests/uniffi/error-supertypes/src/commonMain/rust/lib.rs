@@ -0,0 +1,34 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum NetworkError {
+    #[error("timed out after {seconds} seconds")]
+    Timeout { seconds: u32 },
+    #[error("offline")]
+    Offline,
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum StorageError {
+    #[error("disk full")]
+    DiskFull,
+}
+
+#[uniffi::export]
+fn fetch(offline: bool) -> Result<(), NetworkError> {
+    if offline {
+        Err(NetworkError::Offline)
+    } else {
+        Err(NetworkError::Timeout { seconds: 30 })
+    }
+}
+
+#[uniffi::export]
+fn store() -> Result<(), StorageError> {
+    Err(StorageError::DiskFull)
+}
+
+uniffi::include_scaffolding!("error-supertypes");
@@ -0,0 +1,38 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import error_supertypes.*
+import error_supertypes.support.*
+import io.kotest.assertions.throwables.*
+import io.kotest.matchers.*
+import io.kotest.matchers.types.*
+import kotlin.test.*
+
+class ErrorSupertypesTest {
+    @Test
+    fun testInterfaces() {
+        val exception = shouldThrow<NetworkException.Timeout> {
+            fetch(false)
+        }
+        exception.seconds shouldBe 30u
+        exception.shouldBeInstanceOf<Reportable>()
+        exception.shouldReport shouldBe true
+        // Each target extends its own superclass through the base class.
+        exception.shouldBeInstanceOf<NetworkExceptionBase>()
+        shouldThrow<NetworkException.Offline> {
+            fetch(true)
+        }.shouldBeInstanceOf<Reportable>()
+    }
+
+    @Test
+    fun testSharedSuperclass() {
+        val exception = shouldThrow<StorageException.DiskFull> {
+            store()
+        }
+        exception.shouldBeInstanceOf<AppException>()
+        exception.message shouldBe "disk full"
+    }
+}
@@ -0,0 +1,20 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import error_supertypes.*
+import io.kotest.assertions.throwables.*
+import io.kotest.matchers.types.*
+import java.io.IOException
+import kotlin.test.*
+
+class ErrorSupertypesJvmTest {
+    @Test
+    fun testJvmSuperclass() {
+        shouldThrow<IOException> {
+            fetch(true)
+        }.shouldBeInstanceOf<NetworkException.Offline>()
+    }
+}
@@ -0,0 +1,3 @@
+plugins {
+    id("uniffi-tests-from-library")
+}
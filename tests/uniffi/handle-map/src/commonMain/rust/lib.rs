@@ -0,0 +1,18 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The tests exercise `UniffiHandleMap` of the generated bindings directly, so the library only
+// needs something to generate bindings for.
+
+#[uniffi::export(callback_interface)]
+pub trait Listener: Send + Sync {
+    fn on_value(&self, value: u32);
+}
+
+#[uniffi::export]
+fn notify(listener: Box<dyn Listener>, value: u32) {
+    listener.on_value(value);
+}
+
+uniffi::include_scaffolding!("handle-map");
@@ -0,0 +1,87 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import handle_map.*
+import io.kotest.assertions.throwables.shouldThrow
+import io.kotest.matchers.shouldBe
+import io.kotest.matchers.shouldNotBe
+import io.kotest.matchers.types.shouldNotBeInstanceOf
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.async
+import kotlinx.coroutines.awaitAll
+import kotlinx.coroutines.test.runTest
+import kotlinx.coroutines.withContext
+import kotlin.test.Test
+
+class HandleMapTest {
+    @Test
+    fun removedHandlesAreStale() {
+        val map = UniffiHandleMap<String>()
+        val handle = map.insert("first")
+        map.get(handle) shouldBe "first"
+        map.remove(handle) shouldBe "first"
+
+        shouldThrow<UniffiStaleHandleException> { map.get(handle) }.handle shouldBe handle
+        shouldThrow<UniffiStaleHandleException> { map.remove(handle) }.handle shouldBe handle
+        map.size shouldBe 0
+    }
+
+    @Test
+    fun staleHandlesNeverResolveToNewObjects() {
+        val map = UniffiHandleMap<Int>()
+        val staleHandle = map.insert(-1)
+        map.remove(staleHandle)
+        // Reuses the same slots over and over, like the continuations of a polled future.
+        repeat(100_000) { value ->
+            val handle = map.insert(value)
+            handle shouldNotBe staleHandle
+            shouldThrow<UniffiStaleHandleException> { map.get(staleHandle) }
+            map.remove(handle) shouldBe value
+        }
+    }
+
+    @Test
+    fun invalidHandlesAreRejected() {
+        val map = UniffiHandleMap<String>()
+        map.insert("first")
+        shouldThrow<InternalException> { map.get(0L) }.shouldNotBeInstanceOf<UniffiStaleHandleException>()
+        shouldThrow<InternalException> { map.get(-1L) }.shouldNotBeInstanceOf<UniffiStaleHandleException>()
+        shouldThrow<InternalException> { map.remove(Long.MAX_VALUE) }.shouldNotBeInstanceOf<UniffiStaleHandleException>()
+    }
+
+    @Test
+    fun concurrentInsertAndRemove() = runTest {
+        val map = UniffiHandleMap<Pair<Int, Int>>()
+        val handles = withContext(Dispatchers.Default) {
+            (0 until 8).map { worker ->
+                async {
+                    (0 until 10_000).map { index ->
+                        val value = worker to index
+                        val handle = map.insert(value)
+                        map.get(handle) shouldBe value
+                        if (index % 2 == 0) {
+                            map.remove(handle) shouldBe value
+                        }
+                        handle
+                    }
+                }
+            }.awaitAll().flatten()
+        }
+        map.size shouldBe 8 * 5_000
+        // The handles of the objects still in the map are unique.
+        handles.filterIndexed { index, _ -> index % 2 == 1 }.toSet().size shouldBe 8 * 5_000
+    }
+
+    @Test
+    fun callbacksReleaseTheirHandles() {
+        var received = 0u
+        notify(object : Listener {
+            override fun onValue(value: UInt) {
+                received = value
+            }
+        }, 42u)
+        received shouldBe 42u
+        FfiConverterTypeListener.handleMap.size shouldBe 0
+    }
+}
@@ -0,0 +1,50 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+#[derive(uniffi::Record)]
+pub struct Greeting {
+    pub text: String,
+    pub count: u32,
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum GreetingError {
+    #[error("the name is empty")]
+    EmptyName,
+}
+
+#[uniffi::export]
+fn greet(name: String) -> Result<String, GreetingError> {
+    if name.is_empty() {
+        return Err(GreetingError::EmptyName);
+    }
+    Ok(format!("Hello, {name}!"))
+}
+
+#[derive(uniffi::Object)]
+pub struct Greeter {
+    count: AtomicU32,
+}
+
+#[uniffi::export]
+impl Greeter {
+    #[uniffi::constructor]
+    fn new() -> Self {
+        Self {
+            count: AtomicU32::new(0),
+        }
+    }
+
+    fn greet(&self, name: String) -> Greeting {
+        let count = self.count.fetch_add(1, Ordering::Relaxed) + 1;
+        Greeting {
+            text: format!("Hello, {name}!"),
+            count,
+        }
+    }
+}
+
+uniffi::include_scaffolding!("jvm-common-source-set");
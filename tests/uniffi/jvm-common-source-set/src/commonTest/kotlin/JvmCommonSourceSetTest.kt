@@ -0,0 +1,28 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import io.kotest.assertions.throwables.shouldThrow
+import io.kotest.matchers.*
+import jvm_common_source_set.*
+import kotlin.test.*
+
+// On the JVM, everything but the cleaner and the library loading code is compiled from the
+// `jvmCommonMain` source set.
+class JvmCommonSourceSetTest {
+    @Test
+    fun testFunctions() {
+        greet("Gobley") shouldBe "Hello, Gobley!"
+        shouldThrow<GreetingException.EmptyName> { greet("") }
+    }
+
+    @Test
+    fun testObjects() {
+        Greeter().use { greeter ->
+            greeter.greet("Kotlin") shouldBe Greeting("Hello, Kotlin!", 1u)
+            greeter.greet("Rust") shouldBe Greeting("Hello, Rust!", 2u)
+        }
+    }
+}
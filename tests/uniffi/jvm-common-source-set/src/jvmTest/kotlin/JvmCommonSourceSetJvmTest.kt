@@ -0,0 +1,18 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import io.kotest.matchers.*
+import jvm_common_source_set.*
+import kotlin.test.*
+
+class JvmCommonSourceSetJvmTest {
+    @Test
+    fun testClassPathHelpersAreGeneratedForTheJvm() {
+        // The helpers stay out of the source set shared with Android, which lacks `java.nio.file`
+        // before API level 26.
+        uniffiExtractLibraryFromClassPath("gobley_fixture_missing_library") shouldBe null
+    }
+}
@@ -0,0 +1,13 @@
+plugins {
+    id("uniffi-tests-jvm-ffm")
+}
+
+kotlin {
+    sourceSets {
+        commonMain {
+            dependencies {
+                implementation(project(":tests:uniffi:coverall-pure-kotlin-dep"))
+            }
+        }
+    }
+}
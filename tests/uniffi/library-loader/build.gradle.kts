@@ -0,0 +1,51 @@
+import gobley.gradle.GobleyHost
+import gobley.gradle.rust.CrateType
+
+plugins {
+    id("uniffi-tests-from-library")
+}
+
+// The library name is chosen once per JVM, so each way of choosing it is tested in its own test
+// task. The overrides point to a missing library, so the tests can tell which one was used.
+val missingLibraryName = "gobley_fixture_missing_library"
+val libraryFile = cargo.cargoPackage.map {
+    val rustTarget = GobleyHost.current.rustTarget
+    val fileName = rustTarget.outputFileName(it.libraryCrateName, CrateType.SystemDynamicLibrary)!!
+    it.outputDirectory(target = rustTarget).file(fileName).asFile.path
+}
+
+val jvmTest by tasks.existing(Test::class) {
+    filter {
+        includeTestsMatching("LibraryLoaderTest")
+    }
+    systemProperty("gobley.fixture.libraryFile", libraryFile.get())
+    // The loader hook takes priority over the overrides.
+    systemProperty("uniffi.component.library_loader.libraryOverride", missingLibraryName)
+    environment("UNIFFI_COMPONENT_LIBRARY_LOADER_LIBRARY_OVERRIDE", missingLibraryName)
+}
+
+val jvmLibraryOverridePropertyTest by tasks.registering(Test::class) {
+    testClassesDirs = jvmTest.get().testClassesDirs
+    classpath = jvmTest.get().classpath
+    filter {
+        includeTestsMatching("LibraryOverridePropertyTest")
+    }
+    systemProperty("gobley.fixture.missingLibraryName", missingLibraryName)
+    systemProperty("uniffi.component.library_loader.libraryOverride", missingLibraryName)
+    // The system property takes priority over the environment variable.
+    environment("UNIFFI_COMPONENT_LIBRARY_LOADER_LIBRARY_OVERRIDE", libraryFile.get())
+}
+
+val jvmLibraryOverrideEnvironmentTest by tasks.registering(Test::class) {
+    testClassesDirs = jvmTest.get().testClassesDirs
+    classpath = jvmTest.get().classpath
+    filter {
+        includeTestsMatching("LibraryOverrideEnvironmentTest")
+    }
+    systemProperty("gobley.fixture.missingLibraryName", missingLibraryName)
+    environment("UNIFFI_COMPONENT_LIBRARY_LOADER_LIBRARY_OVERRIDE", missingLibraryName)
+}
+
+tasks.named("check") {
+    dependsOn(jvmLibraryOverridePropertyTest, jvmLibraryOverrideEnvironmentTest)
+}
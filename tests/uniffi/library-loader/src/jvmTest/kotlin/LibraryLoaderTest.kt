@@ -0,0 +1,28 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import io.kotest.matchers.*
+import library_loader.*
+import java.io.File
+import kotlin.test.*
+
+// Run by the jvmTest task, which also sets the overrides to a missing library.
+class LibraryLoaderTest {
+    @Test
+    fun testLoaderHookChoosesTheLibrary() {
+        val libraryFile = File(System.getProperty("gobley.fixture.libraryFile"))
+        val calls = mutableListOf<Pair<String, String>>()
+        uniffiLibraryLoader = { componentName, libraryName ->
+            calls += componentName to libraryName
+            libraryFile.absolutePath
+        }
+
+        add(1, 2) shouldBe 3
+        add(3, 4) shouldBe 7
+        // The library name is chosen once.
+        calls shouldBe listOf("library_loader" to "gobley_fixture_library_loader")
+    }
+}
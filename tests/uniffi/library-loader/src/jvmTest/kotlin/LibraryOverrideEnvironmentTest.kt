@@ -0,0 +1,22 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import io.kotest.assertions.throwables.*
+import io.kotest.matchers.string.*
+import library_loader.*
+import kotlin.test.*
+
+// Run by the jvmLibraryOverrideEnvironmentTest task, which sets the environment variable to a
+// missing library.
+class LibraryOverrideEnvironmentTest {
+    @Test
+    fun testEnvironmentVariableChoosesTheLibrary() {
+        val exception = shouldThrow<UnsatisfiedLinkError> {
+            add(1, 2)
+        }
+        exception.message shouldContain System.getProperty("gobley.fixture.missingLibraryName")
+    }
+}
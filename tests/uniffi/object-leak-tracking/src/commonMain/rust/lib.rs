@@ -0,0 +1,22 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(uniffi::Object)]
+pub struct Resource {
+    name: String,
+}
+
+#[uniffi::export]
+impl Resource {
+    #[uniffi::constructor]
+    fn new(name: String) -> Self {
+        Self { name }
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+uniffi::include_scaffolding!("object-leak-tracking");
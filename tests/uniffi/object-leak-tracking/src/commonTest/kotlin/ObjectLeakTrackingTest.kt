@@ -0,0 +1,45 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import io.kotest.matchers.*
+import io.kotest.matchers.collections.*
+import io.kotest.matchers.string.*
+import object_leak_tracking.*
+import kotlin.test.*
+
+class ObjectLeakTrackingTest {
+    @Test
+    fun testLiveObjectsAreRecorded() {
+        val resource = Resource("tracked")
+        val liveObject = UniffiObjectTracker.liveObjects().last()
+        liveObject.typeName shouldBe "Resource"
+        liveObject.creationStackTrace shouldContain "testLiveObjectsAreRecorded"
+        UniffiObjectTracker.dump() shouldContain "Resource created"
+
+        resource.destroy()
+        UniffiObjectTracker.liveObjects() shouldNotContain liveObject
+    }
+
+    @Test
+    fun testClosedObjectsAreNotReported() {
+        val leakHandler = UniffiObjectTracker.leakHandler
+        val leaked = mutableListOf<UniffiLiveObject>()
+        UniffiObjectTracker.leakHandler = { leaked.add(it) }
+        try {
+            Resource("closed").use { it.name() shouldBe "closed" }
+            leaked shouldBe emptyList()
+        } finally {
+            UniffiObjectTracker.leakHandler = leakHandler
+        }
+    }
+
+    @Test
+    fun testFakeObjectsAreNotRecorded() {
+        val before = UniffiObjectTracker.liveObjects()
+        Resource(NoPointer)
+        UniffiObjectTracker.liveObjects() shouldBe before
+    }
+}
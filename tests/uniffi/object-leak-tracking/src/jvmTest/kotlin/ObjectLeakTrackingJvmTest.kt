@@ -0,0 +1,45 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import io.kotest.matchers.*
+import io.kotest.matchers.string.*
+import object_leak_tracking.*
+import java.util.concurrent.CountDownLatch
+import java.util.concurrent.TimeUnit
+import kotlin.test.*
+
+class ObjectLeakTrackingJvmTest {
+    @Test
+    fun testUnreachableObjectsAreReported() {
+        val leakHandler = UniffiObjectTracker.leakHandler
+        val reported = CountDownLatch(1)
+        var leaked: UniffiLiveObject? = null
+        UniffiObjectTracker.leakHandler = { liveObject ->
+            if (liveObject.creationStackTrace.contains("createUnreachableResource")) {
+                leaked = liveObject
+                reported.countDown()
+            }
+        }
+        try {
+            createUnreachableResource()
+            // The cleaner frees the object on its own thread some time after it is collected.
+            val deadline = System.nanoTime() + TimeUnit.SECONDS.toNanos(10)
+            while (!reported.await(10, TimeUnit.MILLISECONDS) && System.nanoTime() < deadline) {
+                System.gc()
+            }
+            reported.count shouldBe 0
+            leaked?.typeName shouldBe "Resource"
+            UniffiObjectTracker.dump() shouldNotContain "createUnreachableResource"
+        } finally {
+            UniffiObjectTracker.leakHandler = leakHandler
+        }
+    }
+
+    // Creates the object in a separate frame, so no reference to it is left on the stack.
+    private fun createUnreachableResource() {
+        Resource("unreachable").name() shouldBe "unreachable"
+    }
+}
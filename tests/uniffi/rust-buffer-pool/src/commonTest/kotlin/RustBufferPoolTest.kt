@@ -0,0 +1,37 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import io.kotest.matchers.*
+import rust_buffer_pool.*
+import kotlin.test.*
+
+class RustBufferPoolTest {
+    @Test
+    fun testRoundTrip() {
+        echoPoint(Point(4, 5, 6)) shouldBe Point(4, 5, 6)
+        echoPoint(makePoint()) shouldBe Point(1, 2, 3)
+        // Larger than the pooled buffers
+        sumPoints(List(1000) { Point(it, 1, 2) }) shouldBe 502500L
+    }
+
+    @Test
+    fun testArgumentsReuseReturnedBuffers() {
+        repeat(10) { echoPoint(makePoint()) }
+
+        // Only the returned values are allocated by Rust, since each argument reuses the buffer
+        // returned by the previous call.
+        val makeAllocations = countAllocations { repeat(1000) { makePoint() } }
+        var point = makePoint()
+        val echoAllocations = countAllocations { repeat(1000) { point = echoPoint(point) } }
+        echoAllocations shouldBe makeAllocations
+    }
+
+    private inline fun countAllocations(block: () -> Unit): ULong {
+        val before = allocationCount()
+        block()
+        return allocationCount() - before
+    }
+}
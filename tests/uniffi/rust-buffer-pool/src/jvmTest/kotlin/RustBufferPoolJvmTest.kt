@@ -0,0 +1,54 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import io.kotest.matchers.*
+import io.kotest.matchers.collections.*
+import io.kotest.matchers.nulls.*
+import rust_buffer_pool.*
+import kotlin.concurrent.thread
+import kotlin.test.*
+
+class RustBufferPoolJvmTest {
+    @Test
+    fun testPoolSavesArgumentAllocations() {
+        val calls = 100
+        val point = makePoint()
+        // The pools are kept per thread, so a call on a new thread allocates its argument like
+        // the bindings without pooling do.
+        val unpooledAllocations = countAllocations {
+            repeat(calls) { thread { echoPoint(point) }.join() }
+        }
+        var pooledPoint = makePoint()
+        val pooledAllocations = countAllocations {
+            repeat(calls) { pooledPoint = echoPoint(pooledPoint) }
+        }
+        unpooledAllocations shouldBe pooledAllocations + calls.toULong()
+    }
+
+    @Test
+    fun testPoolTakesTheSmallestBufferLargeEnough() {
+        val pool = UniffiRustBufferPool()
+        val buffers = listOf(1024UL, 16UL, 256UL).map { RustBufferHelper.allocValue(it) }
+        buffers.forEach { pool.offer(it) shouldBe true }
+        val capacities = buffers.map { it.capacity }
+
+        pool.take(0UL) shouldBe null
+        val taken = pool.take(100UL).shouldNotBeNull()
+        taken.capacity shouldBe capacities.filter { it >= 100L }.min()
+        taken.len shouldBe 100L
+        pool.buffers.map { it.capacity } shouldContainExactlyInAnyOrder capacities - taken.capacity
+        pool.take(2048UL) shouldBe null
+
+        RustBufferHelper.destroy(taken)
+        pool.buffers.forEach(RustBufferHelper::destroy)
+    }
+
+    private inline fun countAllocations(block: () -> Unit): ULong {
+        val before = allocationCount()
+        block()
+        return allocationCount() - before
+    }
+}
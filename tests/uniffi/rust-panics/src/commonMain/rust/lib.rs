@@ -0,0 +1,26 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum PanicError {
+    #[error("never thrown")]
+    NeverThrown,
+}
+
+#[uniffi::export]
+fn panic_with_message(message: String) {
+    panic!("{message}");
+}
+
+#[uniffi::export]
+fn fallible_panic_with_message(message: String) -> Result<(), PanicError> {
+    panic!("{message}");
+}
+
+#[uniffi::export]
+fn do_not_panic() -> i32 {
+    42
+}
+
+uniffi::include_scaffolding!("rust-panics");
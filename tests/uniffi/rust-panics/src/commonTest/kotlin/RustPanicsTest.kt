@@ -0,0 +1,63 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import io.kotest.assertions.throwables.*
+import io.kotest.matchers.*
+import io.kotest.matchers.nulls.*
+import io.kotest.matchers.string.*
+import io.kotest.matchers.types.*
+import rust_panics.*
+import kotlin.test.*
+
+class RustPanicsTest {
+    @AfterTest
+    fun resetPanicHandler() {
+        uniffiRustPanicHandler = null
+    }
+
+    @Test
+    fun testPanicIsThrownAsRustPanicException() {
+        val exception = shouldThrow<RustPanicException> {
+            panicWithMessage("expected panic")
+        }
+        exception.shouldBeInstanceOf<InternalException>()
+        exception.message shouldBe "expected panic"
+        exception.functionName.shouldNotBeNull() shouldEndWith "fn_func_panic_with_message"
+    }
+
+    @Test
+    fun testPanicInFallibleFunction() {
+        val exception = shouldThrow<RustPanicException> {
+            falliblePanicWithMessage("expected panic in a fallible function")
+        }
+        exception.message shouldBe "expected panic in a fallible function"
+        exception.functionName.shouldNotBeNull() shouldEndWith "fn_func_fallible_panic_with_message"
+    }
+
+    @Test
+    fun testPanicHandlerIsCalledBeforeThrowing() {
+        val handled = mutableListOf<RustPanicException>()
+        uniffiRustPanicHandler = { handled += it }
+
+        doNotPanic() shouldBe 42
+        handled shouldBe emptyList()
+
+        val exception = shouldThrow<RustPanicException> {
+            panicWithMessage("reported panic")
+        }
+        handled shouldBe listOf(exception)
+    }
+
+    @Test
+    fun testPanicHandlerExceptionPropagates() {
+        uniffiRustPanicHandler = { throw IllegalStateException("handler failed: ${it.message}") }
+
+        val exception = shouldThrow<IllegalStateException> {
+            panicWithMessage("panic")
+        }
+        exception.message shouldBe "handler failed: panic"
+    }
+}
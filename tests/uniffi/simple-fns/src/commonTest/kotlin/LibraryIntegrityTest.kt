@@ -0,0 +1,53 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import io.kotest.assertions.throwables.*
+import io.kotest.matchers.*
+import io.kotest.matchers.string.*
+import simple_fns.*
+import kotlin.test.*
+
+class LibraryIntegrityTest {
+    @Test
+    fun testMatchingLibraryPasses() {
+        uniffiCheckLibraryIntegrity("/path/to/libsimple_fns.so", 29, 29, emptyList())
+    }
+
+    @Test
+    fun testContractVersionMismatch() {
+        val exception = shouldThrow<UniffiLibraryMismatchException> {
+            uniffiCheckLibraryIntegrity("/path/to/libsimple_fns.so", 29, 26, emptyList())
+        }
+        exception.message shouldContain "the bindings use version 29, but the library uses version 26"
+        exception.message shouldContain "Loaded library: /path/to/libsimple_fns.so"
+        exception.libraryPath shouldBe "/path/to/libsimple_fns.so"
+        exception.bindingsContractVersion shouldBe 29
+        exception.libraryContractVersion shouldBe 26
+    }
+
+    @Test
+    fun testChecksumMismatches() {
+        val mismatches = listOf(
+            UniffiChecksumMismatch("uniffi_simple_fns_checksum_func_get_string", 1, 2),
+            UniffiChecksumMismatch("uniffi_simple_fns_checksum_func_get_int", 3, 4),
+        )
+        val exception = shouldThrow<UniffiLibraryMismatchException> {
+            uniffiCheckLibraryIntegrity("/path/to/libsimple_fns.so", 29, 29, mismatches)
+        }
+        exception.message shouldContain "uniffi_simple_fns_checksum_func_get_string: expected 1, got 2"
+        exception.message shouldContain "uniffi_simple_fns_checksum_func_get_int: expected 3, got 4"
+        exception.message shouldContain "Loaded library: /path/to/libsimple_fns.so"
+        exception.checksumMismatches shouldBe mismatches
+    }
+
+    @Test
+    fun testUnknownLibraryPath() {
+        val exception = shouldThrow<UniffiLibraryMismatchException> {
+            uniffiCheckLibraryIntegrity(null, 29, 26, emptyList())
+        }
+        exception.message shouldContain "Loaded library: unknown"
+    }
+}
@@ -0,0 +1,67 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+static LAST_CALL_THREAD_ID: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static THREAD_ID: u64 = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+}
+
+/// An identifier of the calling thread, unique for the lifetime of the process.
+#[uniffi::export]
+pub fn current_thread_id() -> u64 {
+    THREAD_ID.with(|id| *id)
+}
+
+/// The thread the last call of `add`, `subtract` or a `Counter` method ran on.
+#[uniffi::export]
+pub fn last_call_thread_id() -> u64 {
+    LAST_CALL_THREAD_ID.load(Ordering::Relaxed)
+}
+
+fn record_call_thread() {
+    LAST_CALL_THREAD_ID.store(current_thread_id(), Ordering::Relaxed);
+}
+
+#[uniffi::export]
+pub fn add(a: u64, b: u64) -> u64 {
+    record_call_thread();
+    a + b
+}
+
+#[uniffi::export]
+pub fn subtract(a: u64, b: u64) -> u64 {
+    record_call_thread();
+    a - b
+}
+
+#[derive(uniffi::Object)]
+pub struct Counter {
+    value: AtomicU64,
+}
+
+#[uniffi::export]
+impl Counter {
+    #[uniffi::constructor]
+    fn new() -> Self {
+        Self {
+            value: AtomicU64::new(0),
+        }
+    }
+
+    fn increment(&self) -> u64 {
+        record_call_thread();
+        self.value.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn value(&self) -> u64 {
+        record_call_thread();
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+uniffi::include_scaffolding!("suspend-sync-calls");
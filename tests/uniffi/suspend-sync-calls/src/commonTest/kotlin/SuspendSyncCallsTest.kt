@@ -0,0 +1,37 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import io.kotest.matchers.shouldBe
+import io.kotest.matchers.shouldNotBe
+import kotlinx.coroutines.test.runTest
+import suspend_sync_calls.*
+import kotlin.test.Test
+
+class SuspendSyncCallsTest {
+    @Test
+    fun listedFunctionsSuspend() = runTest {
+        val callerThreadId = currentThreadId()
+        add(1u, 2u) shouldBe 3uL
+        // The call ran on `Dispatchers.Default` instead of the thread of the test.
+        lastCallThreadId() shouldNotBe callerThreadId
+    }
+
+    @Test
+    fun unlistedFunctionsStayBlocking() {
+        subtract(3u, 2u) shouldBe 1uL
+        lastCallThreadId() shouldBe currentThreadId()
+    }
+
+    @Test
+    fun listedMethodsSuspend() = runTest {
+        val callerThreadId = currentThreadId()
+        Counter().use { counter ->
+            counter.increment() shouldBe 1uL
+            lastCallThreadId() shouldNotBe callerThreadId
+            counter.increment() shouldBe 2uL
+            counter.value() shouldBe 2uL
+            lastCallThreadId() shouldBe callerThreadId
+        }
+    }
+}
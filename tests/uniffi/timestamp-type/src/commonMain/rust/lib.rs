@@ -0,0 +1,33 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::time::{Duration, SystemTime};
+
+#[derive(uniffi::Record)]
+pub struct Event {
+    pub name: String,
+    pub time: Option<SystemTime>,
+}
+
+#[uniffi::export]
+fn seconds_after_epoch(seconds: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(seconds)
+}
+
+#[uniffi::export]
+fn seconds_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[uniffi::export]
+fn make_event(name: String, seconds: u64) -> Event {
+    Event {
+        name,
+        time: Some(seconds_after_epoch(seconds)),
+    }
+}
+
+uniffi::include_scaffolding!("timestamp-type");
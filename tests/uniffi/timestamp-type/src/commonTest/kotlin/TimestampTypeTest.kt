@@ -0,0 +1,26 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+import io.kotest.matchers.*
+import kotlinx.datetime.Instant
+import timestamp_type.*
+import kotlin.test.*
+
+class TimestampTypeTest {
+    @Test
+    fun testTimestampsUseConfiguredType() {
+        val time: Instant = secondsAfterEpoch(60UL)
+        time shouldBe Instant.fromEpochSeconds(60)
+        secondsSinceEpoch(Instant.fromEpochSeconds(3600)) shouldBe 3600UL
+    }
+
+    @Test
+    fun testTimestampsInRecords() {
+        val event = makeEvent("launch", 120UL)
+        event.time shouldBe Instant.fromEpochSeconds(120)
+        event.copy(time = null).time shouldBe null
+    }
+}
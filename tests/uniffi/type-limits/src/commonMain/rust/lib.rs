@@ -41,6 +41,9 @@ fn take_string(v: String) -> String {
     assert!(str::from_utf8(v.as_bytes()).is_ok());
     v
 }
+fn take_strings(v: Vec<String>) -> Vec<String> {
+    v
+}
 fn take_bytes(v: Vec<u8>) -> Vec<u8> {
     v
 }
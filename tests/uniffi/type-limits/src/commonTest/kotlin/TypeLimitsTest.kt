@@ -19,4 +19,53 @@ class TypeLimitsTest {
         takeString("愛") shouldBe "愛"
         takeString("💖") shouldBe "💖"
     }
+
+    @Test
+    fun testSurrogatePairsRoundTrip() {
+        val strings = listOf(
+            "💖",
+            "a💖b",
+            "💖💖💖",
+            "\ud83d\udc96 at the start",
+            "at the end \ud83d\udc96",
+            // One to four bytes per code point
+            "a\u00e9\u611b\ud83d\ude00",
+            // The last code point of each encoded length
+            "\u007f\u07ff\uffff\udbff\udfff",
+        )
+        for (string in strings) {
+            takeString(string) shouldBe string
+        }
+        // Strings in a sequence are written with their length instead of filling the RustBuffer.
+        takeStrings(strings) shouldBe strings
+    }
+
+    @Test
+    fun testLoneSurrogatesAreRejected() {
+        val strings = listOf(
+            "\ud800",
+            "\udc00",
+            "a\ud83d",
+            "\ude00b",
+            // A low surrogate followed by a high surrogate is not a pair.
+            "\ude00\ud83d",
+            "\ud83d\ud83d\ude00",
+        )
+        for (string in strings) {
+            shouldThrow<CharacterCodingException> { takeString(string) }
+            shouldThrow<CharacterCodingException> { takeStrings(listOf("valid", string)) }
+        }
+    }
+
+    @Test
+    fun testLongStringsRoundTrip() {
+        // Longer than the array strings are lifted through.
+        for (length in listOf(16 * 1024 - 1, 16 * 1024, 16 * 1024 + 1, 1024 * 1024)) {
+            val ascii = "a".repeat(length)
+            takeString(ascii) shouldBe ascii
+            val emoji = "💖".repeat(length / 4)
+            takeString(emoji) shouldBe emoji
+            takeStrings(listOf(ascii, emoji)) shouldBe listOf(ascii, emoji)
+        }
+    }
 }
@@ -0,0 +1,86 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The `V2` types are the same types with a variant added, as written by a newer version of the
+// library. The tests lower them and lift the result as the older types.
+
+#[derive(uniffi::Enum)]
+pub enum Color {
+    Red,
+    Green,
+}
+
+#[derive(uniffi::Enum)]
+pub enum ColorV2 {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(uniffi::Enum)]
+pub enum Shape {
+    Circle { radius: f64 },
+    Square { side: f64 },
+}
+
+#[derive(uniffi::Enum)]
+pub enum ShapeV2 {
+    Circle { radius: f64 },
+    Square { side: f64 },
+    Rectangle { width: f64, height: f64 },
+}
+
+#[derive(uniffi::Record)]
+pub struct Drawing {
+    pub shape: Shape,
+    pub name: String,
+}
+
+#[derive(uniffi::Record)]
+pub struct DrawingV2 {
+    pub shape: ShapeV2,
+    pub name: String,
+}
+
+#[uniffi::export]
+fn echo_colors(colors: Vec<Color>) -> Vec<Color> {
+    colors
+}
+
+#[uniffi::export]
+fn echo_colors_v2(colors: Vec<ColorV2>) -> Vec<ColorV2> {
+    colors
+}
+
+#[uniffi::export]
+fn echo_shape(shape: Shape) -> Shape {
+    shape
+}
+
+#[uniffi::export]
+fn echo_shape_v2(shape: ShapeV2) -> ShapeV2 {
+    shape
+}
+
+#[uniffi::export]
+fn echo_shapes(shapes: Vec<Shape>) -> Vec<Shape> {
+    shapes
+}
+
+#[uniffi::export]
+fn echo_shapes_v2(shapes: Vec<ShapeV2>) -> Vec<ShapeV2> {
+    shapes
+}
+
+#[uniffi::export]
+fn echo_drawing(drawing: Drawing) -> Drawing {
+    drawing
+}
+
+#[uniffi::export]
+fn echo_drawing_v2(drawing: DrawingV2) -> DrawingV2 {
+    drawing
+}
+
+uniffi::include_scaffolding!("unknown-enum-variants");
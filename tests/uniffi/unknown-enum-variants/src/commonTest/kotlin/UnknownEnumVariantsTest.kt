@@ -0,0 +1,62 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import io.kotest.assertions.throwables.shouldThrow
+import io.kotest.matchers.shouldBe
+import io.kotest.matchers.string.shouldContain
+import unknown_enum_variants.*
+import kotlin.test.Test
+
+class UnknownEnumVariantsTest {
+    @Test
+    fun knownVariantsRoundTrip() {
+        echoColors(listOf(Color.RED, Color.GREEN)) shouldBe listOf(Color.RED, Color.GREEN)
+        echoShape(Shape.Circle(1.0)) shouldBe Shape.Circle(1.0)
+        echoDrawing(Drawing(Shape.Square(2.0), "square")) shouldBe Drawing(Shape.Square(2.0), "square")
+    }
+
+    @Test
+    fun unknownFlatVariantsAreReadInsideOtherValues() {
+        val buffer = FfiConverterSequenceTypeColorV2.lowerIntoRustBuffer(listOf(ColorV2.BLUE, ColorV2.RED))
+        FfiConverterSequenceTypeColor.lift(buffer) shouldBe listOf(Color.UNKNOWN, Color.RED)
+    }
+
+    @Test
+    fun unknownVariantsWithFieldsAreReadAtTheTopLevel() {
+        val buffer = FfiConverterTypeShapeV2.lowerIntoRustBuffer(ShapeV2.Rectangle(1.0, 2.0))
+        FfiConverterTypeShape.lift(buffer) shouldBe Shape.Unknown(3)
+    }
+
+    @Test
+    fun unknownVariantsWithFieldsInsideRecordsThrow() {
+        val buffer = FfiConverterTypeDrawingV2.lowerIntoRustBuffer(
+            DrawingV2(ShapeV2.Rectangle(1.0, 2.0), "rectangle"),
+        )
+        val exception = shouldThrow<RuntimeException> {
+            FfiConverterTypeDrawing.lift(buffer)
+        }
+        exception.message shouldContain "unknown variant 3 of Shape inside another value"
+    }
+
+    @Test
+    fun unknownVariantsWithFieldsInsideListsThrow() {
+        val buffer = FfiConverterSequenceTypeShapeV2.lowerIntoRustBuffer(
+            listOf(ShapeV2.Rectangle(1.0, 2.0), ShapeV2.Circle(1.0)),
+        )
+        val exception = shouldThrow<RuntimeException> {
+            FfiConverterSequenceTypeShape.lift(buffer)
+        }
+        exception.message shouldContain "unknown variant 3 of Shape inside another value"
+    }
+
+    @Test
+    fun unknownVariantsCantBePassedToRust() {
+        shouldThrow<IllegalArgumentException> {
+            echoShape(Shape.Unknown(3))
+        }
+        shouldThrow<IllegalArgumentException> {
+            echoColors(listOf(Color.UNKNOWN))
+        }
+    }
+}